@@ -6,6 +6,8 @@ pub enum Resize {
     Fill,
 }
 
+/// Box widgets are laid out in, in logical pixels. It's drawn in the
+/// physical pixels of the target, see [`Constraints::to_physical`].
 #[derive(Debug, Clone, Copy, Default, PartialEq)]
 pub struct Constraints {
     pub x: i32,
@@ -30,6 +32,54 @@ impl Constraints {
         }
     }
 
+    /// Smallest box containing both boxes, an empty box contains nothing.
+    pub fn union(&self, other: Constraints) -> Constraints {
+        if self.width <= 0 || self.height <= 0 {
+            return other;
+        }
+        if other.width <= 0 || other.height <= 0 {
+            return *self;
+        }
+
+        let x = self.x.min(other.x);
+        let y = self.y.min(other.y);
+        Constraints {
+            x,
+            y,
+            width: (self.x + self.width).max(other.x + other.width) - x,
+            height: (self.y + self.height).max(other.y + other.height) - y,
+            ..*self
+        }
+    }
+
+    pub fn intersect(&self, other: Constraints) -> Constraints {
+        let x = self.x.max(other.x);
+        let y = self.y.max(other.y);
+        Constraints {
+            x,
+            y,
+            width: (self.x + self.width).min(other.x + other.width) - x,
+            height: (self.y + self.height).min(other.y + other.height) - y,
+            ..*self
+        }
+        .check()
+    }
+
+    /// This box in physical pixels, `scale` of them per logical pixel. The
+    /// edges are rounded, boxes side by side stay side by side.
+    pub fn to_physical(&self, scale: f64) -> Constraints {
+        let round = |v: i32| (v as f64 * scale).round() as i32;
+        let x = round(self.x);
+        let y = round(self.y);
+        Constraints {
+            x,
+            y,
+            width: round(self.x + self.width) - x,
+            height: round(self.y + self.height) - y,
+            ..*self
+        }
+    }
+
     pub fn in_box(&self, x: f64, y: f64) -> bool {
         x > self.x as f64
             && x < (self.x + self.width) as f64
@@ -41,3 +91,80 @@ impl Constraints {
 pub trait Draw {
     fn draw(&mut self, constraints: Constraints) -> bool;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(x: i32, y: i32, width: i32, height: i32) -> Constraints {
+        Constraints {
+            x,
+            y,
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn union() {
+        let a = area(0, 0, 10, 10);
+        let b = area(20, 5, 10, 10);
+        assert_eq!(a.union(b), area(0, 0, 30, 15));
+        assert_eq!(b.union(a), area(0, 0, 30, 15));
+        assert_eq!(a.union(area(2, 2, 4, 4)), a);
+    }
+
+    #[test]
+    fn union_empty() {
+        let a = area(5, 5, 10, 10);
+        assert_eq!(a.union(area(100, 100, 0, 0)), a);
+        assert_eq!(area(-50, -50, 0, 10).union(a), a);
+        assert_eq!(
+            Constraints::default().union(Constraints::default()).width,
+            0
+        );
+    }
+
+    #[test]
+    fn intersect() {
+        let a = area(0, 0, 10, 10);
+        assert_eq!(a.intersect(area(5, 5, 10, 10)), area(5, 5, 5, 5));
+        assert_eq!(a.intersect(area(2, 2, 4, 4)), area(2, 2, 4, 4));
+    }
+
+    #[test]
+    fn intersect_disjoint() {
+        let overlap = area(0, 0, 10, 10).intersect(area(20, 20, 10, 10));
+        assert_eq!((overlap.width, overlap.height), (0, 0));
+        // touching edges share nothing
+        let overlap = area(0, 0, 10, 10).intersect(area(10, 0, 10, 10));
+        assert_eq!(overlap.width, 0);
+    }
+
+    #[test]
+    fn to_physical_whole_scale() {
+        assert_eq!(area(1, 2, 3, 4).to_physical(1.0), area(1, 2, 3, 4));
+        assert_eq!(area(1, 2, 3, 4).to_physical(2.0), area(2, 4, 6, 8));
+    }
+
+    #[test]
+    fn to_physical_rounds_edges() {
+        // 1.5 -> 2, 4.5 -> 5
+        assert_eq!(area(1, 1, 2, 2).to_physical(1.5), area(2, 2, 3, 3));
+        assert_eq!(area(0, 0, 1, 1).to_physical(1.25), area(0, 0, 1, 1));
+    }
+
+    #[test]
+    fn to_physical_keeps_neighbours_adjacent() {
+        for scale in [1.25, 1.5, 1.75, 2.5] {
+            let left = area(0, 0, 3, 5).to_physical(scale);
+            let right = area(3, 0, 4, 5).to_physical(scale);
+            assert_eq!(left.x + left.width, right.x, "scale {scale}");
+            assert_eq!(
+                area(0, 0, 7, 5).to_physical(scale).width,
+                left.width + right.width
+            );
+        }
+    }
+}
@@ -1,11 +1,13 @@
 use super::{
-    draw::Draw,
+    draw::{Constraints, Draw},
     event::{Event, EventHandler},
     widget::WidgetBuilder,
 };
 
 pub trait Window<E: Event>: EventHandler<E> + Draw {
     fn frame(&mut self);
+    /// Requests a frame repainting only `area` of the window.
+    fn damage(&mut self, area: Constraints);
     fn close(&mut self);
 }
 
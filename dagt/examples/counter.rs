@@ -75,11 +75,7 @@ fn main() {
                     use GlobalEvent::*;
                     use KeyboardEvent::*;
                     match event {
-                        Keyboard(KeyPressed {
-                            key: _,
-                            keysym,
-                            utf8: _,
-                        }) => match *keysym {
+                        Keyboard(KeyPressed { keysym, .. }) => match *keysym {
                             Keysym::q => {
                                 println!("Closing window");
                                 window.close();
@@ -12,17 +12,15 @@ fn main() {
                     ),
                 )
                 .handle_event(|window, event| match event {
-                    GlobalEvent::Keyboard(KeyboardEvent::KeyPressed {
-                        key: _,
-                        keysym,
-                        utf8: _,
-                    }) => match *keysym {
-                        Keysym::q => {
-                            window.close();
-                            true
+                    GlobalEvent::Keyboard(KeyboardEvent::KeyPressed { keysym, .. }) => {
+                        match *keysym {
+                            Keysym::q => {
+                                window.close();
+                                true
+                            }
+                            _ => false,
                         }
-                        _ => false,
-                    },
+                    }
                     _ => false,
                 }),
         )
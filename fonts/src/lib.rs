@@ -4,6 +4,9 @@ use std::{collections::HashMap, fs::File};
 
 pub mod reader;
 
+/// Font used by the widgets and the profiler overlay.
+pub const DEFAULT_FONT: &str = "/usr/share/fonts/TTF/JetBrainsMonoNerdFontMono-Bold.ttf";
+
 #[derive(Clone)]
 pub struct Font {
     glyphs: HashMap<u32, GlyphData>,
@@ -472,6 +475,6 @@ pub mod tests {
 
     #[test]
     fn load() {
-        let _font = Font::load("/usr/share/fonts/TTF/JetBrainsMonoNerdFontMono-Bold.ttf");
+        let _font = Font::load(DEFAULT_FONT);
     }
 }
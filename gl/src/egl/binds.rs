@@ -1,14 +1,32 @@
 use super::types::*;
+use crate::{
+    dyn_binds, dyn_ext_binds,
+    error::{Error, Result},
+    loader::Library,
+};
+use std::{
+    ffi::{c_char, c_void, CString},
+    sync::OnceLock,
+};
+
+dyn_binds!(
+    pub struct EglFns;
+
+    pub fn eglGetProcAddress(procname: *const c_char) -> *mut c_void;
 
-#[link(name = "EGL")]
-extern "C" {
     pub fn eglGetError() -> EGLint;
 
     pub fn eglGetDisplay(native_display: NativeDisplayType) -> EGLDisplay;
     pub fn eglGetCurrentDisplay() -> EGLDisplay;
+    pub fn eglGetPlatformDisplay(
+        platform: EGLenum,
+        native_display: *mut c_void,
+        attrib_list: *const EGLAttrib,
+    ) -> EGLDisplay;
     pub fn eglInitialize(display: EGLDisplay, major: *mut EGLint, minor: *mut EGLint)
         -> EglBoolean;
     pub fn eglTerminate(display: EGLDisplay) -> EglBoolean;
+    pub fn eglQueryString(display: EGLDisplay, name: EGLint) -> *const c_char;
 
     pub fn eglGetConfigs(
         display: EGLDisplay,
@@ -23,6 +41,12 @@ extern "C" {
         config_size: EGLint,
         num_config: *mut EGLint,
     ) -> EglBoolean;
+    pub fn eglGetConfigAttrib(
+        display: EGLDisplay,
+        config: EGLConfig,
+        attribute: EGLint,
+        value: *mut EGLint,
+    ) -> EglBoolean;
 
     pub fn eglCreateWindowSurface(
         display: EGLDisplay,
@@ -30,6 +54,11 @@ extern "C" {
         native_window: NativeWindowType,
         attrib_list: *const EGLint,
     ) -> EGLSurface;
+    pub fn eglCreatePbufferSurface(
+        display: EGLDisplay,
+        config: EGLConfig,
+        attrib_list: *const EGLint,
+    ) -> EGLSurface;
 
     pub fn eglCreateContext(
         display: EGLDisplay,
@@ -46,5 +75,92 @@ extern "C" {
     pub fn eglDestroyContext(display: EGLDisplay, context: EGLContext) -> EglBoolean;
 
     pub fn eglSwapBuffers(display: EGLDisplay, surface: EGLSurface) -> EglBoolean;
+    pub fn eglSwapInterval(display: EGLDisplay, interval: EGLint) -> EglBoolean;
+    pub fn eglQuerySurface(
+        display: EGLDisplay,
+        surface: EGLSurface,
+        attribute: EGLint,
+        value: *mut EGLint,
+    ) -> EglBoolean;
     pub fn eglDestroySurface(display: EGLDisplay, surface: EGLSurface) -> EglBoolean;
+);
+
+dyn_ext_binds!(
+    pub struct EglExtFns;
+
+    pub fn eglSwapBuffersWithDamageKHR(
+        display: EGLDisplay,
+        surface: EGLSurface,
+        rects: *const EGLint,
+        n_rects: EGLint,
+    ) -> EglBoolean;
+    pub fn eglSwapBuffersWithDamageEXT(
+        display: EGLDisplay,
+        surface: EGLSurface,
+        rects: *const EGLint,
+        n_rects: EGLint,
+    ) -> EglBoolean;
+);
+
+const LIBS: &[&str] = &["libEGL.so.1", "libEGL.so"];
+
+static EGL: OnceLock<std::result::Result<(EglFns, EglExtFns), String>> = OnceLock::new();
+
+fn init() -> &'static std::result::Result<(EglFns, EglExtFns), String> {
+    EGL.get_or_init(|| unsafe {
+        let lib = Library::open(LIBS)?;
+        let get_proc: unsafe extern "C" fn(*const c_char) -> *mut c_void = std::mem::transmute(
+            lib.sym("eglGetProcAddress")
+                .ok_or("missing `eglGetProcAddress`")?,
+        );
+        let get = |name: &str| {
+            lib.sym(name).or_else(|| {
+                let c_name = CString::new(name).unwrap();
+                let ptr = get_proc(c_name.as_ptr());
+                (!ptr.is_null()).then_some(ptr)
+            })
+        };
+
+        let fns = EglFns::load(get)?;
+        let ext = EglExtFns::load(get);
+        Ok((fns, ext))
+    })
+}
+
+/// Loads libEGL, reporting why it couldn't be loaded.
+///
+/// Every other function in this module loads it on first use and panics on failure.
+pub fn load() -> Result<()> {
+    match init() {
+        Ok(_) => Ok(()),
+        Err(e) => Err(Error::Load(e.clone(), "libEGL".to_owned())),
+    }
+}
+
+/// Optional EGL extension entry points.
+pub fn ext() -> &'static EglExtFns {
+    &EglFns::get_init().1
+}
+
+/// Address of the EGL or client API function `name`, if EGL could be loaded.
+pub fn proc_address(name: &str) -> Option<*mut c_void> {
+    init().as_ref().ok()?;
+    unsafe {
+        let c_name = CString::new(name).unwrap();
+        let ptr = eglGetProcAddress(c_name.as_ptr());
+        (!ptr.is_null()).then_some(ptr)
+    }
+}
+
+impl EglFns {
+    fn get_init() -> &'static (EglFns, EglExtFns) {
+        match init() {
+            Ok(fns) => fns,
+            Err(e) => panic!("failed to load libEGL: {e}"),
+        }
+    }
+
+    fn get() -> &'static EglFns {
+        &EglFns::get_init().0
+    }
 }
@@ -1,9 +1,24 @@
-use super::types::*;
+use super::{binds::*, types::*};
+use crate::error::{Error, Result};
+use attrs::EglConfAttr;
 
 pub mod attrs;
+pub mod chooser;
 
 #[derive(Clone, Copy)]
 pub struct EglConfig {
     pub(super) display: EGLDisplay,
     pub(super) config: EGLConfig,
 }
+
+impl EglConfig {
+    pub fn attrib(&self, attr: EglConfAttr) -> Result<i32> {
+        unsafe {
+            let mut value = 0;
+            match eglGetConfigAttrib(self.display, self.config, attr.into(), &mut value) {
+                EglBoolean::True => Ok(value),
+                EglBoolean::False => Err(Error::egl("eglGetConfigAttrib")),
+            }
+        }
+    }
+}
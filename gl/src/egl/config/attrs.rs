@@ -1,4 +1,4 @@
-use crate::{attr_bitstruct, attr_list, egl::types::*};
+use crate::{attr_bitstruct, attr_enum, attr_list, egl::types::*};
 
 pub const EGL_BUFFER_SIZE: EGLint = 0x3020;
 pub const EGL_ALPHA_SIZE: EGLint = 0x3021;
@@ -7,7 +7,19 @@ pub const EGL_GREEN_SIZE: EGLint = 0x3023;
 pub const EGL_RED_SIZE: EGLint = 0x3024;
 pub const EGL_DEPTH_SIZE: EGLint = 0x3025;
 pub const EGL_STENCIL_SIZE: EGLint = 0x3026;
+pub const EGL_CONFIG_CAVEAT: EGLint = 0x3027;
+pub const EGL_CONFIG_ID: EGLint = 0x3028;
+pub const EGL_MAX_PBUFFER_HEIGHT: EGLint = 0x302A;
+pub const EGL_MAX_PBUFFER_PIXELS: EGLint = 0x302B;
+pub const EGL_MAX_PBUFFER_WIDTH: EGLint = 0x302C;
+pub const EGL_NATIVE_RENDERABLE: EGLint = 0x302D;
+pub const EGL_NATIVE_VISUAL_ID: EGLint = 0x302E;
+pub const EGL_NATIVE_VISUAL_TYPE: EGLint = 0x302F;
+pub const EGL_SAMPLES: EGLint = 0x3031;
+pub const EGL_SAMPLE_BUFFERS: EGLint = 0x3032;
 pub const EGL_SURFACE_TYPE: EGLint = 0x3033;
+pub const EGL_RENDERABLE_TYPE: EGLint = 0x3040;
+pub const EGL_CONFORMANT: EGLint = 0x3042;
 
 attr_list!(
     pub struct EglConfAttrs {
@@ -19,6 +31,12 @@ attr_list!(
         depth_size: i32 = EGL_DEPTH_SIZE,
         stencil_size: i32 = EGL_STENCIL_SIZE,
         surface_type: EglSurfaceType = EGL_SURFACE_TYPE,
+        samples: i32 = EGL_SAMPLES,
+        sample_buffers: i32 = EGL_SAMPLE_BUFFERS,
+        renderable_type: EglRenderableType = EGL_RENDERABLE_TYPE,
+        config_caveat: EglConfigCaveat = EGL_CONFIG_CAVEAT,
+        native_renderable: bool = EGL_NATIVE_RENDERABLE,
+        native_visual_type: i32 = EGL_NATIVE_VISUAL_TYPE,
     }
 );
 
@@ -33,3 +51,55 @@ attr_bitstruct!(
         window = EGL_WINDOW_BIT,
     }
 );
+
+pub const EGL_OPENGL_ES_BIT: EGLint = 0x01;
+pub const EGL_OPENVG_BIT: EGLint = 0x02;
+pub const EGL_OPENGL_ES2_BIT: EGLint = 0x04;
+pub const EGL_OPENGL_BIT: EGLint = 0x08;
+pub const EGL_OPENGL_ES3_BIT: EGLint = 0x40;
+
+attr_bitstruct!(
+    pub bitstruct EglRenderableType(0) {
+        opengl_es = EGL_OPENGL_ES_BIT,
+        openvg = EGL_OPENVG_BIT,
+        opengl_es2 = EGL_OPENGL_ES2_BIT,
+        opengl = EGL_OPENGL_BIT,
+        opengl_es3 = EGL_OPENGL_ES3_BIT,
+    }
+);
+
+pub const EGL_SLOW_CONFIG: EGLint = 0x3050;
+pub const EGL_NON_CONFORMANT_CONFIG: EGLint = 0x3051;
+
+attr_enum!(
+    pub enum EglConfigCaveat {
+        NoCaveat = EGL_NONE,
+        Slow = EGL_SLOW_CONFIG,
+        NonConformant = EGL_NON_CONFORMANT_CONFIG,
+    }
+);
+
+attr_enum!(
+    pub enum EglConfAttr {
+        BufferSize = EGL_BUFFER_SIZE,
+        RedSize = EGL_RED_SIZE,
+        GreenSize = EGL_GREEN_SIZE,
+        BlueSize = EGL_BLUE_SIZE,
+        AlphaSize = EGL_ALPHA_SIZE,
+        DepthSize = EGL_DEPTH_SIZE,
+        StencilSize = EGL_STENCIL_SIZE,
+        ConfigCaveat = EGL_CONFIG_CAVEAT,
+        ConfigId = EGL_CONFIG_ID,
+        MaxPbufferHeight = EGL_MAX_PBUFFER_HEIGHT,
+        MaxPbufferPixels = EGL_MAX_PBUFFER_PIXELS,
+        MaxPbufferWidth = EGL_MAX_PBUFFER_WIDTH,
+        NativeRenderable = EGL_NATIVE_RENDERABLE,
+        NativeVisualId = EGL_NATIVE_VISUAL_ID,
+        NativeVisualType = EGL_NATIVE_VISUAL_TYPE,
+        Samples = EGL_SAMPLES,
+        SampleBuffers = EGL_SAMPLE_BUFFERS,
+        SurfaceType = EGL_SURFACE_TYPE,
+        RenderableType = EGL_RENDERABLE_TYPE,
+        Conformant = EGL_CONFORMANT,
+    }
+);
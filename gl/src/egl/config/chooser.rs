@@ -0,0 +1,148 @@
+use super::{
+    attrs::{EglConfAttr, EglConfAttrs, EglConfigCaveat, EglRenderableType, EglSurfaceType},
+    EglConfig,
+};
+use crate::egl::display::EglDisplay;
+
+/// Picks the best config for the requested framebuffer, relaxing the request
+/// when nothing matches: multisampling is reduced first, then alpha is dropped,
+/// and finally the stencil buffer.
+#[derive(Debug, Clone, Copy)]
+pub struct EglConfChooser {
+    alpha: bool,
+    depth_size: i32,
+    stencil_size: i32,
+    samples: i32,
+    surface_type: EglSurfaceType,
+    renderable_type: EglRenderableType,
+}
+
+impl EglConfChooser {
+    /// RGBA8 with an 8 bit stencil buffer, no depth buffer and no multisampling,
+    /// for OpenGL ES 3 window surfaces.
+    pub fn new() -> Self {
+        EglConfChooser {
+            alpha: true,
+            depth_size: 0,
+            stencil_size: 8,
+            samples: 0,
+            surface_type: EglSurfaceType::new().window(),
+            renderable_type: EglRenderableType::new().opengl_es3(),
+        }
+    }
+
+    pub fn alpha(mut self, alpha: bool) -> Self {
+        self.alpha = alpha;
+        self
+    }
+
+    pub fn depth_size(mut self, depth_size: i32) -> Self {
+        self.depth_size = depth_size;
+        self
+    }
+
+    pub fn stencil_size(mut self, stencil_size: i32) -> Self {
+        self.stencil_size = stencil_size;
+        self
+    }
+
+    /// Samples per pixel, `0` disables multisampling.
+    pub fn samples(mut self, samples: i32) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    pub fn surface_type(mut self, surface_type: EglSurfaceType) -> Self {
+        self.surface_type = surface_type;
+        self
+    }
+
+    pub fn renderable_type(mut self, renderable_type: EglRenderableType) -> Self {
+        self.renderable_type = renderable_type;
+        self
+    }
+
+    pub fn choose(&self, display: &EglDisplay) -> Option<EglConfig> {
+        self.fallbacks().into_iter().find_map(|request| {
+            let mut confs = display.get_confs(Some(&request.attrs())).ok()?;
+            confs.sort_by_key(|conf| request.rank(conf));
+            confs.first().copied()
+        })
+    }
+
+    fn fallbacks(&self) -> Vec<EglConfChooser> {
+        let mut samples = vec![self.samples];
+        let mut s = self.samples;
+        while s > 0 {
+            s = if s > 2 { s / 2 } else { 0 };
+            samples.push(s);
+        }
+
+        let mut alphas = vec![self.alpha];
+        if self.alpha {
+            alphas.push(false);
+        }
+
+        let mut list = Vec::new();
+        for alpha in alphas {
+            for samples in &samples {
+                list.push(EglConfChooser {
+                    alpha,
+                    samples: *samples,
+                    ..*self
+                });
+            }
+        }
+        if self.stencil_size > 0 {
+            list.push(EglConfChooser {
+                alpha: false,
+                samples: 0,
+                stencil_size: 0,
+                ..*self
+            });
+        }
+        list
+    }
+
+    fn attrs(&self) -> EglConfAttrs {
+        let attrs = EglConfAttrs::new()
+            .red_size(8)
+            .green_size(8)
+            .blue_size(8)
+            .alpha_size(if self.alpha { 8 } else { 0 })
+            .depth_size(self.depth_size)
+            .stencil_size(self.stencil_size)
+            .surface_type(self.surface_type)
+            .renderable_type(self.renderable_type);
+
+        if self.samples > 0 {
+            attrs.sample_buffers(1).samples(self.samples)
+        } else {
+            attrs
+        }
+    }
+
+    /// Lower is better: configs without caveats, then exact color sizes, then
+    /// sample counts and depth/stencil sizes closest to the request.
+    fn rank(&self, conf: &EglConfig) -> (bool, i32, i32, i32) {
+        let attr = |attr| conf.attrib(attr).unwrap_or(0);
+
+        let caveat = attr(EglConfAttr::ConfigCaveat) != i32::from(EglConfigCaveat::NoCaveat);
+        let alpha = if self.alpha { 8 } else { 0 };
+        let color = (attr(EglConfAttr::RedSize) - 8).abs()
+            + (attr(EglConfAttr::GreenSize) - 8).abs()
+            + (attr(EglConfAttr::BlueSize) - 8).abs()
+            + (attr(EglConfAttr::AlphaSize) - alpha).abs();
+        let samples = (attr(EglConfAttr::Samples) - self.samples).abs();
+        let buffers = (attr(EglConfAttr::DepthSize) - self.depth_size).abs()
+            + (attr(EglConfAttr::StencilSize) - self.stencil_size).abs();
+
+        (caveat, color, samples, buffers)
+    }
+}
+
+impl Default for EglConfChooser {
+    fn default() -> Self {
+        Self::new()
+    }
+}
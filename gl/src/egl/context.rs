@@ -1,37 +1,63 @@
-use super::{binds::*, surface::EglSurface, types::*};
-use crate::error::{Error, Result};
+use super::{
+    binds::*,
+    surface::{EglSurface, EGL_NO_SURFACE},
+    types::*,
+};
+use crate::{
+    error::{Error, Result},
+    gles::context::GlContextState,
+};
+use std::sync::Arc;
 
 pub mod attrs;
 
 pub const EGL_NO_CONTEXT: EGLContext = 0x0 as EGLContext;
 
+/// Context destroyed once it and every GL object created in it are dropped.
 pub struct EglContext {
     pub(super) display: EGLDisplay,
     pub(super) context: EGLContext,
+    pub(super) state: Arc<GlContextState>,
 }
 
 impl EglContext {
     pub fn make_current(&self, draw: &EglSurface, read: &EglSurface) -> Result<()> {
         unsafe {
             match eglMakeCurrent(self.display, draw.surface, read.surface, self.context) {
-                EglBoolean::True => Ok(()),
+                EglBoolean::True => {
+                    GlContextState::make_current(&self.state);
+                    Ok(())
+                }
                 EglBoolean::False => Err(Error::egl("eglMakeCurrent")),
             }
         }
     }
 
-    pub fn term(&self) -> Result<()> {
+    /// Makes the context current without any surface bound, requires
+    /// `EGL_KHR_surfaceless_context`. Rendering goes to framebuffer objects only.
+    pub fn make_current_surfaceless(&self) -> Result<()> {
         unsafe {
-            match eglDestroyContext(self.display, self.context) {
-                EglBoolean::True => Ok(()),
-                EglBoolean::False => Err(Error::egl("eglDestroyContext")),
+            match eglMakeCurrent(self.display, EGL_NO_SURFACE, EGL_NO_SURFACE, self.context) {
+                EglBoolean::True => {
+                    GlContextState::make_current(&self.state);
+                    Ok(())
+                }
+                EglBoolean::False => Err(Error::egl("eglMakeCurrent")),
             }
         }
     }
-}
 
-impl Drop for EglContext {
-    fn drop(&mut self) {
-        let _ = self.term();
+    /// Makes no context current on this thread, so that another thread can
+    /// make this one current.
+    pub fn release(&self) -> Result<()> {
+        unsafe {
+            match eglMakeCurrent(self.display, EGL_NO_SURFACE, EGL_NO_SURFACE, EGL_NO_CONTEXT) {
+                EglBoolean::True => {
+                    GlContextState::release(&self.state);
+                    Ok(())
+                }
+                EglBoolean::False => Err(Error::egl("eglMakeCurrent")),
+            }
+        }
     }
 }
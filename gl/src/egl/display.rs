@@ -2,16 +2,26 @@ use super::{
     binds::*,
     config::{attrs::EglConfAttrs, EglConfig},
     context::{attrs::EglCtxAttrs, EglContext, EGL_NO_CONTEXT},
-    surface::{EglSurface, EGL_NO_SURFACE},
+    platform::EglPlatform,
+    surface::{attrs::EglSurfAttrs, EglSurface, EGL_NO_SURFACE},
     types::{EglBoolean::*, *},
 };
-use crate::error::{Error, Result};
+use crate::{
+    error::{Error, Result},
+    gles::context::GlContextState,
+};
+use std::{collections::HashSet, ffi::CStr};
 
 pub const EGL_NO_DISPLAY: EGLDisplay = 0x0 as EGLDisplay;
 pub const EGL_DEFAULT_DISPLAY: NativeDisplayType = 0x0 as NativeDisplayType;
 
+pub const EGL_VENDOR: EGLint = 0x3053;
+pub const EGL_VERSION: EGLint = 0x3054;
+pub const EGL_EXTENSIONS: EGLint = 0x3055;
+pub const EGL_CLIENT_APIS: EGLint = 0x308D;
+
 pub struct EglDisplay {
-    display: EGLDisplay,
+    pub(super) display: EGLDisplay,
 }
 
 impl EglDisplay {
@@ -25,6 +35,33 @@ impl EglDisplay {
         }
     }
 
+    /// Gets a display of `platform`, see [`EglPlatform`] for what `native_display` is.
+    ///
+    /// # Safety
+    ///
+    /// `native_display` has to be [`EGL_DEFAULT_DISPLAY`] or point to a live
+    /// native display of `platform`, which outlives the returned display.
+    pub unsafe fn get_platform(
+        platform: EglPlatform,
+        native_display: NativeDisplayType,
+    ) -> Option<EglDisplay> {
+        let display = eglGetPlatformDisplay(
+            i32::from(platform) as EGLenum,
+            native_display,
+            std::ptr::null(),
+        );
+        match display {
+            EGL_NO_DISPLAY => None,
+            _ => Some(EglDisplay { display }),
+        }
+    }
+
+    /// Gets a display that needs neither a display server nor a GPU device,
+    /// rendering through Mesa into pbuffers or surfaceless contexts.
+    pub fn get_surfaceless() -> Option<EglDisplay> {
+        unsafe { EglDisplay::get_platform(EglPlatform::SurfacelessMesa, EGL_DEFAULT_DISPLAY) }
+    }
+
     pub fn get_default() -> Option<EglDisplay> {
         EglDisplay::get(EGL_DEFAULT_DISPLAY)
     }
@@ -50,6 +87,38 @@ impl EglDisplay {
         }
     }
 
+    pub fn vendor(&self) -> Result<String> {
+        query_string(self.display, EGL_VENDOR)
+    }
+
+    pub fn version(&self) -> Result<String> {
+        query_string(self.display, EGL_VERSION)
+    }
+
+    pub fn client_apis(&self) -> Result<String> {
+        query_string(self.display, EGL_CLIENT_APIS)
+    }
+
+    /// Extensions supported by the display, available once it's initialized.
+    pub fn extensions(&self) -> Result<HashSet<String>> {
+        query_string(self.display, EGL_EXTENSIONS)
+            .map(|list| list.split_whitespace().map(str::to_owned).collect())
+    }
+
+    pub fn has_extension(&self, name: &str) -> bool {
+        self.extensions()
+            .map(|list| list.contains(name))
+            .unwrap_or(false)
+    }
+
+    /// Client extensions that don't depend on a display, such as the supported
+    /// platforms. Empty if EGL doesn't support `EGL_EXT_client_extensions`.
+    pub fn client_extensions() -> HashSet<String> {
+        query_string(EGL_NO_DISPLAY, EGL_EXTENSIONS)
+            .map(|list| list.split_whitespace().map(str::to_owned).collect())
+            .unwrap_or_default()
+    }
+
     pub fn get_confs_num(&self, attrs: Option<&EglConfAttrs>) -> Result<i32> {
         unsafe {
             let mut func_name = "eglChooseConfig";
@@ -130,10 +199,27 @@ impl EglDisplay {
             if surface == EGL_NO_SURFACE {
                 Err(Error::egl("eglCreateWindowSurface"))
             } else {
-                Ok(EglSurface {
-                    display: self.display,
-                    surface,
-                })
+                Ok(EglSurface::new(self, surface))
+            }
+        }
+    }
+
+    /// Creates an offscreen surface, `config` has to support pbuffers.
+    pub fn create_pbuffer_surface(
+        &self,
+        config: &EglConfig,
+        attrs: Option<&EglSurfAttrs>,
+    ) -> Result<EglSurface> {
+        unsafe {
+            let attrs = match attrs {
+                Some(list) => list.as_ptr(),
+                None => std::ptr::null(),
+            };
+            let surface = eglCreatePbufferSurface(self.display, config.config, attrs);
+            if surface == EGL_NO_SURFACE {
+                Err(Error::egl("eglCreatePbufferSurface"))
+            } else {
+                Ok(EglSurface::new(self, surface))
             }
         }
     }
@@ -160,6 +246,7 @@ impl EglDisplay {
                 Ok(EglContext {
                     display: self.display,
                     context,
+                    state: GlContextState::new(self.display, context),
                 })
             }
         }
@@ -180,3 +267,14 @@ impl Drop for EglDisplay {
         let _ = self.term();
     }
 }
+
+fn query_string(display: EGLDisplay, name: EGLint) -> Result<String> {
+    unsafe {
+        let ptr = eglQueryString(display, name);
+        if ptr.is_null() {
+            Err(Error::egl("eglQueryString"))
+        } else {
+            Ok(CStr::from_ptr(ptr).to_string_lossy().into_owned())
+        }
+    }
+}
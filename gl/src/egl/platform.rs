@@ -0,0 +1,16 @@
+use crate::{attr_enum, egl::types::*};
+
+pub const EGL_PLATFORM_GBM_KHR: EGLint = 0x31D7;
+pub const EGL_PLATFORM_WAYLAND_KHR: EGLint = 0x31D8;
+pub const EGL_PLATFORM_SURFACELESS_MESA: EGLint = 0x31DD;
+
+attr_enum!(
+    pub enum EglPlatform {
+        /// Native display is a `gbm_device *`.
+        Gbm = EGL_PLATFORM_GBM_KHR,
+        /// Native display is a `wl_display *`.
+        Wayland = EGL_PLATFORM_WAYLAND_KHR,
+        /// No native display, only pbuffers and surfaceless contexts can be used.
+        SurfacelessMesa = EGL_PLATFORM_SURFACELESS_MESA,
+    }
+);
@@ -1,14 +1,30 @@
-use super::{binds::*, types::*};
+use super::{binds::*, display::EglDisplay, types::*};
 use crate::error::{Error, Result};
 
+pub mod attrs;
+
 pub const EGL_NO_SURFACE: EGLSurface = 0x0 as EGLSurface;
 
+pub const EGL_BUFFER_AGE_EXT: EGLint = 0x313D;
+
 pub struct EglSurface {
     pub(super) display: EGLDisplay,
     pub(super) surface: EGLSurface,
+    buffer_age: bool,
+    swap_damage: bool,
 }
 
 impl EglSurface {
+    pub(super) fn new(display: &EglDisplay, surface: EGLSurface) -> EglSurface {
+        EglSurface {
+            display: display.display,
+            surface,
+            buffer_age: display.has_extension("EGL_EXT_buffer_age"),
+            swap_damage: display.has_extension("EGL_KHR_swap_buffers_with_damage")
+                || display.has_extension("EGL_EXT_swap_buffers_with_damage"),
+        }
+    }
+
     pub fn swap(&self) -> Result<()> {
         unsafe {
             match eglSwapBuffers(self.display, self.surface) {
@@ -18,6 +34,59 @@ impl EglSurface {
         }
     }
 
+    /// Swaps buffers telling the compositor only `rects` changed, each rect is
+    /// `[x, y, width, height]` with the origin in the bottom left corner.
+    ///
+    /// Falls back to a full swap without `EGL_KHR_swap_buffers_with_damage`
+    /// or `EGL_EXT_swap_buffers_with_damage`.
+    pub fn swap_with_damage(&self, rects: &[[i32; 4]]) -> Result<()> {
+        let ext = ext();
+        let swap = ext
+            .eglSwapBuffersWithDamageKHR
+            .or(ext.eglSwapBuffersWithDamageEXT);
+
+        match swap {
+            Some(swap) if self.swap_damage && !rects.is_empty() => unsafe {
+                match swap(
+                    self.display,
+                    self.surface,
+                    rects.as_ptr() as *const EGLint,
+                    rects.len() as EGLint,
+                ) {
+                    EglBoolean::True => Ok(()),
+                    EglBoolean::False => Err(Error::egl("eglSwapBuffersWithDamage")),
+                }
+            },
+            _ => self.swap(),
+        }
+    }
+
+    /// Number of frames ago the back buffer was last drawn, `0` when its
+    /// contents are unknown and the whole surface has to be redrawn.
+    pub fn buffer_age(&self) -> Result<i32> {
+        if !self.buffer_age {
+            return Ok(0);
+        }
+        unsafe {
+            let mut age = 0;
+            match eglQuerySurface(self.display, self.surface, EGL_BUFFER_AGE_EXT, &mut age) {
+                EglBoolean::True => Ok(age),
+                EglBoolean::False => Err(Error::egl("eglQuerySurface")),
+            }
+        }
+    }
+
+    /// Minimum number of vertical blanks between swaps of the current
+    /// context's surface, `0` disables vsync.
+    pub fn set_swap_interval(&self, interval: i32) -> Result<()> {
+        unsafe {
+            match eglSwapInterval(self.display, interval) {
+                EglBoolean::True => Ok(()),
+                EglBoolean::False => Err(Error::egl("eglSwapInterval")),
+            }
+        }
+    }
+
     pub fn term(&self) -> Result<()> {
         unsafe {
             match eglDestroySurface(self.display, self.surface) {
@@ -0,0 +1,11 @@
+use crate::{attr_list, egl::types::*};
+
+pub const EGL_HEIGHT: EGLint = 0x3056;
+pub const EGL_WIDTH: EGLint = 0x3057;
+
+attr_list!(
+    pub struct EglSurfAttrs {
+        width: i32 = EGL_WIDTH,
+        height: i32 = EGL_HEIGHT,
+    }
+);
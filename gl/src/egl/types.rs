@@ -2,6 +2,7 @@ use std::ffi::c_void;
 
 pub type EGLint = i32;
 pub type EGLenum = u32;
+pub type EGLAttrib = isize;
 
 pub type NativeDisplayType = *mut c_void;
 pub type NativeWindowType = *mut c_void;
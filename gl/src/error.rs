@@ -1,6 +1,9 @@
 use crate::{
     egl::{binds::eglGetError, error::EGLError},
-    gles::{binds::glGetError, error::GLError},
+    gles::{
+        binds::glGetError,
+        error::{GLError, GLFramebufferStatus},
+    },
 };
 use std::fmt::Display;
 
@@ -10,7 +13,11 @@ pub type Result<T> = std::result::Result<T, Error>;
 pub enum Error {
     Egl(EGLError, String),
     Gl(GLError, String),
+    Framebuffer(GLFramebufferStatus, String),
     Compile(String, String),
+    Load(String, String),
+    NoContext(String),
+    Unsupported(String),
 }
 
 impl Error {
@@ -29,6 +36,16 @@ impl Error {
         Error::Gl(glGetError().try_into().unwrap(), func.to_owned())
     }
 
+    pub unsafe fn framebuffer(status: i32, func: &str) -> Result<()> {
+        let status = status
+            .try_into()
+            .unwrap_or_else(|status| GLFramebufferStatus::Unknown(status as u32));
+        match status {
+            GLFramebufferStatus::Complete => Ok(()),
+            s => Err(Error::Framebuffer(s, func.to_owned())),
+        }
+    }
+
     pub unsafe fn compile_shader(shader_name: &str, e: String) -> Error {
         Error::Compile(e, format!("shader `{}`", shader_name.to_owned()))
     }
@@ -44,7 +61,11 @@ impl Display for Error {
         match self {
             Egl(e, func) => write!(f, "error in `{func}`: {e}"),
             Gl(e, func) => write!(f, "error in `{func}`: {e}"),
+            Framebuffer(e, func) => write!(f, "error in `{func}`: {e}"),
             Compile(e, func) => write!(f, "error in `{func}`: {e}"),
+            Load(e, lib) => write!(f, "failed to load `{lib}`: {e}"),
+            NoContext(func) => write!(f, "`{func}` called without a current context"),
+            Unsupported(ext) => write!(f, "`{ext}` is not supported"),
         }
     }
 }
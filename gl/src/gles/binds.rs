@@ -1,8 +1,14 @@
 use super::types::*;
-use std::ffi::c_void;
+use crate::{
+    dyn_binds, dyn_ext_binds, egl,
+    error::{Error, Result},
+    loader::Library,
+};
+use std::{ffi::c_void, sync::OnceLock};
+
+dyn_binds!(
+    pub struct GlesFns;
 
-#[link(name = "GLESv2")]
-extern "C" {
     pub fn glGetError() -> GLenum;
 
     pub fn glColorMask(red: GLboolean, green: GLboolean, blue: GLboolean, alpha: GLboolean);
@@ -13,15 +19,26 @@ extern "C" {
 
     pub fn glGenVertexArrays(n: GLsizei, arrays: *mut GLuint);
     pub fn glBindVertexArray(array: GLuint);
+    pub fn glDeleteVertexArrays(n: GLsizei, arrays: *const GLuint);
 
     pub fn glGenBuffers(n: GLsizei, buffers: *mut GLuint);
     pub fn glBindBuffer(target: GLenum, buffer: GLuint);
+    pub fn glDeleteBuffers(n: GLsizei, buffers: *const GLuint);
     pub fn glBufferData(target: GLenum, size: GLsizeiptr, data: *const c_void, usage: GLenum);
+    pub fn glBufferSubData(target: GLenum, offset: GLintptr, size: GLsizeiptr, data: *const c_void);
+    pub fn glMapBufferRange(
+        target: GLenum,
+        offset: GLintptr,
+        length: GLsizeiptr,
+        access: GLbitfield,
+    ) -> *mut c_void;
+    pub fn glUnmapBuffer(target: GLenum) -> GLboolean;
 
     pub fn glDrawArrays(mode: GLenum, first: GLint, count: GLsizei);
     pub fn glDrawElements(mode: GLenum, count: GLsizei, el_type: GLenum, indices: *const c_void);
 
     pub fn glViewport(x: GLint, y: GLint, width: GLsizei, height: GLsizei);
+    pub fn glScissor(x: GLint, y: GLint, width: GLsizei, height: GLsizei);
 
     pub fn glVertexAttribPointer(
         index: GLuint,
@@ -41,6 +58,7 @@ extern "C" {
         length: *const GLint,
     );
     pub fn glCompileShader(shader: GLuint);
+    pub fn glDeleteShader(shader: GLuint);
     pub fn glGetShaderiv(shader: GLuint, pname: GLenum, params: *mut GLint);
     pub fn glGetShaderInfoLog(
         shader: GLuint,
@@ -53,6 +71,7 @@ extern "C" {
     pub fn glUseProgram(program: GLuint);
     pub fn glAttachShader(program: GLuint, shader: GLuint);
     pub fn glLinkProgram(program: GLuint);
+    pub fn glDeleteProgram(program: GLuint);
     pub fn glGetProgramiv(program: GLuint, pname: GLenum, params: *mut GLint);
     pub fn glGetProgramInfoLog(
         program: GLuint,
@@ -63,6 +82,72 @@ extern "C" {
 
     pub fn glGetUniformLocation(program: GLuint, name: *const GLchar) -> GLint;
 
+    pub fn glGetActiveUniform(
+        program: GLuint,
+        index: GLuint,
+        bufSize: GLsizei,
+        length: *mut GLsizei,
+        size: *mut GLint,
+        el_type: *mut GLenum,
+        name: *mut GLchar,
+    );
+    pub fn glGetUniformBlockIndex(program: GLuint, uniformBlockName: *const GLchar) -> GLuint;
+    pub fn glUniformBlockBinding(
+        program: GLuint,
+        uniformBlockIndex: GLuint,
+        uniformBlockBinding: GLuint,
+    );
+    pub fn glBindBufferBase(target: GLenum, index: GLuint, buffer: GLuint);
+    pub fn glBindBufferRange(
+        target: GLenum,
+        index: GLuint,
+        buffer: GLuint,
+        offset: GLintptr,
+        size: GLsizeiptr,
+    );
+
+    pub fn glUniform1i(location: GLint, v0: GLint);
+    pub fn glUniform2i(location: GLint, v0: GLint, v1: GLint);
+    pub fn glUniform3i(location: GLint, v0: GLint, v1: GLint, v2: GLint);
+    pub fn glUniform4i(location: GLint, v0: GLint, v1: GLint, v2: GLint, v3: GLint);
+
+    pub fn glUniform1ui(location: GLint, v0: GLuint);
+    pub fn glUniform2ui(location: GLint, v0: GLuint, v1: GLuint);
+    pub fn glUniform3ui(location: GLint, v0: GLuint, v1: GLuint, v2: GLuint);
+    pub fn glUniform4ui(location: GLint, v0: GLuint, v1: GLuint, v2: GLuint, v3: GLuint);
+
+    pub fn glUniform1fv(location: GLint, count: GLsizei, value: *const GLfloat);
+    pub fn glUniform2fv(location: GLint, count: GLsizei, value: *const GLfloat);
+    pub fn glUniform3fv(location: GLint, count: GLsizei, value: *const GLfloat);
+    pub fn glUniform4fv(location: GLint, count: GLsizei, value: *const GLfloat);
+    pub fn glUniform1iv(location: GLint, count: GLsizei, value: *const GLint);
+    pub fn glUniform2iv(location: GLint, count: GLsizei, value: *const GLint);
+    pub fn glUniform3iv(location: GLint, count: GLsizei, value: *const GLint);
+    pub fn glUniform4iv(location: GLint, count: GLsizei, value: *const GLint);
+    pub fn glUniform1uiv(location: GLint, count: GLsizei, value: *const GLuint);
+    pub fn glUniform2uiv(location: GLint, count: GLsizei, value: *const GLuint);
+    pub fn glUniform3uiv(location: GLint, count: GLsizei, value: *const GLuint);
+    pub fn glUniform4uiv(location: GLint, count: GLsizei, value: *const GLuint);
+
+    pub fn glUniformMatrix2fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    );
+    pub fn glUniformMatrix3fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    );
+    pub fn glUniformMatrix4fv(
+        location: GLint,
+        count: GLsizei,
+        transpose: GLboolean,
+        value: *const GLfloat,
+    );
+
     pub fn glUniform1f(location: GLint, v0: GLfloat);
     pub fn glUniform2f(location: GLint, v0: GLfloat, v1: GLfloat);
     pub fn glUniform3f(location: GLint, v0: GLfloat, v1: GLfloat, v2: GLfloat);
@@ -78,4 +163,180 @@ extern "C" {
     pub fn glStencilMask(mask: GLuint);
     pub fn glStencilFunc(func: GLenum, ref_value: GLint, mask: GLuint);
     pub fn glStencilOp(sfail: GLenum, dpfail: GLenum, dppass: GLenum);
+
+    pub fn glGetIntegerv(pname: GLenum, data: *mut GLint);
+    pub fn glGetString(name: GLenum) -> *const GLubyte;
+    pub fn glGetStringi(name: GLenum, index: GLuint) -> *const GLubyte;
+    pub fn glPixelStorei(pname: GLenum, param: GLint);
+    pub fn glReadPixels(
+        x: GLint,
+        y: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        el_type: GLenum,
+        data: *mut c_void,
+    );
+
+    pub fn glGenTextures(n: GLsizei, textures: *mut GLuint);
+    pub fn glDeleteTextures(n: GLsizei, textures: *const GLuint);
+    pub fn glActiveTexture(texture: GLenum);
+    pub fn glBindTexture(target: GLenum, texture: GLuint);
+    pub fn glTexImage2D(
+        target: GLenum,
+        level: GLint,
+        internalformat: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        border: GLint,
+        format: GLenum,
+        el_type: GLenum,
+        data: *const c_void,
+    );
+    pub fn glTexSubImage2D(
+        target: GLenum,
+        level: GLint,
+        xoffset: GLint,
+        yoffset: GLint,
+        width: GLsizei,
+        height: GLsizei,
+        format: GLenum,
+        el_type: GLenum,
+        data: *const c_void,
+    );
+    pub fn glTexParameteri(target: GLenum, pname: GLenum, param: GLint);
+    pub fn glGenerateMipmap(target: GLenum);
+
+    pub fn glGenFramebuffers(n: GLsizei, framebuffers: *mut GLuint);
+    pub fn glDeleteFramebuffers(n: GLsizei, framebuffers: *const GLuint);
+    pub fn glBindFramebuffer(target: GLenum, framebuffer: GLuint);
+    pub fn glFramebufferTexture2D(
+        target: GLenum,
+        attachment: GLenum,
+        textarget: GLenum,
+        texture: GLuint,
+        level: GLint,
+    );
+    pub fn glFramebufferRenderbuffer(
+        target: GLenum,
+        attachment: GLenum,
+        renderbuffertarget: GLenum,
+        renderbuffer: GLuint,
+    );
+    pub fn glCheckFramebufferStatus(target: GLenum) -> GLenum;
+    pub fn glBlitFramebuffer(
+        srcX0: GLint,
+        srcY0: GLint,
+        srcX1: GLint,
+        srcY1: GLint,
+        dstX0: GLint,
+        dstY0: GLint,
+        dstX1: GLint,
+        dstY1: GLint,
+        mask: GLbitfield,
+        filter: GLenum,
+    );
+
+    pub fn glGenQueries(n: GLsizei, ids: *mut GLuint);
+    pub fn glDeleteQueries(n: GLsizei, ids: *const GLuint);
+    pub fn glBeginQuery(target: GLenum, id: GLuint);
+    pub fn glEndQuery(target: GLenum);
+    pub fn glGetQueryObjectuiv(id: GLuint, pname: GLenum, params: *mut GLuint);
+
+    pub fn glGenRenderbuffers(n: GLsizei, renderbuffers: *mut GLuint);
+    pub fn glDeleteRenderbuffers(n: GLsizei, renderbuffers: *const GLuint);
+    pub fn glBindRenderbuffer(target: GLenum, renderbuffer: GLuint);
+    pub fn glRenderbufferStorage(
+        target: GLenum,
+        internalformat: GLenum,
+        width: GLsizei,
+        height: GLsizei,
+    );
+    pub fn glRenderbufferStorageMultisample(
+        target: GLenum,
+        samples: GLsizei,
+        internalformat: GLenum,
+        width: GLsizei,
+        height: GLsizei,
+    );
+);
+
+dyn_ext_binds!(
+    pub struct GlesExtFns;
+
+    pub fn glDebugMessageCallback(callback: GLDEBUGPROC, userParam: *const c_void);
+    pub fn glDebugMessageControl(
+        source: GLenum,
+        msg_type: GLenum,
+        severity: GLenum,
+        count: GLsizei,
+        ids: *const GLuint,
+        enabled: GLboolean,
+    );
+    pub fn glObjectLabel(identifier: GLenum, name: GLuint, length: GLsizei, label: *const GLchar);
+    pub fn glPushDebugGroup(source: GLenum, id: GLuint, length: GLsizei, message: *const GLchar);
+    pub fn glPopDebugGroup();
+
+    pub fn glDebugMessageCallbackKHR(callback: GLDEBUGPROC, userParam: *const c_void);
+    pub fn glDebugMessageControlKHR(
+        source: GLenum,
+        msg_type: GLenum,
+        severity: GLenum,
+        count: GLsizei,
+        ids: *const GLuint,
+        enabled: GLboolean,
+    );
+    pub fn glObjectLabelKHR(
+        identifier: GLenum,
+        name: GLuint,
+        length: GLsizei,
+        label: *const GLchar,
+    );
+    pub fn glPushDebugGroupKHR(source: GLenum, id: GLuint, length: GLsizei, message: *const GLchar);
+    pub fn glPopDebugGroupKHR();
+
+    pub fn glGetQueryObjectui64vEXT(id: GLuint, pname: GLenum, params: *mut u64);
+);
+
+const LIBS: &[&str] = &["libGLESv2.so.2", "libGLESv2.so"];
+
+static GLES: OnceLock<std::result::Result<(GlesFns, GlesExtFns), String>> = OnceLock::new();
+
+fn init() -> &'static std::result::Result<(GlesFns, GlesExtFns), String> {
+    GLES.get_or_init(|| unsafe {
+        let lib = Library::open(LIBS)?;
+        let get = |name: &str| lib.sym(name).or_else(|| egl::binds::proc_address(name));
+
+        let fns = GlesFns::load(get)?;
+        let ext = GlesExtFns::load(get);
+        Ok((fns, ext))
+    })
+}
+
+/// Loads libGLESv2, reporting why it couldn't be loaded.
+///
+/// Every other function in this module loads it on first use and panics on failure.
+pub fn load() -> Result<()> {
+    match init() {
+        Ok(_) => Ok(()),
+        Err(e) => Err(Error::Load(e.clone(), "libGLESv2".to_owned())),
+    }
+}
+
+/// Optional GLES extension entry points.
+pub fn ext() -> &'static GlesExtFns {
+    &GlesFns::get_init().1
+}
+
+impl GlesFns {
+    fn get_init() -> &'static (GlesFns, GlesExtFns) {
+        match init() {
+            Ok(fns) => fns,
+            Err(e) => panic!("failed to load libGLESv2: {e}"),
+        }
+    }
+
+    fn get() -> &'static GlesFns {
+        &GlesFns::get_init().0
+    }
 }
@@ -0,0 +1,220 @@
+use super::{
+    binds::*,
+    context::{GlCtxRef, GlObject},
+    types::*,
+};
+use crate::error::{Error, Result};
+use std::ffi::c_void;
+
+#[derive(Debug)]
+pub struct GlBuffer {
+    pub(super) buffer: GLuint,
+    pub(super) target: GlBuffTarget,
+    usage: GlBuffUsage,
+    size: usize,
+    ctx: GlCtxRef,
+}
+
+impl GlBuffer {
+    pub fn create(target: GlBuffTarget) -> Result<GlBuffer> {
+        unsafe {
+            let ctx = GlCtxRef::current("glGenBuffers")?;
+            let mut buffer = 0;
+            glGenBuffers(1, &mut buffer);
+            Error::gl("glGenBuffers")?;
+
+            Ok(GlBuffer {
+                buffer,
+                target,
+                usage: GlBuffUsage::StaticDraw,
+                size: 0,
+                ctx,
+            })
+        }
+    }
+
+    /// Binds the buffer to its target, an element array buffer gets attached
+    /// to the bound vertex array.
+    pub fn bind(&self) {
+        unsafe {
+            glBindBuffer(self.target.into(), self.buffer);
+        }
+    }
+
+    /// Binds the buffer for writing its storage and returns the target it's
+    /// bound to. Element array buffers go to the copy write target, binding
+    /// them to their own would replace the one of the bound vertex array.
+    fn bind_write(&self) -> GLenum {
+        let target = match self.target {
+            GlBuffTarget::ElementArrayBuffer => GlBuffTarget::CopyWriteBuffer,
+            target => target,
+        }
+        .into();
+        unsafe {
+            glBindBuffer(target, self.buffer);
+        }
+        target
+    }
+
+    /// Binds the buffer to indexed `binding` point of its target.
+    pub fn bind_base(&self, binding: u32) -> Result<()> {
+        unsafe {
+            glBindBufferBase(self.target.into(), binding, self.buffer);
+            Error::gl("glBindBufferBase")
+        }
+    }
+
+    /// Binds `size` bytes starting at `offset` to indexed `binding` point of its target.
+    pub fn bind_range(&self, binding: u32, offset: usize, size: usize) -> Result<()> {
+        unsafe {
+            glBindBufferRange(
+                self.target.into(),
+                binding,
+                self.buffer,
+                offset as GLintptr,
+                size as GLsizeiptr,
+            );
+            Error::gl("glBindBufferRange")
+        }
+    }
+
+    /// Size of the buffer storage in bytes.
+    pub fn size(&self) -> usize {
+        self.size
+    }
+
+    /// Reallocates the buffer storage and fills it with `data`.
+    pub fn data<T>(&mut self, data: &[T], usage: GlBuffUsage) -> Result<()> {
+        self.alloc(
+            std::mem::size_of_val(data),
+            data.as_ptr() as *const c_void,
+            usage,
+        )
+    }
+
+    /// Reallocates the buffer storage to `size` bytes with undefined contents.
+    pub fn reserve(&mut self, size: usize, usage: GlBuffUsage) -> Result<()> {
+        self.alloc(size, std::ptr::null(), usage)
+    }
+
+    /// Writes `data` at byte `offset` without reallocating.
+    pub fn sub_data<T>(&self, offset: usize, data: &[T]) -> Result<()> {
+        unsafe {
+            let target = self.bind_write();
+            glBufferSubData(
+                target,
+                offset as GLintptr,
+                std::mem::size_of_val(data) as GLsizeiptr,
+                data.as_ptr() as *const c_void,
+            );
+            Error::gl("glBufferSubData")
+        }
+    }
+
+    /// Detaches the current storage so the driver doesn't have to wait for
+    /// draws still reading from it before the buffer is written again.
+    pub fn orphan(&mut self) -> Result<()> {
+        self.alloc(self.size, std::ptr::null(), self.usage)
+    }
+
+    /// Replaces the whole contents with `data`, reusing the storage when it fits.
+    ///
+    /// Dynamic and stream buffers are orphaned first; growing the buffer or
+    /// changing `usage` reallocates it.
+    pub fn update<T>(&mut self, data: &[T], usage: GlBuffUsage) -> Result<()> {
+        let size = std::mem::size_of_val(data);
+        if size > self.size || self.usage != usage {
+            return self.data(data, usage);
+        }
+
+        if !matches!(
+            usage,
+            GlBuffUsage::StaticDraw | GlBuffUsage::StaticRead | GlBuffUsage::StaticCopy
+        ) {
+            self.orphan()?;
+        }
+        self.sub_data(0, data)
+    }
+
+    fn alloc(&mut self, size: usize, data: *const c_void, usage: GlBuffUsage) -> Result<()> {
+        unsafe {
+            let target = self.bind_write();
+            glBufferData(target, size as GLsizeiptr, data, usage.into());
+            Error::gl("glBufferData")?;
+
+            self.size = size;
+            self.usage = usage;
+            Ok(())
+        }
+    }
+}
+
+impl Drop for GlBuffer {
+    fn drop(&mut self) {
+        self.ctx.delete(GlObject::Buffer(self.buffer));
+    }
+}
+
+/// Buffer written front to back by successive frames or batches.
+///
+/// Each [`GlStreamBuffer::push`] maps the next free range unsynchronized, so
+/// ranges already handed to the GPU are never waited on. When the end is
+/// reached the storage is orphaned and writing starts over at the beginning.
+#[derive(Debug)]
+pub struct GlStreamBuffer {
+    buffer: GlBuffer,
+    head: usize,
+}
+
+impl GlStreamBuffer {
+    pub fn create(target: GlBuffTarget, size: usize) -> Result<GlStreamBuffer> {
+        let mut buffer = GlBuffer::create(target)?;
+        buffer.reserve(size, GlBuffUsage::StreamDraw)?;
+
+        Ok(GlStreamBuffer { buffer, head: 0 })
+    }
+
+    pub fn buffer(&self) -> &GlBuffer {
+        &self.buffer
+    }
+
+    /// Copies `data` into the buffer and returns the byte offset it was written at.
+    ///
+    /// Data larger than the whole buffer grows it.
+    pub fn push<T: Copy>(&mut self, data: &[T]) -> Result<usize> {
+        let size = std::mem::size_of_val(data);
+        let align = std::mem::align_of::<T>().max(4);
+        let mut offset = self.head.div_ceil(align) * align;
+
+        if size > self.buffer.size() {
+            self.buffer.reserve(size, GlBuffUsage::StreamDraw)?;
+            offset = 0;
+        } else if offset + size > self.buffer.size() {
+            self.buffer.orphan()?;
+            offset = 0;
+        }
+
+        unsafe {
+            let target = self.buffer.bind_write();
+            let ptr = glMapBufferRange(
+                target,
+                offset as GLintptr,
+                size as GLsizeiptr,
+                GlMapAccess::new()
+                    .write()
+                    .invalidate_range()
+                    .unsynchronized()
+                    .into(),
+            );
+            if ptr.is_null() {
+                return Err(Error::gl_err("glMapBufferRange"));
+            }
+            std::ptr::copy_nonoverlapping(data.as_ptr() as *const u8, ptr as *mut u8, size);
+            glUnmapBuffer(target);
+            Error::gl("glUnmapBuffer")?;
+        }
+
+        self.head = offset + size;
+        Ok(offset)
+    }
+}
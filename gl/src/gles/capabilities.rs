@@ -0,0 +1,92 @@
+use super::{binds::*, types::*};
+use crate::error::{Error, Result};
+use std::{collections::HashSet, ffi::CStr};
+
+/// Implementation information and limits of the current context.
+#[derive(Debug, Clone)]
+pub struct Capabilities {
+    pub vendor: String,
+    pub renderer: String,
+    pub version: String,
+    pub glsl_version: String,
+    pub major_version: i32,
+    pub minor_version: i32,
+    pub extensions: HashSet<String>,
+
+    pub max_texture_size: i32,
+    pub max_renderbuffer_size: i32,
+    pub max_viewport_dims: (i32, i32),
+    pub max_samples: i32,
+    pub max_vertex_attribs: i32,
+    pub max_texture_units: i32,
+    pub max_combined_texture_units: i32,
+    pub max_uniform_buffer_bindings: i32,
+    pub max_uniform_block_size: i32,
+}
+
+impl Capabilities {
+    pub fn query() -> Result<Capabilities> {
+        let mut extensions = HashSet::new();
+        for i in 0..get_integer(GL_NUM_EXTENSIONS)? {
+            unsafe {
+                let ext = glGetStringi(GL_EXTENSIONS, i as GLuint);
+                Error::gl("glGetStringi")?;
+                extensions.insert(CStr::from_ptr(ext as _).to_string_lossy().into_owned());
+            }
+        }
+
+        let mut viewport = [0; 2];
+        unsafe {
+            glGetIntegerv(GL_MAX_VIEWPORT_DIMS, viewport.as_mut_ptr());
+            Error::gl("glGetIntegerv")?;
+        }
+
+        Ok(Capabilities {
+            vendor: get_string(GL_VENDOR)?,
+            renderer: get_string(GL_RENDERER)?,
+            version: get_string(GL_VERSION)?,
+            glsl_version: get_string(GL_SHADING_LANGUAGE_VERSION)?,
+            major_version: get_integer(GL_MAJOR_VERSION)?,
+            minor_version: get_integer(GL_MINOR_VERSION)?,
+            extensions,
+
+            max_texture_size: get_integer(GL_MAX_TEXTURE_SIZE)?,
+            max_renderbuffer_size: get_integer(GL_MAX_RENDERBUFFER_SIZE)?,
+            max_viewport_dims: (viewport[0], viewport[1]),
+            max_samples: get_integer(GL_MAX_SAMPLES)?,
+            max_vertex_attribs: get_integer(GL_MAX_VERTEX_ATTRIBS)?,
+            max_texture_units: get_integer(GL_MAX_TEXTURE_IMAGE_UNITS)?,
+            max_combined_texture_units: get_integer(GL_MAX_COMBINED_TEXTURE_IMAGE_UNITS)?,
+            max_uniform_buffer_bindings: get_integer(GL_MAX_UNIFORM_BUFFER_BINDINGS)?,
+            max_uniform_block_size: get_integer(GL_MAX_UNIFORM_BLOCK_SIZE)?,
+        })
+    }
+
+    pub fn has_extension(&self, name: &str) -> bool {
+        self.extensions.contains(name)
+    }
+
+    /// Whether the context is at least version `major`.`minor`.
+    pub fn version_at_least(&self, major: i32, minor: i32) -> bool {
+        (self.major_version, self.minor_version) >= (major, minor)
+    }
+}
+
+fn get_string(name: GLenum) -> Result<String> {
+    unsafe {
+        let ptr = glGetString(name);
+        if ptr.is_null() {
+            return Err(Error::gl_err("glGetString"));
+        }
+        Ok(CStr::from_ptr(ptr as _).to_string_lossy().into_owned())
+    }
+}
+
+fn get_integer(name: GLenum) -> Result<i32> {
+    unsafe {
+        let mut value = 0;
+        glGetIntegerv(name, &mut value);
+        Error::gl("glGetIntegerv")?;
+        Ok(value)
+    }
+}
@@ -0,0 +1,125 @@
+use super::{binds::*, types::*};
+use crate::{
+    egl::{
+        binds::eglDestroyContext,
+        types::{EGLContext, EGLDisplay},
+    },
+    error::{Error, Result},
+};
+use std::{
+    cell::RefCell,
+    sync::{Arc, Mutex, Weak},
+};
+
+/// GL object name together with the call that deletes it.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub(crate) enum GlObject {
+    Buffer(GLuint),
+    VertexArray(GLuint),
+    Shader(GLuint),
+    Program(GLuint),
+    Texture(GLuint),
+    Framebuffer(GLuint),
+    Renderbuffer(GLuint),
+    Query(GLuint),
+}
+
+impl GlObject {
+    unsafe fn delete(self) {
+        use GlObject::*;
+        match self {
+            Buffer(id) => glDeleteBuffers(1, &id),
+            VertexArray(id) => glDeleteVertexArrays(1, &id),
+            Shader(id) => glDeleteShader(id),
+            Program(id) => glDeleteProgram(id),
+            Texture(id) => glDeleteTextures(1, &id),
+            Framebuffer(id) => glDeleteFramebuffers(1, &id),
+            Renderbuffer(id) => glDeleteRenderbuffers(1, &id),
+            Query(id) => glDeleteQueries(1, &id),
+        }
+    }
+}
+
+/// Context shared between its `EglContext` and the objects created in it,
+/// destroyed once they're all dropped. Objects can't outlive their context.
+///
+/// Objects dropped while their context isn't current are queued and deleted
+/// the next time it's made current, or with the context.
+#[derive(Debug)]
+pub struct GlContextState {
+    display: EGLDisplay,
+    context: EGLContext,
+    pending: Mutex<Vec<GlObject>>,
+}
+
+// EGL handles can be used from any thread, the GL calls only happen on the
+// thread the context is current on
+unsafe impl Send for GlContextState {}
+unsafe impl Sync for GlContextState {}
+
+thread_local! {
+    static CURRENT: RefCell<Weak<GlContextState>> = const { RefCell::new(Weak::new()) };
+}
+
+impl GlContextState {
+    pub(crate) fn new(display: EGLDisplay, context: EGLContext) -> Arc<GlContextState> {
+        Arc::new(GlContextState {
+            display,
+            context,
+            pending: Mutex::new(Vec::new()),
+        })
+    }
+
+    /// Records `state` as current on this thread and deletes its queued objects.
+    pub fn make_current(state: &Arc<GlContextState>) {
+        CURRENT.with(|cur| *cur.borrow_mut() = Arc::downgrade(state));
+
+        let pending = std::mem::take(&mut *state.pending.lock().unwrap());
+        for object in pending {
+            unsafe { object.delete() }
+        }
+    }
+
+    /// Forgets `state` as current on this thread.
+    pub fn release(state: &Arc<GlContextState>) {
+        CURRENT.with(|cur| {
+            if cur.borrow().ptr_eq(&Arc::downgrade(state)) {
+                *cur.borrow_mut() = Weak::new();
+            }
+        });
+    }
+}
+
+impl Drop for GlContextState {
+    fn drop(&mut self) {
+        // queued objects go with the context, a current context is only
+        // destroyed once released
+        unsafe {
+            eglDestroyContext(self.display, self.context);
+        }
+    }
+}
+
+/// Reference from an object to the context it was created in, keeping the
+/// context alive.
+#[derive(Debug)]
+pub(crate) struct GlCtxRef(Arc<GlContextState>);
+
+impl GlCtxRef {
+    /// Context current on this thread, objects can only be created in a
+    /// context made current through `EglContext`.
+    pub(crate) fn current(func: &str) -> Result<GlCtxRef> {
+        CURRENT.with(|cur| match cur.borrow().upgrade() {
+            Some(state) => Ok(GlCtxRef(state)),
+            None => Err(Error::NoContext(func.to_owned())),
+        })
+    }
+
+    pub(crate) fn delete(&self, object: GlObject) {
+        if CURRENT.with(|cur| std::ptr::eq(cur.borrow().as_ptr(), Arc::as_ptr(&self.0))) {
+            unsafe { object.delete() }
+        } else {
+            self.0.pending.lock().unwrap().push(object);
+        }
+    }
+}
@@ -0,0 +1,213 @@
+use super::{binds::*, func, types::*};
+use crate::error::{Error, Result};
+use std::{
+    ffi::{c_void, CStr},
+    sync::{
+        atomic::{AtomicBool, Ordering},
+        RwLock,
+    },
+};
+
+/// Message reported by the debug output.
+#[derive(Debug, Clone, Copy)]
+pub struct GlDebugMessage<'a> {
+    pub source: GlDebugSource,
+    pub msg_type: GlDebugType,
+    pub id: u32,
+    pub severity: GlDebugSeverity,
+    pub message: &'a str,
+}
+
+/// Receives every message of the debug output that passed the filters.
+pub trait GlDebugLogger: Send + Sync {
+    fn log(&self, msg: &GlDebugMessage);
+}
+
+impl<F: Fn(&GlDebugMessage) + Send + Sync> GlDebugLogger for F {
+    fn log(&self, msg: &GlDebugMessage) {
+        self(msg)
+    }
+}
+
+/// Logger printing messages to stderr, skipping notifications and debug
+/// group markers.
+pub struct StderrLogger;
+
+impl GlDebugLogger for StderrLogger {
+    fn log(&self, msg: &GlDebugMessage) {
+        if msg.severity == GlDebugSeverity::Notification {
+            return;
+        }
+        eprintln!(
+            "GL {:?} {:?} {:?} [{}]: {}",
+            msg.severity, msg.source, msg.msg_type, msg.id, msg.message
+        );
+    }
+}
+
+static LOGGER: RwLock<Option<Box<dyn GlDebugLogger>>> = RwLock::new(None);
+static ENABLED: AtomicBool = AtomicBool::new(false);
+
+/// Enables the debug output of the current context, routing its messages to
+/// `logger`.
+///
+/// Messages are delivered synchronously, from inside the failing call. Needs
+/// GLES 3.2 or `GL_KHR_debug`, the context should be created with
+/// `EglCtxAttrs::debug` for drivers to report anything.
+pub fn enable(logger: impl GlDebugLogger + 'static) -> Result<()> {
+    let ext = ext();
+    let Some(callback) = ext.glDebugMessageCallback.or(ext.glDebugMessageCallbackKHR) else {
+        return Err(Error::Unsupported("GL_KHR_debug".to_owned()));
+    };
+
+    *LOGGER.write().unwrap() = Some(Box::new(logger));
+    unsafe {
+        callback(Some(message_callback), std::ptr::null());
+        Error::gl("glDebugMessageCallback")?;
+    }
+    func::enable(GlCap::DebugOutput);
+    func::enable(GlCap::DebugOutputSynchronous);
+    ENABLED.store(true, Ordering::Release);
+
+    Ok(())
+}
+
+/// Disables the debug output and drops the logger.
+pub fn disable() {
+    if ENABLED.swap(false, Ordering::AcqRel) {
+        func::disable(GlCap::DebugOutput);
+        *LOGGER.write().unwrap() = None;
+    }
+}
+
+pub fn is_enabled() -> bool {
+    ENABLED.load(Ordering::Acquire)
+}
+
+/// Enables or disables the messages matching all of the given filters,
+/// `None` matches any value.
+///
+/// Messages of low and notification severity are disabled by default.
+pub fn filter(
+    source: Option<GlDebugSource>,
+    msg_type: Option<GlDebugType>,
+    severity: Option<GlDebugSeverity>,
+    enabled: bool,
+) -> Result<()> {
+    let ext = ext();
+    let Some(control) = ext.glDebugMessageControl.or(ext.glDebugMessageControlKHR) else {
+        return Ok(());
+    };
+    unsafe {
+        control(
+            source.map_or(GL_DONT_CARE, |s| s.into()),
+            msg_type.map_or(GL_DONT_CARE, |t| t.into()),
+            severity.map_or(GL_DONT_CARE, |s| s.into()),
+            0,
+            std::ptr::null(),
+            enabled as GLboolean,
+        );
+        Error::gl("glDebugMessageControl")
+    }
+}
+
+/// Names an object in debug messages and graphics debuggers.
+///
+/// Does nothing unless the debug output is enabled.
+pub fn label(identifier: GlObjectType, name: GLuint, label: &str) -> Result<()> {
+    if !is_enabled() {
+        return Ok(());
+    }
+    let ext = ext();
+    let Some(object_label) = ext.glObjectLabel.or(ext.glObjectLabelKHR) else {
+        return Ok(());
+    };
+    unsafe {
+        object_label(
+            identifier.into(),
+            name,
+            label.len() as GLsizei,
+            label.as_ptr() as *const GLchar,
+        );
+        Error::gl("glObjectLabel")
+    }
+}
+
+/// Debug group popped when dropped.
+pub struct GlDebugGroup {
+    pushed: bool,
+}
+
+impl GlDebugGroup {
+    /// Pushes a debug group named `message`, grouping the calls made until
+    /// it's dropped.
+    ///
+    /// Does nothing unless the debug output is enabled, GLES isn't touched
+    /// then.
+    pub fn push(message: &str) -> GlDebugGroup {
+        if !is_enabled() {
+            return GlDebugGroup { pushed: false };
+        }
+        let ext = ext();
+        let push = ext.glPushDebugGroup.or(ext.glPushDebugGroupKHR);
+        let pushed = match push {
+            Some(push) => unsafe {
+                push(
+                    GL_DEBUG_SOURCE_APPLICATION,
+                    0,
+                    message.len() as GLsizei,
+                    message.as_ptr() as *const GLchar,
+                );
+                true
+            },
+            _ => false,
+        };
+        GlDebugGroup { pushed }
+    }
+}
+
+impl Drop for GlDebugGroup {
+    fn drop(&mut self) {
+        if !self.pushed {
+            return;
+        }
+        let ext = ext();
+        if let Some(pop) = ext.glPopDebugGroup.or(ext.glPopDebugGroupKHR) {
+            unsafe {
+                pop();
+            }
+        }
+    }
+}
+
+unsafe extern "C" fn message_callback(
+    source: GLenum,
+    msg_type: GLenum,
+    id: GLuint,
+    severity: GLenum,
+    length: GLsizei,
+    message: *const GLchar,
+    _user_param: *const c_void,
+) {
+    let message = if length < 0 {
+        CStr::from_ptr(message).to_string_lossy()
+    } else {
+        String::from_utf8_lossy(std::slice::from_raw_parts(
+            message as *const u8,
+            length as usize,
+        ))
+    };
+    let msg = GlDebugMessage {
+        source: source.try_into().unwrap_or(GlDebugSource::Other),
+        msg_type: msg_type.try_into().unwrap_or(GlDebugType::Other),
+        id,
+        severity: severity.try_into().unwrap_or(GlDebugSeverity::Notification),
+        message: &message,
+    };
+
+    if let Ok(logger) = LOGGER.read() {
+        if let Some(logger) = logger.as_ref() {
+            logger.log(&msg);
+        }
+    }
+}
@@ -25,3 +25,31 @@ raw_error!(
         OutOfMemory = GL_OUT_OF_MEMORY,
     }
 );
+
+pub const GL_FRAMEBUFFER_COMPLETE: GLenum = 0x8CD5;
+pub const GL_FRAMEBUFFER_UNDEFINED: GLenum = 0x8219;
+pub const GL_FRAMEBUFFER_INCOMPLETE_ATTACHMENT: GLenum = 0x8CD6;
+pub const GL_FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT: GLenum = 0x8CD7;
+pub const GL_FRAMEBUFFER_INCOMPLETE_DIMENSIONS: GLenum = 0x8CD9;
+pub const GL_FRAMEBUFFER_UNSUPPORTED: GLenum = 0x8CDD;
+pub const GL_FRAMEBUFFER_INCOMPLETE_MULTISAMPLE: GLenum = 0x8D56;
+
+raw_error!(
+    pub enum GLFramebufferStatus {
+        /// The framebuffer is complete.
+        Complete = GL_FRAMEBUFFER_COMPLETE,
+        /// The default framebuffer is bound but does not exist.
+        Undefined = GL_FRAMEBUFFER_UNDEFINED,
+        /// One of the framebuffer attachment points is incomplete.
+        IncompleteAttachment = GL_FRAMEBUFFER_INCOMPLETE_ATTACHMENT,
+        /// The framebuffer does not have at least one image attached to it.
+        IncompleteMissingAttachment = GL_FRAMEBUFFER_INCOMPLETE_MISSING_ATTACHMENT,
+        /// The attached images do not all have the same width and height.
+        IncompleteDimensions = GL_FRAMEBUFFER_INCOMPLETE_DIMENSIONS,
+        /// The combination of internal formats of the attached images is not supported.
+        Unsupported = GL_FRAMEBUFFER_UNSUPPORTED,
+        /// The attached renderbuffers do not all have the same number of samples.
+        IncompleteMultisample = GL_FRAMEBUFFER_INCOMPLETE_MULTISAMPLE,
+        _ => Unknown,
+    }
+);
@@ -0,0 +1,202 @@
+use super::{
+    binds::*,
+    context::{GlCtxRef, GlObject},
+    texture::GlTexture,
+    types::*,
+};
+use crate::error::{Error, Result};
+
+pub struct GlRenderbuffer {
+    renderbuffer: GLuint,
+    ctx: GlCtxRef,
+}
+
+impl GlRenderbuffer {
+    pub fn create() -> Result<GlRenderbuffer> {
+        unsafe {
+            let ctx = GlCtxRef::current("glGenRenderbuffers")?;
+            let mut renderbuffer = 0;
+            glGenRenderbuffers(1, &mut renderbuffer);
+            Error::gl("glGenRenderbuffers")?;
+
+            Ok(GlRenderbuffer { renderbuffer, ctx })
+        }
+    }
+
+    pub fn bind(&self) {
+        unsafe {
+            glBindRenderbuffer(GL_RENDERBUFFER, self.renderbuffer);
+        }
+    }
+
+    /// Allocates storage, multisampled when `samples` is greater than zero.
+    pub fn storage(
+        &self,
+        format: GlTexFormat,
+        width: i32,
+        height: i32,
+        samples: i32,
+    ) -> Result<()> {
+        unsafe {
+            self.bind();
+            if samples > 0 {
+                glRenderbufferStorageMultisample(
+                    GL_RENDERBUFFER,
+                    samples,
+                    format.into(),
+                    width,
+                    height,
+                );
+                Error::gl("glRenderbufferStorageMultisample")
+            } else {
+                glRenderbufferStorage(GL_RENDERBUFFER, format.into(), width, height);
+                Error::gl("glRenderbufferStorage")
+            }
+        }
+    }
+}
+
+impl Drop for GlRenderbuffer {
+    fn drop(&mut self) {
+        self.ctx.delete(GlObject::Renderbuffer(self.renderbuffer));
+    }
+}
+
+pub struct GlFramebuffer {
+    framebuffer: GLuint,
+    ctx: GlCtxRef,
+}
+
+impl GlFramebuffer {
+    pub fn create() -> Result<GlFramebuffer> {
+        unsafe {
+            let ctx = GlCtxRef::current("glGenFramebuffers")?;
+            let mut framebuffer = 0;
+            glGenFramebuffers(1, &mut framebuffer);
+            Error::gl("glGenFramebuffers")?;
+
+            Ok(GlFramebuffer { framebuffer, ctx })
+        }
+    }
+
+    pub fn bind(&self, target: GlFramebufferTarget) {
+        unsafe {
+            glBindFramebuffer(target.into(), self.framebuffer);
+        }
+    }
+
+    /// Binds the window system provided framebuffer to `target`.
+    pub fn bind_default(target: GlFramebufferTarget) {
+        unsafe {
+            glBindFramebuffer(target.into(), 0);
+        }
+    }
+
+    pub fn attach_texture(&self, attachment: GlAttachment, texture: &GlTexture) -> Result<()> {
+        unsafe {
+            self.bind(GlFramebufferTarget::Framebuffer);
+            glFramebufferTexture2D(
+                GL_FRAMEBUFFER,
+                attachment.into(),
+                texture.target.into(),
+                texture.texture,
+                0,
+            );
+            Error::gl("glFramebufferTexture2D")
+        }
+    }
+
+    pub fn attach_renderbuffer(
+        &self,
+        attachment: GlAttachment,
+        renderbuffer: &GlRenderbuffer,
+    ) -> Result<()> {
+        unsafe {
+            self.bind(GlFramebufferTarget::Framebuffer);
+            glFramebufferRenderbuffer(
+                GL_FRAMEBUFFER,
+                attachment.into(),
+                GL_RENDERBUFFER,
+                renderbuffer.renderbuffer,
+            );
+            Error::gl("glFramebufferRenderbuffer")
+        }
+    }
+
+    pub fn check(&self) -> Result<()> {
+        unsafe {
+            self.bind(GlFramebufferTarget::Framebuffer);
+            let status = glCheckFramebufferStatus(GL_FRAMEBUFFER);
+            Error::framebuffer(status, "glCheckFramebufferStatus")
+        }
+    }
+
+    /// Copies `src` of this framebuffer into `dst` of `target`, or of the
+    /// default framebuffer when `target` is `None`. Rects are `(x0, y0, x1, y1)`.
+    pub fn blit(
+        &self,
+        target: Option<&GlFramebuffer>,
+        src: (i32, i32, i32, i32),
+        dst: (i32, i32, i32, i32),
+        mask: GlClearMask,
+        filter: GlTexFilter,
+    ) -> Result<()> {
+        unsafe {
+            self.bind(GlFramebufferTarget::ReadFramebuffer);
+            match target {
+                Some(target) => target.bind(GlFramebufferTarget::DrawFramebuffer),
+                None => GlFramebuffer::bind_default(GlFramebufferTarget::DrawFramebuffer),
+            }
+            glBlitFramebuffer(
+                src.0,
+                src.1,
+                src.2,
+                src.3,
+                dst.0,
+                dst.1,
+                dst.2,
+                dst.3,
+                mask.into(),
+                filter.into(),
+            );
+            Error::gl("glBlitFramebuffer")
+        }
+    }
+}
+
+impl Drop for GlFramebuffer {
+    fn drop(&mut self) {
+        self.ctx.delete(GlObject::Framebuffer(self.framebuffer));
+    }
+}
+
+/// Framebuffers bound at the time of [`GlFramebufferBinding::current`],
+/// used to restore them after drawing offscreen.
+#[derive(Debug, Clone, Copy)]
+pub struct GlFramebufferBinding {
+    draw: GLuint,
+    read: GLuint,
+}
+
+impl GlFramebufferBinding {
+    pub fn current() -> GlFramebufferBinding {
+        unsafe {
+            let mut draw = 0;
+            let mut read = 0;
+            glGetIntegerv(GL_FRAMEBUFFER_BINDING, &mut draw);
+            glGetIntegerv(GL_READ_FRAMEBUFFER_BINDING, &mut read);
+
+            GlFramebufferBinding {
+                draw: draw as GLuint,
+                read: read as GLuint,
+            }
+        }
+    }
+
+    pub fn restore(&self) {
+        unsafe {
+            glBindFramebuffer(GL_DRAW_FRAMEBUFFER, self.draw);
+            glBindFramebuffer(GL_READ_FRAMEBUFFER, self.read);
+        }
+    }
+}
@@ -1,6 +1,16 @@
 use super::{binds::*, types::*};
 use crate::error::{Error, Result};
-use std::ffi::c_void;
+use std::{
+    ffi::c_void,
+    sync::atomic::{AtomicU32, Ordering},
+};
+
+static DRAW_CALLS: AtomicU32 = AtomicU32::new(0);
+
+/// Number of draw calls made so far, wrapping on overflow.
+pub fn draw_calls() -> u32 {
+    DRAW_CALLS.load(Ordering::Relaxed)
+}
 
 pub fn clear_color((r, g, b, a): (u8, u8, u8, u8)) {
     unsafe {
@@ -23,6 +33,7 @@ pub fn clear(mask: GlClearMask) -> Result<()> {
 pub fn draw_arrays(mode: GlDrawMode, first: i32, count: i32) -> Result<()> {
     unsafe {
         glDrawArrays(mode.into(), first, count);
+        DRAW_CALLS.fetch_add(1, Ordering::Relaxed);
         Error::gl("glDrawArrays")
     }
 }
@@ -35,6 +46,7 @@ pub fn draw_elements(mode: GlDrawMode, first: i32, count: i32) -> Result<()> {
             GlElType::UnsignedInt.into(),
             first as *const c_void,
         );
+        DRAW_CALLS.fetch_add(1, Ordering::Relaxed);
         Error::gl("glDrawElements")
     }
 }
@@ -46,6 +58,13 @@ pub fn view_port(x: i32, y: i32, width: i32, height: i32) -> Result<()> {
     }
 }
 
+pub fn scissor(x: i32, y: i32, width: i32, height: i32) -> Result<()> {
+    unsafe {
+        glScissor(x, y, width, height);
+        Error::gl("glScissor")
+    }
+}
+
 pub fn enable(cap: GlCap) {
     unsafe {
         glEnable(cap.into());
@@ -99,16 +118,41 @@ pub fn stencil_mask(mask: u32) {
     }
 }
 
-pub fn stencil_func(func: GlStencilFunc, ref_value: i32, mask: u32) {
+pub fn stencil_func(func: GlStencilFunc, ref_value: i32, mask: u32) -> Result<()> {
     unsafe {
         glStencilFunc(func.into(), ref_value, mask);
-        Error::gl("glStencilFunc").unwrap();
+        Error::gl("glStencilFunc")
     }
 }
 
-pub fn stencil_op(sfail: GlStencilOp, dpfail: GlStencilOp, dppass: GlStencilOp) {
+pub fn stencil_op(sfail: GlStencilOp, dpfail: GlStencilOp, dppass: GlStencilOp) -> Result<()> {
     unsafe {
         glStencilOp(sfail.into(), dpfail.into(), dppass.into());
-        Error::gl("glStencilOp").unwrap();
+        Error::gl("glStencilOp")
+    }
+}
+
+pub fn pixel_store(param: GlPixelStore, value: i32) -> Result<()> {
+    unsafe {
+        glPixelStorei(param.into(), value);
+        Error::gl("glPixelStorei")
+    }
+}
+
+/// Reads RGBA pixels of the bound read framebuffer, bottom row first.
+pub fn read_pixels(x: i32, y: i32, width: i32, height: i32) -> Result<Vec<u8>> {
+    let mut pixels = vec![0u8; (width.max(0) * height.max(0) * 4) as usize];
+    unsafe {
+        glReadPixels(
+            x,
+            y,
+            width,
+            height,
+            GlPixelFormat::Rgba.into(),
+            GlPixelType::UnsignedByte.into(),
+            pixels.as_mut_ptr() as *mut c_void,
+        );
+        Error::gl("glReadPixels")?;
     }
+    Ok(pixels)
 }
@@ -0,0 +1,293 @@
+use super::{
+    capabilities::Capabilities,
+    func,
+    query::{self, GlQuery},
+    types::GlQueryTarget,
+};
+use crate::error::Result;
+use std::{
+    cell::RefCell,
+    collections::VecDeque,
+    time::{Duration, Instant},
+};
+
+/// Frames kept waiting for their GPU timings before being dropped.
+const MAX_PENDING: usize = 4;
+
+/// Timings of a profiled scope, including its nested scopes.
+#[derive(Debug, Clone)]
+pub struct ScopeStats {
+    pub name: &'static str,
+    /// Number of scopes the scope is nested in, `0` for the outermost ones.
+    pub depth: usize,
+    pub cpu: Duration,
+    /// `None` without `GL_EXT_disjoint_timer_query`, or when the GPU was
+    /// disjoint during the frame.
+    pub gpu: Option<Duration>,
+    pub draw_calls: u32,
+}
+
+/// Timings of a whole frame and of the scopes run during it, in the order
+/// they were entered.
+#[derive(Debug, Clone)]
+pub struct FrameStats {
+    pub cpu: Duration,
+    pub gpu: Option<Duration>,
+    pub draw_calls: u32,
+    pub scopes: Vec<ScopeStats>,
+}
+
+struct Scope {
+    name: &'static str,
+    parent: Option<usize>,
+    depth: usize,
+    cpu_start: Instant,
+    cpu: Duration,
+    draw_calls_start: u32,
+    draw_calls: u32,
+}
+
+/// Time elapsed query covering the calls made while `scope` was the
+/// innermost open scope.
+struct Segment {
+    scope: usize,
+    query: GlQuery,
+}
+
+#[derive(Default)]
+struct Frame {
+    scopes: Vec<Scope>,
+    segments: Vec<Segment>,
+}
+
+struct Profiler {
+    timer: bool,
+    frame: Option<Frame>,
+    open: Vec<usize>,
+    pending: VecDeque<Frame>,
+    free: Vec<GlQuery>,
+    last: Option<FrameStats>,
+}
+
+thread_local! {
+    static PROFILER: RefCell<Option<Profiler>> = const { RefCell::new(None) };
+}
+
+/// Starts profiling frames drawn on this thread with the current context.
+///
+/// GPU times are measured with timer queries when the context supports
+/// `GL_EXT_disjoint_timer_query`, only CPU times and draw calls otherwise.
+pub fn enable() -> Result<()> {
+    let timer = Capabilities::query()?.has_extension("GL_EXT_disjoint_timer_query");
+    if timer {
+        // clear the disjoint flag left from before profiling
+        query::gpu_disjoint()?;
+    }
+    PROFILER.with(|p| {
+        *p.borrow_mut() = Some(Profiler {
+            timer,
+            frame: None,
+            open: Vec::new(),
+            pending: VecDeque::new(),
+            free: Vec::new(),
+            last: None,
+        })
+    });
+    Ok(())
+}
+
+pub fn disable() {
+    PROFILER.with(|p| *p.borrow_mut() = None);
+}
+
+pub fn is_enabled() -> bool {
+    PROFILER.with(|p| p.borrow().is_some())
+}
+
+/// Starts a frame, collecting the GPU timings of earlier frames that are
+/// ready.
+pub fn begin_frame() {
+    with(|p| {
+        p.resolve();
+        p.frame = Some(Frame::default());
+        p.open.clear();
+        p.push_scope("frame");
+    });
+}
+
+/// Ends the frame started by [`begin_frame`], closing any scopes left open.
+pub fn end_frame() {
+    with(|p| {
+        if p.frame.is_none() {
+            return;
+        }
+        while !p.open.is_empty() {
+            p.pop_scope();
+        }
+        if let Some(frame) = p.frame.take() {
+            p.pending.push_back(frame);
+        }
+        p.resolve();
+    });
+}
+
+/// Stats of the latest frame whose timings are all known.
+pub fn last_frame() -> Option<FrameStats> {
+    PROFILER.with(|p| p.borrow().as_ref().and_then(|p| p.last.clone()))
+}
+
+/// Profiles the calls made until the returned guard is dropped as `name`.
+///
+/// Does nothing unless profiling is enabled and a frame was begun.
+pub fn scope(name: &'static str) -> GlProfileScope {
+    let active = with(|p| {
+        if p.frame.is_some() {
+            p.push_scope(name);
+            true
+        } else {
+            false
+        }
+    })
+    .unwrap_or(false);
+    GlProfileScope { active }
+}
+
+/// Profiled scope ended when dropped.
+pub struct GlProfileScope {
+    active: bool,
+}
+
+impl Drop for GlProfileScope {
+    fn drop(&mut self) {
+        if self.active {
+            with(|p| {
+                if p.open.len() > 1 {
+                    p.pop_scope();
+                }
+            });
+        }
+    }
+}
+
+fn with<T>(f: impl FnOnce(&mut Profiler) -> T) -> Option<T> {
+    PROFILER.with(|p| p.borrow_mut().as_mut().map(f))
+}
+
+impl Profiler {
+    fn push_scope(&mut self, name: &'static str) {
+        let Some(frame) = self.frame.as_mut() else {
+            return;
+        };
+        let parent = self.open.last().copied();
+        frame.scopes.push(Scope {
+            name,
+            parent,
+            depth: self.open.len(),
+            cpu_start: Instant::now(),
+            cpu: Duration::ZERO,
+            draw_calls_start: func::draw_calls(),
+            draw_calls: 0,
+        });
+        self.open.push(frame.scopes.len() - 1);
+        self.next_segment();
+    }
+
+    fn pop_scope(&mut self) {
+        let (Some(frame), Some(index)) = (self.frame.as_mut(), self.open.pop()) else {
+            return;
+        };
+        let scope = &mut frame.scopes[index];
+        scope.cpu = scope.cpu_start.elapsed();
+        scope.draw_calls = func::draw_calls().wrapping_sub(scope.draw_calls_start);
+        self.next_segment();
+    }
+
+    /// Ends the running timer query and starts one for the innermost open
+    /// scope, as time elapsed queries can't be nested.
+    fn next_segment(&mut self) {
+        if !self.timer {
+            return;
+        }
+        let Some(frame) = self.frame.as_mut() else {
+            return;
+        };
+
+        let result = (|| -> Result<()> {
+            if !frame.segments.is_empty() {
+                GlQuery::end(GlQueryTarget::TimeElapsed)?;
+            }
+            if let Some(&scope) = self.open.last() {
+                let query = match self.free.pop() {
+                    Some(query) => query,
+                    None => GlQuery::create()?,
+                };
+                query.begin(GlQueryTarget::TimeElapsed)?;
+                frame.segments.push(Segment { scope, query });
+            }
+            Ok(())
+        })();
+
+        // the profiler must not break drawing, fall back to CPU times
+        if result.is_err() {
+            self.timer = false;
+            frame.segments.clear();
+        }
+    }
+
+    /// Turns the oldest pending frames with available GPU timings into stats.
+    fn resolve(&mut self) {
+        while let Some(frame) = self.pending.front() {
+            let ready = frame
+                .segments
+                .iter()
+                .all(|s| s.query.is_available().unwrap_or(true));
+            if !ready && self.pending.len() < MAX_PENDING {
+                break;
+            }
+            let frame = self.pending.pop_front().unwrap();
+            if ready {
+                self.last = Some(self.stats(&frame));
+            }
+            self.free
+                .extend(frame.segments.into_iter().map(|s| s.query));
+        }
+    }
+
+    fn stats(&self, frame: &Frame) -> FrameStats {
+        let gpu = if self.timer && !frame.segments.is_empty() {
+            let disjoint = query::gpu_disjoint().unwrap_or(true);
+            let mut gpu = vec![0u64; frame.scopes.len()];
+            for segment in &frame.segments {
+                gpu[segment.scope] += segment.query.result().unwrap_or(0);
+            }
+            // nested scopes come after their parents
+            for (index, scope) in frame.scopes.iter().enumerate().rev() {
+                if let Some(parent) = scope.parent {
+                    gpu[parent] += gpu[index];
+                }
+            }
+            (!disjoint).then_some(gpu)
+        } else {
+            None
+        };
+        let gpu_time = |index: usize| gpu.as_ref().map(|gpu| Duration::from_nanos(gpu[index]));
+
+        let root = &frame.scopes[0];
+        FrameStats {
+            cpu: root.cpu,
+            gpu: gpu_time(0),
+            draw_calls: root.draw_calls,
+            scopes: frame.scopes[1..]
+                .iter()
+                .enumerate()
+                .map(|(index, scope)| ScopeStats {
+                    name: scope.name,
+                    depth: scope.depth - 1,
+                    cpu: scope.cpu,
+                    gpu: gpu_time(index + 1),
+                    draw_calls: scope.draw_calls,
+                })
+                .collect(),
+        }
+    }
+}
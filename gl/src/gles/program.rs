@@ -1,23 +1,47 @@
-use super::{binds::*, shader::GlShader, types::*};
+use super::{
+    binds::*,
+    context::{GlCtxRef, GlObject},
+    debug,
+    shader::GlShader,
+    types::*,
+};
 use crate::error::{Error, Result};
-use std::ffi::CString;
+use std::{collections::HashMap, ffi::CString};
+
+pub const GL_INVALID_INDEX: GLuint = 0xFFFFFFFF;
 
 pub struct GlProgram {
     program: GLuint,
+    name: String,
+    uniforms: HashMap<String, GLint>,
+    ctx: GlCtxRef,
 }
 
 impl GlProgram {
     pub fn create() -> Result<GlProgram> {
         unsafe {
+            let ctx = GlCtxRef::current("glCreateProgram")?;
             let program = glCreateProgram();
             if program == 0 {
                 Err(Error::gl_err("glCreateProgram"))
             } else {
-                Ok(GlProgram { program })
+                Ok(GlProgram {
+                    program,
+                    name: String::new(),
+                    uniforms: HashMap::new(),
+                    ctx,
+                })
             }
         }
     }
 
+    /// Names the program in link errors and, with debug output enabled, in
+    /// debug messages.
+    pub fn set_label(&mut self, name: &str) -> Result<()> {
+        self.name = name.to_owned();
+        debug::label(GlObjectType::Program, self.program, name)
+    }
+
     pub fn bind(&self) {
         unsafe {
             glUseProgram(self.program);
@@ -31,7 +55,8 @@ impl GlProgram {
         }
     }
 
-    pub fn link(&self) -> Result<()> {
+    /// Links the program and caches the locations of its active uniforms.
+    pub fn link(&mut self) -> Result<()> {
         unsafe {
             glLinkProgram(self.program);
 
@@ -52,15 +77,78 @@ impl GlProgram {
                     msg.as_mut_ptr() as *mut i8,
                 );
                 msg.set_len(length as usize);
-                // TODO: replace prog name with prog shaders names
-                Err(Error::compile_program("", String::from_utf8(msg).unwrap()))
+                Err(Error::compile_program(
+                    &self.name,
+                    String::from_utf8(msg).unwrap(),
+                ))
             } else {
-                Ok(())
+                self.reflect_uniforms()
+            }
+        }
+    }
+
+    fn reflect_uniforms(&mut self) -> Result<()> {
+        unsafe {
+            let mut count = 0;
+            glGetProgramiv(
+                self.program,
+                GlProgramParam::ActiveUniforms.into(),
+                &mut count,
+            );
+            let mut max_len = 0;
+            glGetProgramiv(
+                self.program,
+                GlProgramParam::ActiveUniformMaxLength.into(),
+                &mut max_len,
+            );
+            Error::gl("glGetProgramiv")?;
+
+            self.uniforms.clear();
+            for index in 0..count as GLuint {
+                let mut name: Vec<u8> = Vec::with_capacity(max_len as usize);
+                let mut length = 0;
+                let mut size = 0;
+                let mut el_type = 0;
+                glGetActiveUniform(
+                    self.program,
+                    index,
+                    max_len,
+                    &mut length,
+                    &mut size,
+                    &mut el_type,
+                    name.as_mut_ptr() as *mut i8,
+                );
+                Error::gl("glGetActiveUniform")?;
+                name.set_len(length as usize);
+                let name = String::from_utf8(name).unwrap();
+
+                let c_name = CString::new(name.as_str()).unwrap();
+                let loc = glGetUniformLocation(self.program, c_name.as_ptr());
+                // uniforms in blocks have no location
+                if loc < 0 {
+                    continue;
+                }
+
+                // arrays are reported as `name[0]`, make them reachable by `name` too
+                if let Some(base) = name.strip_suffix("[0]") {
+                    self.uniforms.insert(base.to_owned(), loc);
+                }
+                self.uniforms.insert(name, loc);
             }
+
+            Ok(())
         }
     }
 
+    /// Location of uniform `name`, or `-1` if the program has no such uniform.
+    ///
+    /// Names found while linking are served from the cache, anything else
+    /// (such as a single array element) is queried.
     pub fn get_uniform_loc(&self, name: &str) -> Result<i32> {
+        if let Some(loc) = self.uniforms.get(name) {
+            return Ok(*loc);
+        }
+
         unsafe {
             let name = CString::new(name).unwrap();
             let loc = glGetUniformLocation(self.program, name.as_ptr());
@@ -68,48 +156,102 @@ impl GlProgram {
             Ok(loc)
         }
     }
-}
 
-pub trait Uniform<T> {
-    fn set_uniform(&self, name: &str, data: T) -> Result<()>;
-}
-
-impl Uniform<f32> for GlProgram {
-    fn set_uniform(&self, name: &str, data: f32) -> Result<()> {
+    /// Assigns uniform block `name` to `binding`, see
+    /// [`GlBuffer::bind_base`](super::buffer::GlBuffer::bind_base).
+    /// Does nothing if the program has no such block.
+    pub fn set_uniform_block(&self, name: &str, binding: u32) -> Result<()> {
         unsafe {
-            let loc = self.get_uniform_loc(name)?;
-            glUniform1f(loc, data);
-            Error::gl("glUniform1f")
+            let name = CString::new(name).unwrap();
+            let index = glGetUniformBlockIndex(self.program, name.as_ptr());
+            Error::gl("glGetUniformBlockIndex")?;
+            if index == GL_INVALID_INDEX {
+                return Ok(());
+            }
+
+            glUniformBlockBinding(self.program, index, binding);
+            Error::gl("glUniformBlockBinding")
         }
     }
 }
 
-impl Uniform<&[f32; 2]> for GlProgram {
-    fn set_uniform(&self, name: &str, data: &[f32; 2]) -> Result<()> {
-        unsafe {
-            let loc = self.get_uniform_loc(name)?;
-            glUniform2f(loc, data[0], data[1]);
-            Error::gl("glUniform1f")
-        }
+impl Drop for GlProgram {
+    fn drop(&mut self) {
+        self.ctx.delete(GlObject::Program(self.program));
     }
 }
 
-impl Uniform<&[f32; 3]> for GlProgram {
-    fn set_uniform(&self, name: &str, data: &[f32; 3]) -> Result<()> {
-        unsafe {
-            let loc = self.get_uniform_loc(name)?;
-            glUniform3f(loc, data[0], data[1], data[2]);
-            Error::gl("glUniform1f")
-        }
-    }
+/// Sets uniform `name` of the currently bound program.
+///
+/// Matrices are column-major, one inner array per column.
+pub trait Uniform<T> {
+    fn set_uniform(&self, name: &str, data: T) -> Result<()>;
 }
 
-impl Uniform<&[f32; 4]> for GlProgram {
-    fn set_uniform(&self, name: &str, data: &[f32; 4]) -> Result<()> {
-        unsafe {
-            let loc = self.get_uniform_loc(name)?;
-            glUniform4f(loc, data[0], data[1], data[2], data[3]);
-            Error::gl("glUniform1f")
-        }
-    }
+macro_rules! uniform {
+    (|$loc:ident, $data:ident| $($type:ty => $func:ident($($arg:expr),*);)*) => {
+        $(
+            impl Uniform<$type> for GlProgram {
+                fn set_uniform(&self, name: &str, $data: $type) -> Result<()> {
+                    unsafe {
+                        let $loc = self.get_uniform_loc(name)?;
+                        $func($($arg),*);
+                        Error::gl(stringify!($func))
+                    }
+                }
+            }
+        )*
+    };
+}
+
+uniform! { |loc, d|
+    f32 => glUniform1f(loc, d);
+    &[f32; 2] => glUniform2f(loc, d[0], d[1]);
+    &[f32; 3] => glUniform3f(loc, d[0], d[1], d[2]);
+    &[f32; 4] => glUniform4f(loc, d[0], d[1], d[2], d[3]);
+
+    i32 => glUniform1i(loc, d);
+    &[i32; 2] => glUniform2i(loc, d[0], d[1]);
+    &[i32; 3] => glUniform3i(loc, d[0], d[1], d[2]);
+    &[i32; 4] => glUniform4i(loc, d[0], d[1], d[2], d[3]);
+
+    u32 => glUniform1ui(loc, d);
+    &[u32; 2] => glUniform2ui(loc, d[0], d[1]);
+    &[u32; 3] => glUniform3ui(loc, d[0], d[1], d[2]);
+    &[u32; 4] => glUniform4ui(loc, d[0], d[1], d[2], d[3]);
+
+    &[f32] => glUniform1fv(loc, d.len() as GLsizei, d.as_ptr());
+    &[[f32; 2]] => glUniform2fv(loc, d.len() as GLsizei, d.as_ptr() as *const GLfloat);
+    &[[f32; 3]] => glUniform3fv(loc, d.len() as GLsizei, d.as_ptr() as *const GLfloat);
+    &[[f32; 4]] => glUniform4fv(loc, d.len() as GLsizei, d.as_ptr() as *const GLfloat);
+    &[i32] => glUniform1iv(loc, d.len() as GLsizei, d.as_ptr());
+    &[[i32; 2]] => glUniform2iv(loc, d.len() as GLsizei, d.as_ptr() as *const GLint);
+    &[[i32; 3]] => glUniform3iv(loc, d.len() as GLsizei, d.as_ptr() as *const GLint);
+    &[[i32; 4]] => glUniform4iv(loc, d.len() as GLsizei, d.as_ptr() as *const GLint);
+    &[u32] => glUniform1uiv(loc, d.len() as GLsizei, d.as_ptr());
+    &[[u32; 2]] => glUniform2uiv(loc, d.len() as GLsizei, d.as_ptr() as *const GLuint);
+    &[[u32; 3]] => glUniform3uiv(loc, d.len() as GLsizei, d.as_ptr() as *const GLuint);
+    &[[u32; 4]] => glUniform4uiv(loc, d.len() as GLsizei, d.as_ptr() as *const GLuint);
+
+    &[[f32; 2]; 2] => glUniformMatrix2fv(loc, 1, GL_FALSE as GLboolean, d.as_ptr() as *const GLfloat);
+    &[[f32; 3]; 3] => glUniformMatrix3fv(loc, 1, GL_FALSE as GLboolean, d.as_ptr() as *const GLfloat);
+    &[[f32; 4]; 4] => glUniformMatrix4fv(loc, 1, GL_FALSE as GLboolean, d.as_ptr() as *const GLfloat);
+    &[[[f32; 2]; 2]] => glUniformMatrix2fv(
+        loc,
+        d.len() as GLsizei,
+        GL_FALSE as GLboolean,
+        d.as_ptr() as *const GLfloat
+    );
+    &[[[f32; 3]; 3]] => glUniformMatrix3fv(
+        loc,
+        d.len() as GLsizei,
+        GL_FALSE as GLboolean,
+        d.as_ptr() as *const GLfloat
+    );
+    &[[[f32; 4]; 4]] => glUniformMatrix4fv(
+        loc,
+        d.len() as GLsizei,
+        GL_FALSE as GLboolean,
+        d.as_ptr() as *const GLfloat
+    );
 }
@@ -0,0 +1,86 @@
+use super::{
+    binds::*,
+    context::{GlCtxRef, GlObject},
+    types::*,
+};
+use crate::error::{Error, Result};
+
+pub struct GlQuery {
+    query: GLuint,
+    ctx: GlCtxRef,
+}
+
+impl GlQuery {
+    pub fn create() -> Result<GlQuery> {
+        unsafe {
+            let ctx = GlCtxRef::current("glGenQueries")?;
+            let mut query = 0;
+            glGenQueries(1, &mut query);
+            Error::gl("glGenQueries")?;
+            Ok(GlQuery { query, ctx })
+        }
+    }
+
+    /// Starts counting into the query, only one query per target can be active.
+    pub fn begin(&self, target: GlQueryTarget) -> Result<()> {
+        unsafe {
+            glBeginQuery(target.into(), self.query);
+            Error::gl("glBeginQuery")
+        }
+    }
+
+    /// Stops the active query of `target`.
+    pub fn end(target: GlQueryTarget) -> Result<()> {
+        unsafe {
+            glEndQuery(target.into());
+            Error::gl("glEndQuery")
+        }
+    }
+
+    /// Whether the result can be read without stalling.
+    pub fn is_available(&self) -> Result<bool> {
+        unsafe {
+            let mut available = 0;
+            glGetQueryObjectuiv(self.query, GL_QUERY_RESULT_AVAILABLE, &mut available);
+            Error::gl("glGetQueryObjectuiv")?;
+            Ok(available != 0)
+        }
+    }
+
+    /// Result of the query, waiting for it if it isn't available yet.
+    pub fn result(&self) -> Result<u64> {
+        unsafe {
+            match ext().glGetQueryObjectui64vEXT {
+                Some(get) => {
+                    let mut result = 0;
+                    get(self.query, GL_QUERY_RESULT, &mut result);
+                    Error::gl("glGetQueryObjectui64vEXT")?;
+                    Ok(result)
+                }
+                None => {
+                    let mut result = 0;
+                    glGetQueryObjectuiv(self.query, GL_QUERY_RESULT, &mut result);
+                    Error::gl("glGetQueryObjectuiv")?;
+                    Ok(result as u64)
+                }
+            }
+        }
+    }
+}
+
+impl Drop for GlQuery {
+    fn drop(&mut self) {
+        self.ctx.delete(GlObject::Query(self.query));
+    }
+}
+
+/// Whether the GPU was disjoint (for example changed its clock) since the
+/// last check, making the timer queries run meanwhile meaningless.
+pub fn gpu_disjoint() -> Result<bool> {
+    unsafe {
+        let mut disjoint = 0;
+        glGetIntegerv(GL_GPU_DISJOINT_EXT, &mut disjoint);
+        Error::gl("glGetIntegerv")?;
+        Ok(disjoint != 0)
+    }
+}
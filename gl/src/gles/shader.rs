@@ -1,22 +1,29 @@
-use super::{binds::*, types::*};
+use super::{
+    binds::*,
+    context::{GlCtxRef, GlObject},
+    types::*,
+};
 use crate::error::{Error, Result};
 use std::ffi::CString;
 
 pub struct GlShader {
     pub(super) shader: GLuint,
+    ctx: GlCtxRef,
 }
 
 impl GlShader {
     pub fn create(shader_type: GlShaderType, source: &str) -> Result<GlShader> {
         unsafe {
+            let ctx = GlCtxRef::current("glCreateShader")?;
             let shader = glCreateShader(shader_type.into());
             Error::gl("glCreateShader")?;
+            let shader = GlShader { shader, ctx };
 
             let src = CString::new(source).unwrap();
-            glShaderSource(shader, 1, &src.as_ptr(), std::ptr::null());
+            glShaderSource(shader.shader, 1, &src.as_ptr(), std::ptr::null());
             Error::gl("glShaderSource")?;
 
-            Ok(GlShader { shader })
+            Ok(shader)
         }
     }
 
@@ -53,3 +60,9 @@ impl GlShader {
         }
     }
 }
+
+impl Drop for GlShader {
+    fn drop(&mut self) {
+        self.ctx.delete(GlObject::Shader(self.shader));
+    }
+}
@@ -0,0 +1,214 @@
+use super::{
+    binds::*,
+    context::{GlCtxRef, GlObject},
+    program::{GlProgram, Uniform},
+    types::*,
+};
+use crate::error::{Error, Result};
+use std::ffi::c_void;
+
+/// Texture unit a texture is bound to and a sampler uniform reads from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct GlTexUnit(pub u32);
+
+pub struct GlTexture {
+    pub(super) texture: GLuint,
+    pub(super) target: GlTexTarget,
+    ctx: GlCtxRef,
+}
+
+impl GlTexture {
+    pub fn create(target: GlTexTarget) -> Result<GlTexture> {
+        unsafe {
+            let ctx = GlCtxRef::current("glGenTextures")?;
+            let mut texture = 0;
+            glGenTextures(1, &mut texture);
+            Error::gl("glGenTextures")?;
+
+            Ok(GlTexture {
+                texture,
+                target,
+                ctx,
+            })
+        }
+    }
+
+    pub fn bind(&self) {
+        unsafe {
+            glBindTexture(self.target.into(), self.texture);
+        }
+    }
+
+    /// Binds the texture to `unit`, leaving `unit` as the active texture unit.
+    pub fn bind_unit(&self, unit: GlTexUnit) -> Result<()> {
+        unsafe {
+            glActiveTexture(GL_TEXTURE0 + unit.0 as GLenum);
+            Error::gl("glActiveTexture")?;
+            self.bind();
+            Ok(())
+        }
+    }
+
+    /// Allocates storage for mipmap `level` and optionally fills it with `data`.
+    ///
+    /// Panics if `data` is shorter than `width` x `height` pixels of `format`
+    /// and `el_type` laid out with the current unpack alignment.
+    #[allow(clippy::too_many_arguments)]
+    pub fn image_2d(
+        &self,
+        level: i32,
+        internal_format: GlTexFormat,
+        width: i32,
+        height: i32,
+        format: GlPixelFormat,
+        el_type: GlPixelType,
+        data: Option<&[u8]>,
+    ) -> Result<()> {
+        unsafe {
+            let data = match data {
+                Some(data) => {
+                    check_data_len(data, width, height, format, el_type);
+                    data.as_ptr() as *const c_void
+                }
+                None => std::ptr::null(),
+            };
+
+            self.bind();
+            glTexImage2D(
+                self.target.into(),
+                level,
+                internal_format.into(),
+                width,
+                height,
+                0,
+                format.into(),
+                el_type.into(),
+                data,
+            );
+            Error::gl("glTexImage2D")
+        }
+    }
+
+    /// Replaces a `width` x `height` region of mipmap `level` starting at `x`, `y`.
+    ///
+    /// Panics under the same conditions as [`GlTexture::image_2d`].
+    #[allow(clippy::too_many_arguments)]
+    pub fn sub_image_2d(
+        &self,
+        level: i32,
+        x: i32,
+        y: i32,
+        width: i32,
+        height: i32,
+        format: GlPixelFormat,
+        el_type: GlPixelType,
+        data: &[u8],
+    ) -> Result<()> {
+        unsafe {
+            check_data_len(data, width, height, format, el_type);
+
+            self.bind();
+            glTexSubImage2D(
+                self.target.into(),
+                level,
+                x,
+                y,
+                width,
+                height,
+                format.into(),
+                el_type.into(),
+                data.as_ptr() as *const c_void,
+            );
+            Error::gl("glTexSubImage2D")
+        }
+    }
+
+    pub fn set_filter(&self, min: GlTexFilter, mag: GlTexFilter) -> Result<()> {
+        self.set_param(GlTexParam::MinFilter, min.into())?;
+        self.set_param(GlTexParam::MagFilter, mag.into())
+    }
+
+    pub fn set_wrap(&self, s: GlTexWrap, t: GlTexWrap) -> Result<()> {
+        self.set_param(GlTexParam::WrapS, s.into())?;
+        self.set_param(GlTexParam::WrapT, t.into())
+    }
+
+    pub fn set_param(&self, param: GlTexParam, value: i32) -> Result<()> {
+        unsafe {
+            self.bind();
+            glTexParameteri(self.target.into(), param.into(), value);
+            Error::gl("glTexParameteri")
+        }
+    }
+
+    pub fn generate_mipmap(&self) -> Result<()> {
+        unsafe {
+            self.bind();
+            glGenerateMipmap(self.target.into());
+            Error::gl("glGenerateMipmap")
+        }
+    }
+}
+
+impl Drop for GlTexture {
+    fn drop(&mut self) {
+        self.ctx.delete(GlObject::Texture(self.texture));
+    }
+}
+
+impl Uniform<GlTexUnit> for GlProgram {
+    fn set_uniform(&self, name: &str, data: GlTexUnit) -> Result<()> {
+        self.set_uniform(name, data.0 as i32)
+    }
+}
+
+fn pixel_size(format: GlPixelFormat, el_type: GlPixelType) -> usize {
+    use GlPixelType::*;
+    let components = match format {
+        GlPixelFormat::Alpha
+        | GlPixelFormat::Luminance
+        | GlPixelFormat::Red
+        | GlPixelFormat::RedInteger
+        | GlPixelFormat::DepthComponent
+        | GlPixelFormat::DepthStencil => 1,
+        GlPixelFormat::LuminanceAlpha | GlPixelFormat::Rg | GlPixelFormat::RgInteger => 2,
+        GlPixelFormat::Rgb | GlPixelFormat::RgbInteger => 3,
+        GlPixelFormat::Rgba | GlPixelFormat::RgbaInteger => 4,
+    };
+    match el_type {
+        Byte | UnsignedByte => components,
+        Short | UnsignedShort | HalfFloat => components * 2,
+        Int | UnsignedInt | Float => components * 4,
+        UnsignedShort565 | UnsignedShort4444 | UnsignedShort5551 => 2,
+        UnsignedInt24_8 => 4,
+    }
+}
+
+unsafe fn check_data_len(
+    data: &[u8],
+    width: i32,
+    height: i32,
+    format: GlPixelFormat,
+    el_type: GlPixelType,
+) {
+    let mut align = 4;
+    glGetIntegerv(GlPixelStore::UnpackAlignment.into(), &mut align);
+
+    let (width, height, align) = (
+        width.max(0) as usize,
+        height.max(0) as usize,
+        align as usize,
+    );
+    let row = width * pixel_size(format, el_type);
+    let stride = row.div_ceil(align) * align;
+    let needed = match height {
+        0 => 0,
+        h => stride * (h - 1) + row,
+    };
+
+    assert!(
+        data.len() >= needed,
+        "texture data has {} bytes, expected at least {needed}",
+        data.len()
+    );
+}
@@ -1,5 +1,5 @@
 use crate::{attr_bitstruct, attr_enum};
-use std::ffi::{c_char, c_long, c_uchar};
+use std::ffi::{c_char, c_long, c_uchar, c_void};
 
 pub type GLchar = c_char;
 pub type GLint = i32;
@@ -9,7 +9,21 @@ pub type GLsizei = i32;
 pub type GLenum = i32;
 pub type GLbitfield = i32;
 pub type GLsizeiptr = c_long;
+pub type GLintptr = c_long;
 pub type GLboolean = c_uchar;
+pub type GLubyte = c_uchar;
+
+pub type GLDEBUGPROC = Option<
+    unsafe extern "C" fn(
+        source: GLenum,
+        msg_type: GLenum,
+        id: GLuint,
+        severity: GLenum,
+        length: GLsizei,
+        message: *const GLchar,
+        user_param: *const c_void,
+    ),
+>;
 
 pub const GL_TRUE: GLint = 0x1;
 pub const GL_FALSE: GLint = 0x0;
@@ -99,6 +113,24 @@ attr_enum!(
     }
 );
 
+pub const GL_MAP_READ_BIT: GLbitfield = 0x0001;
+pub const GL_MAP_WRITE_BIT: GLbitfield = 0x0002;
+pub const GL_MAP_INVALIDATE_RANGE_BIT: GLbitfield = 0x0004;
+pub const GL_MAP_INVALIDATE_BUFFER_BIT: GLbitfield = 0x0008;
+pub const GL_MAP_FLUSH_EXPLICIT_BIT: GLbitfield = 0x0010;
+pub const GL_MAP_UNSYNCHRONIZED_BIT: GLbitfield = 0x0020;
+
+attr_bitstruct!(
+    pub bitstruct GlMapAccess(0) {
+        read = GL_MAP_READ_BIT,
+        write = GL_MAP_WRITE_BIT,
+        invalidate_range = GL_MAP_INVALIDATE_RANGE_BIT,
+        invalidate_buffer = GL_MAP_INVALIDATE_BUFFER_BIT,
+        flush_explicit = GL_MAP_FLUSH_EXPLICIT_BIT,
+        unsynchronized = GL_MAP_UNSYNCHRONIZED_BIT,
+    }
+);
+
 pub const GL_STREAM_DRAW: GLenum = 0x88E0;
 pub const GL_STREAM_READ: GLenum = 0x88E1;
 pub const GL_STREAM_COPY: GLenum = 0x88E2;
@@ -245,6 +277,8 @@ pub const GL_SAMPLE_COVERAGE: GLenum = 0x80A0;
 pub const GL_SAMPLE_MASK: GLenum = 0x8E51;
 pub const GL_SCISSOR_TEST: GLenum = 0x0C11;
 pub const GL_STENCIL_TEST: GLenum = 0x0B90;
+pub const GL_DEBUG_OUTPUT: GLenum = 0x92E0;
+pub const GL_DEBUG_OUTPUT_SYNCHRONOUS: GLenum = 0x8242;
 
 attr_enum!(
     pub enum GlCap {
@@ -260,6 +294,8 @@ attr_enum!(
         SampleMask = GL_SAMPLE_MASK,
         ScissorTest = GL_SCISSOR_TEST,
         StencilTest = GL_STENCIL_TEST,
+        DebugOutput = GL_DEBUG_OUTPUT,
+        DebugOutputSynchronous = GL_DEBUG_OUTPUT_SYNCHRONOUS,
     }
 );
 
@@ -341,3 +377,305 @@ attr_enum!(
         Invert = GL_INVERT,
     }
 );
+
+pub const GL_PACK_ALIGNMENT: GLenum = 0x0D05;
+pub const GL_UNPACK_ALIGNMENT: GLenum = 0x0CF5;
+
+attr_enum!(
+    pub enum GlPixelStore {
+        PackAlignment = GL_PACK_ALIGNMENT,
+        UnpackAlignment = GL_UNPACK_ALIGNMENT,
+    }
+);
+
+pub const GL_TEXTURE_2D: GLenum = 0x0DE1;
+
+attr_enum!(
+    pub enum GlTexTarget {
+        Texture2D = GL_TEXTURE_2D,
+    }
+);
+
+pub const GL_TEXTURE0: GLenum = 0x84C0;
+
+pub const GL_ALPHA: GLenum = 0x1906;
+pub const GL_RGB: GLenum = 0x1907;
+pub const GL_RGBA: GLenum = 0x1908;
+pub const GL_LUMINANCE: GLenum = 0x1909;
+pub const GL_LUMINANCE_ALPHA: GLenum = 0x190A;
+pub const GL_RED: GLenum = 0x1903;
+pub const GL_RG: GLenum = 0x8227;
+pub const GL_RED_INTEGER: GLenum = 0x8D94;
+pub const GL_RG_INTEGER: GLenum = 0x8228;
+pub const GL_RGB_INTEGER: GLenum = 0x8D98;
+pub const GL_RGBA_INTEGER: GLenum = 0x8D99;
+pub const GL_DEPTH_COMPONENT: GLenum = 0x1902;
+pub const GL_DEPTH_STENCIL: GLenum = 0x84F9;
+
+attr_enum!(
+    pub enum GlPixelFormat {
+        Alpha = GL_ALPHA,
+        Rgb = GL_RGB,
+        Rgba = GL_RGBA,
+        Luminance = GL_LUMINANCE,
+        LuminanceAlpha = GL_LUMINANCE_ALPHA,
+        Red = GL_RED,
+        Rg = GL_RG,
+        RedInteger = GL_RED_INTEGER,
+        RgInteger = GL_RG_INTEGER,
+        RgbInteger = GL_RGB_INTEGER,
+        RgbaInteger = GL_RGBA_INTEGER,
+        DepthComponent = GL_DEPTH_COMPONENT,
+        DepthStencil = GL_DEPTH_STENCIL,
+    }
+);
+
+pub const GL_R8: GLenum = 0x8229;
+pub const GL_RG8: GLenum = 0x822B;
+pub const GL_RGB8: GLenum = 0x8051;
+pub const GL_RGBA8: GLenum = 0x8058;
+pub const GL_SRGB8: GLenum = 0x8C41;
+pub const GL_SRGB8_ALPHA8: GLenum = 0x8C43;
+pub const GL_R16F: GLenum = 0x822D;
+pub const GL_RGBA16F: GLenum = 0x881A;
+pub const GL_R32F: GLenum = 0x822E;
+pub const GL_RGBA32F: GLenum = 0x8814;
+pub const GL_DEPTH_COMPONENT16: GLenum = 0x81A5;
+pub const GL_DEPTH_COMPONENT24: GLenum = 0x81A6;
+pub const GL_DEPTH24_STENCIL8: GLenum = 0x88F0;
+
+attr_enum!(
+    pub enum GlTexFormat {
+        Alpha = GL_ALPHA,
+        Rgb = GL_RGB,
+        Rgba = GL_RGBA,
+        Luminance = GL_LUMINANCE,
+        LuminanceAlpha = GL_LUMINANCE_ALPHA,
+        R8 = GL_R8,
+        Rg8 = GL_RG8,
+        Rgb8 = GL_RGB8,
+        Rgba8 = GL_RGBA8,
+        Srgb8 = GL_SRGB8,
+        Srgb8Alpha8 = GL_SRGB8_ALPHA8,
+        R16F = GL_R16F,
+        Rgba16F = GL_RGBA16F,
+        R32F = GL_R32F,
+        Rgba32F = GL_RGBA32F,
+        DepthComponent16 = GL_DEPTH_COMPONENT16,
+        DepthComponent24 = GL_DEPTH_COMPONENT24,
+        Depth24Stencil8 = GL_DEPTH24_STENCIL8,
+    }
+);
+
+pub const GL_UNSIGNED_SHORT_5_6_5: GLenum = 0x8363;
+pub const GL_UNSIGNED_SHORT_4_4_4_4: GLenum = 0x8033;
+pub const GL_UNSIGNED_SHORT_5_5_5_1: GLenum = 0x8034;
+pub const GL_UNSIGNED_INT_24_8: GLenum = 0x84FA;
+
+attr_enum!(
+    pub enum GlPixelType {
+        Byte = GL_BYTE,
+        UnsignedByte = GL_UNSIGNED_BYTE,
+        Short = GL_SHORT,
+        UnsignedShort = GL_UNSIGNED_SHORT,
+        Int = GL_INT,
+        UnsignedInt = GL_UNSIGNED_INT,
+        HalfFloat = GL_HALF_FLOAT,
+        Float = GL_FLOAT,
+        UnsignedShort565 = GL_UNSIGNED_SHORT_5_6_5,
+        UnsignedShort4444 = GL_UNSIGNED_SHORT_4_4_4_4,
+        UnsignedShort5551 = GL_UNSIGNED_SHORT_5_5_5_1,
+        UnsignedInt24_8 = GL_UNSIGNED_INT_24_8,
+    }
+);
+
+pub const GL_TEXTURE_MAG_FILTER: GLenum = 0x2800;
+pub const GL_TEXTURE_MIN_FILTER: GLenum = 0x2801;
+pub const GL_TEXTURE_WRAP_S: GLenum = 0x2802;
+pub const GL_TEXTURE_WRAP_T: GLenum = 0x2803;
+
+attr_enum!(
+    pub enum GlTexParam {
+        MagFilter = GL_TEXTURE_MAG_FILTER,
+        MinFilter = GL_TEXTURE_MIN_FILTER,
+        WrapS = GL_TEXTURE_WRAP_S,
+        WrapT = GL_TEXTURE_WRAP_T,
+    }
+);
+
+pub const GL_NEAREST: GLenum = 0x2600;
+pub const GL_LINEAR: GLenum = 0x2601;
+pub const GL_NEAREST_MIPMAP_NEAREST: GLenum = 0x2700;
+pub const GL_LINEAR_MIPMAP_NEAREST: GLenum = 0x2701;
+pub const GL_NEAREST_MIPMAP_LINEAR: GLenum = 0x2702;
+pub const GL_LINEAR_MIPMAP_LINEAR: GLenum = 0x2703;
+
+attr_enum!(
+    pub enum GlTexFilter {
+        Nearest = GL_NEAREST,
+        Linear = GL_LINEAR,
+        NearestMipmapNearest = GL_NEAREST_MIPMAP_NEAREST,
+        LinearMipmapNearest = GL_LINEAR_MIPMAP_NEAREST,
+        NearestMipmapLinear = GL_NEAREST_MIPMAP_LINEAR,
+        LinearMipmapLinear = GL_LINEAR_MIPMAP_LINEAR,
+    }
+);
+
+pub const GL_REPEAT: GLenum = 0x2901;
+pub const GL_CLAMP_TO_EDGE: GLenum = 0x812F;
+pub const GL_MIRRORED_REPEAT: GLenum = 0x8370;
+
+attr_enum!(
+    pub enum GlTexWrap {
+        Repeat = GL_REPEAT,
+        ClampToEdge = GL_CLAMP_TO_EDGE,
+        MirroredRepeat = GL_MIRRORED_REPEAT,
+    }
+);
+
+pub const GL_FRAMEBUFFER: GLenum = 0x8D40;
+pub const GL_READ_FRAMEBUFFER: GLenum = 0x8CA8;
+pub const GL_DRAW_FRAMEBUFFER: GLenum = 0x8CA9;
+
+attr_enum!(
+    pub enum GlFramebufferTarget {
+        Framebuffer = GL_FRAMEBUFFER,
+        ReadFramebuffer = GL_READ_FRAMEBUFFER,
+        DrawFramebuffer = GL_DRAW_FRAMEBUFFER,
+    }
+);
+
+pub const GL_FRAMEBUFFER_BINDING: GLenum = 0x8CA6;
+pub const GL_READ_FRAMEBUFFER_BINDING: GLenum = 0x8CAA;
+pub const GL_RENDERBUFFER: GLenum = 0x8D41;
+
+pub const GL_COLOR_ATTACHMENT0: GLenum = 0x8CE0;
+pub const GL_DEPTH_ATTACHMENT: GLenum = 0x8D00;
+pub const GL_STENCIL_ATTACHMENT: GLenum = 0x8D20;
+pub const GL_DEPTH_STENCIL_ATTACHMENT: GLenum = 0x821A;
+
+attr_enum!(
+    pub enum GlAttachment {
+        Color0 = GL_COLOR_ATTACHMENT0,
+        Depth = GL_DEPTH_ATTACHMENT,
+        Stencil = GL_STENCIL_ATTACHMENT,
+        DepthStencil = GL_DEPTH_STENCIL_ATTACHMENT,
+    }
+);
+
+pub const GL_VENDOR: GLenum = 0x1F00;
+pub const GL_RENDERER: GLenum = 0x1F01;
+pub const GL_VERSION: GLenum = 0x1F02;
+pub const GL_EXTENSIONS: GLenum = 0x1F03;
+pub const GL_SHADING_LANGUAGE_VERSION: GLenum = 0x8B8C;
+
+pub const GL_MAX_TEXTURE_SIZE: GLenum = 0x0D33;
+pub const GL_MAX_VIEWPORT_DIMS: GLenum = 0x0D3A;
+pub const GL_MAX_RENDERBUFFER_SIZE: GLenum = 0x84E8;
+pub const GL_MAX_VERTEX_ATTRIBS: GLenum = 0x8869;
+pub const GL_MAX_TEXTURE_IMAGE_UNITS: GLenum = 0x8872;
+pub const GL_MAX_COMBINED_TEXTURE_IMAGE_UNITS: GLenum = 0x8B4D;
+pub const GL_MAX_UNIFORM_BUFFER_BINDINGS: GLenum = 0x8A2F;
+pub const GL_MAX_UNIFORM_BLOCK_SIZE: GLenum = 0x8A30;
+pub const GL_MAJOR_VERSION: GLenum = 0x821B;
+pub const GL_MINOR_VERSION: GLenum = 0x821C;
+pub const GL_NUM_EXTENSIONS: GLenum = 0x821D;
+pub const GL_MAX_SAMPLES: GLenum = 0x8D57;
+
+pub const GL_DONT_CARE: GLenum = 0x1100;
+
+pub const GL_DEBUG_SOURCE_API: GLenum = 0x8246;
+pub const GL_DEBUG_SOURCE_WINDOW_SYSTEM: GLenum = 0x8247;
+pub const GL_DEBUG_SOURCE_SHADER_COMPILER: GLenum = 0x8248;
+pub const GL_DEBUG_SOURCE_THIRD_PARTY: GLenum = 0x8249;
+pub const GL_DEBUG_SOURCE_APPLICATION: GLenum = 0x824A;
+pub const GL_DEBUG_SOURCE_OTHER: GLenum = 0x824B;
+
+attr_enum!(
+    pub enum GlDebugSource {
+        Api = GL_DEBUG_SOURCE_API,
+        WindowSystem = GL_DEBUG_SOURCE_WINDOW_SYSTEM,
+        ShaderCompiler = GL_DEBUG_SOURCE_SHADER_COMPILER,
+        ThirdParty = GL_DEBUG_SOURCE_THIRD_PARTY,
+        Application = GL_DEBUG_SOURCE_APPLICATION,
+        Other = GL_DEBUG_SOURCE_OTHER,
+    }
+);
+
+pub const GL_DEBUG_TYPE_ERROR: GLenum = 0x824C;
+pub const GL_DEBUG_TYPE_DEPRECATED_BEHAVIOR: GLenum = 0x824D;
+pub const GL_DEBUG_TYPE_UNDEFINED_BEHAVIOR: GLenum = 0x824E;
+pub const GL_DEBUG_TYPE_PORTABILITY: GLenum = 0x824F;
+pub const GL_DEBUG_TYPE_PERFORMANCE: GLenum = 0x8250;
+pub const GL_DEBUG_TYPE_OTHER: GLenum = 0x8251;
+pub const GL_DEBUG_TYPE_MARKER: GLenum = 0x8268;
+pub const GL_DEBUG_TYPE_PUSH_GROUP: GLenum = 0x8269;
+pub const GL_DEBUG_TYPE_POP_GROUP: GLenum = 0x826A;
+
+attr_enum!(
+    pub enum GlDebugType {
+        Error = GL_DEBUG_TYPE_ERROR,
+        DeprecatedBehavior = GL_DEBUG_TYPE_DEPRECATED_BEHAVIOR,
+        UndefinedBehavior = GL_DEBUG_TYPE_UNDEFINED_BEHAVIOR,
+        Portability = GL_DEBUG_TYPE_PORTABILITY,
+        Performance = GL_DEBUG_TYPE_PERFORMANCE,
+        Other = GL_DEBUG_TYPE_OTHER,
+        Marker = GL_DEBUG_TYPE_MARKER,
+        PushGroup = GL_DEBUG_TYPE_PUSH_GROUP,
+        PopGroup = GL_DEBUG_TYPE_POP_GROUP,
+    }
+);
+
+pub const GL_DEBUG_SEVERITY_HIGH: GLenum = 0x9146;
+pub const GL_DEBUG_SEVERITY_MEDIUM: GLenum = 0x9147;
+pub const GL_DEBUG_SEVERITY_LOW: GLenum = 0x9148;
+pub const GL_DEBUG_SEVERITY_NOTIFICATION: GLenum = 0x826B;
+
+attr_enum!(
+    pub enum GlDebugSeverity {
+        High = GL_DEBUG_SEVERITY_HIGH,
+        Medium = GL_DEBUG_SEVERITY_MEDIUM,
+        Low = GL_DEBUG_SEVERITY_LOW,
+        Notification = GL_DEBUG_SEVERITY_NOTIFICATION,
+    }
+);
+
+pub const GL_BUFFER: GLenum = 0x82E0;
+pub const GL_SHADER: GLenum = 0x82E1;
+pub const GL_PROGRAM: GLenum = 0x82E2;
+pub const GL_VERTEX_ARRAY: GLenum = 0x8074;
+pub const GL_QUERY: GLenum = 0x82E3;
+pub const GL_TEXTURE: GLenum = 0x1702;
+
+attr_enum!(
+    pub enum GlObjectType {
+        Buffer = GL_BUFFER,
+        Shader = GL_SHADER,
+        Program = GL_PROGRAM,
+        VertexArray = GL_VERTEX_ARRAY,
+        Query = GL_QUERY,
+        Texture = GL_TEXTURE,
+        Framebuffer = GL_FRAMEBUFFER,
+        Renderbuffer = GL_RENDERBUFFER,
+    }
+);
+
+pub const GL_ANY_SAMPLES_PASSED: GLenum = 0x8C2F;
+pub const GL_ANY_SAMPLES_PASSED_CONSERVATIVE: GLenum = 0x8D6A;
+pub const GL_TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN: GLenum = 0x8C88;
+pub const GL_TIME_ELAPSED_EXT: GLenum = 0x88BF;
+
+attr_enum!(
+    pub enum GlQueryTarget {
+        AnySamplesPassed = GL_ANY_SAMPLES_PASSED,
+        AnySamplesPassedConservative = GL_ANY_SAMPLES_PASSED_CONSERVATIVE,
+        TransformFeedbackPrimitivesWritten = GL_TRANSFORM_FEEDBACK_PRIMITIVES_WRITTEN,
+        /// Nanoseconds the GPU spent on the commands between begin and end,
+        /// needs `GL_EXT_disjoint_timer_query`.
+        TimeElapsed = GL_TIME_ELAPSED_EXT,
+    }
+);
+
+pub const GL_QUERY_RESULT: GLenum = 0x8866;
+pub const GL_QUERY_RESULT_AVAILABLE: GLenum = 0x8867;
+pub const GL_GPU_DISJOINT_EXT: GLenum = 0x8FBB;
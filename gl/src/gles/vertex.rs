@@ -1,4 +1,9 @@
-use super::{binds::*, types::*};
+use super::{
+    binds::*,
+    buffer::GlBuffer,
+    context::{GlCtxRef, GlObject},
+    types::*,
+};
 use crate::error::{Error, Result};
 use std::ffi::c_void;
 
@@ -16,18 +21,27 @@ pub trait GlVertex {
     fn get_attrs() -> &'static [GlVAttrib];
 }
 
-#[derive(Debug, Clone, Copy)]
+/// Vertex array owning the buffers attached to it.
+#[derive(Debug)]
 pub struct GlVArray {
     array: GLuint,
+    buffers: Vec<GlBuffer>,
+    ctx: GlCtxRef,
 }
 
 impl GlVArray {
-    pub fn create() -> GlVArray {
+    pub fn create() -> Result<GlVArray> {
         unsafe {
+            let ctx = GlCtxRef::current("glGenVertexArrays")?;
             let mut array = 0;
             glGenVertexArrays(1, &mut array);
+            Error::gl("glGenVertexArrays")?;
 
-            GlVArray { array }
+            Ok(GlVArray {
+                array,
+                buffers: Vec::new(),
+                ctx,
+            })
         }
     }
 
@@ -37,21 +51,31 @@ impl GlVArray {
         }
     }
 
-    pub fn add_v_buffer<T: GlVertex>(&self, v: &[T]) -> Result<()> {
+    pub fn add_v_buffer<T: GlVertex>(&mut self, v: &[T]) -> Result<()> {
+        let mut vbo = GlBuffer::create(GlBuffTarget::ArrayBuffer)?;
+        vbo.data(v, GlBuffUsage::StaticDraw)?;
+        self.set_v_buffer::<T>(&vbo, 0)?;
+
+        self.buffers.push(vbo);
+        Ok(())
+    }
+
+    pub fn add_e_buffer(&mut self, i: &[u32]) -> Result<()> {
+        let mut ebo = GlBuffer::create(GlBuffTarget::ElementArrayBuffer)?;
+        ebo.data(i, GlBuffUsage::StaticDraw)?;
+        self.set_e_buffer(&ebo);
+
+        self.buffers.push(ebo);
+        Ok(())
+    }
+
+    /// Points the attributes of `T` at `buffer`, starting `offset` bytes in.
+    ///
+    /// The buffer isn't owned by the array, so one buffer can feed several arrays.
+    pub fn set_v_buffer<T: GlVertex>(&self, buffer: &GlBuffer, offset: usize) -> Result<()> {
         unsafe {
             self.bind();
-
-            let mut vbo = 0;
-            glGenBuffers(1, &mut vbo);
-            glBindBuffer(GlBuffTarget::ArrayBuffer.into(), vbo);
-            let v_size = std::mem::size_of_val(&v[0]);
-            glBufferData(
-                GlBuffTarget::ArrayBuffer.into(),
-                (v_size * v.len()) as GLsizeiptr,
-                v.as_ptr() as *const c_void,
-                GlBuffUsage::StaticDraw.into(),
-            );
-            Error::gl("glBufferData")?;
+            glBindBuffer(GlBuffTarget::ArrayBuffer.into(), buffer.buffer);
 
             for attr in T::get_attrs() {
                 glVertexAttribPointer(
@@ -60,7 +84,7 @@ impl GlVArray {
                     attr.el_type.into(),
                     attr.normalize as u8,
                     attr.stride,
-                    attr.offset as *const c_void,
+                    (offset + attr.offset as usize) as *const c_void,
                 );
                 Error::gl("glVertexAttribPointer")?;
                 glEnableVertexAttribArray(attr.index);
@@ -71,20 +95,16 @@ impl GlVArray {
         }
     }
 
-    pub fn add_e_buffer(&self, i: &[u32]) -> Result<()> {
+    pub fn set_e_buffer(&self, buffer: &GlBuffer) {
         unsafe {
-            glBindVertexArray(self.array);
-
-            let mut ebo = 0;
-            glGenBuffers(1, &mut ebo);
-            glBindBuffer(GlBuffTarget::ElementArrayBuffer.into(), ebo);
-            glBufferData(
-                GlBuffTarget::ElementArrayBuffer.into(),
-                (std::mem::size_of_val(i)) as GLsizeiptr,
-                i.as_ptr() as *const c_void,
-                GlBuffUsage::StaticDraw.into(),
-            );
-            Error::gl("glBufferData")
+            self.bind();
+            glBindBuffer(GlBuffTarget::ElementArrayBuffer.into(), buffer.buffer);
         }
     }
 }
+
+impl Drop for GlVArray {
+    fn drop(&mut self) {
+        self.ctx.delete(GlObject::VertexArray(self.array));
+    }
+}
@@ -6,6 +6,7 @@ pub mod egl {
     pub mod config;
     pub mod context;
     pub mod display;
+    pub mod platform;
     pub mod surface;
 }
 
@@ -14,11 +15,21 @@ pub mod gles {
     pub mod error;
     pub mod types;
 
+    pub mod buffer;
+    pub mod capabilities;
+    pub mod context;
+    pub mod debug;
+    pub mod framebuffer;
     pub mod func;
+    pub mod profiler;
     pub mod program;
+    pub mod query;
     pub mod shader;
+    pub mod texture;
     pub mod vertex;
 }
 
 pub mod error;
 pub mod macros;
+
+mod loader;
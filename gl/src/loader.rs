@@ -0,0 +1,50 @@
+use std::ffi::{c_char, c_int, c_void, CStr, CString};
+
+const RTLD_NOW: c_int = 0x2;
+
+extern "C" {
+    fn dlopen(filename: *const c_char, flag: c_int) -> *mut c_void;
+    fn dlsym(handle: *mut c_void, symbol: *const c_char) -> *mut c_void;
+    fn dlerror() -> *mut c_char;
+}
+
+/// Shared library opened at runtime. It's never closed, the function pointers
+/// loaded from it live as long as the process.
+pub(crate) struct Library {
+    handle: *mut c_void,
+}
+
+unsafe impl Send for Library {}
+unsafe impl Sync for Library {}
+
+impl Library {
+    /// Opens the first of `names` that can be loaded.
+    pub(crate) fn open(names: &[&str]) -> std::result::Result<Library, String> {
+        let mut errors = Vec::new();
+        for name in names {
+            unsafe {
+                let c_name = CString::new(*name).unwrap();
+                let handle = dlopen(c_name.as_ptr(), RTLD_NOW);
+                if !handle.is_null() {
+                    return Ok(Library { handle });
+                }
+
+                let err = dlerror();
+                if err.is_null() {
+                    errors.push(format!("{name}: unknown error"));
+                } else {
+                    errors.push(CStr::from_ptr(err).to_string_lossy().into_owned());
+                }
+            }
+        }
+        Err(errors.join("; "))
+    }
+
+    pub(crate) fn sym(&self, name: &str) -> Option<*mut c_void> {
+        unsafe {
+            let c_name = CString::new(name).unwrap();
+            let ptr = dlsym(self.handle, c_name.as_ptr());
+            (!ptr.is_null()).then_some(ptr)
+        }
+    }
+}
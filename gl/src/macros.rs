@@ -6,6 +6,7 @@ macro_rules! raw_error {
                 #[doc = $desc:expr]
                 $val:ident = $const:ident,
             )*
+            $(_ => $unknown:ident,)?
         }
     ) => {
         #[derive(Debug)]
@@ -14,14 +15,19 @@ macro_rules! raw_error {
                 #[doc=$desc]
                 $val,
             )*
+            $(
+                /// Value this enum doesn't know.
+                $unknown(u32),
+            )?
         }
 
         impl Display for $enum {
             fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
                 use $enum::*;
                 match self {
-                    $($val => $desc,)*
-                }.fmt(f)
+                    $($val => $desc.fmt(f),)*
+                    $($unknown(value) => write!(f, "Unknown value {value:#x}."),)?
+                }
             }
         }
 
@@ -36,6 +42,7 @@ macro_rules! raw_error {
                 use $enum::*;
                 match value {
                     $($val => $const,)*
+                    $($unknown(value) => value as i32,)?
                 }
             }
         }
@@ -85,6 +92,12 @@ macro_rules! attr_list {
                 }
             )*
         }
+
+        impl Default for $struct {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
     };
 }
 
@@ -98,12 +111,18 @@ macro_rules! attr_bitstruct {
             )*
         }
     ) => {
+        #[derive(Debug, Clone, Copy)]
         $qual struct $struct(i32);
 
         impl $struct {
             pub fn new() -> Self {
                 Self($default)
             }
+
+            /// No bits set, unlike [`Self::new`] which starts from the default.
+            pub fn empty() -> Self {
+                Self(0)
+            }
             $(
                 $(#[$inner $($args)*])*
                 pub fn $func(mut self) -> Self {
@@ -113,6 +132,12 @@ macro_rules! attr_bitstruct {
             )*
         }
 
+        impl Default for $struct {
+            fn default() -> Self {
+                Self::new()
+            }
+        }
+
         impl From<$struct> for i32 {
             fn from(value: $struct) -> i32 {
                 value.0
@@ -131,7 +156,7 @@ macro_rules! attr_enum {
             )*
         }
     ) => {
-        #[derive(Debug, Clone, Copy)]
+        #[derive(Debug, Clone, Copy, PartialEq, Eq)]
         #[repr(i32)]
         $qual enum $enum {
             $(
@@ -145,5 +170,88 @@ macro_rules! attr_enum {
                 value as i32
             }
         }
+
+        impl TryFrom<i32> for $enum {
+            type Error = i32;
+            fn try_from(value: i32) -> std::result::Result<Self, i32> {
+                match value {
+                    $($const => Ok($enum::$val),)*
+                    e => Err(e)
+                }
+            }
+        }
+    };
+}
+
+/// Declares a table of entry points resolved at runtime along with `unsafe`
+/// wrappers named after them. The wrappers call through `$struct::get()`,
+/// which the declaring module provides.
+#[macro_export]
+macro_rules! dyn_binds {
+    (
+        $qual:vis struct $struct:ident;
+        $(
+            pub fn $func:ident($($arg:ident: $argty:ty),* $(,)?) $(-> $ret:ty)?;
+        )*
+    ) => {
+        #[allow(non_snake_case)]
+        $qual struct $struct {
+            $(pub $func: unsafe extern "C" fn($($argty),*) $(-> $ret)?,)*
+        }
+
+        impl $struct {
+            /// Resolves every entry point with `get`, failing on the first missing one.
+            unsafe fn load(
+                get: impl Fn(&str) -> Option<*mut std::ffi::c_void>,
+            ) -> std::result::Result<$struct, String> {
+                Ok($struct {
+                    $($func: match get(stringify!($func)) {
+                        Some(ptr) => std::mem::transmute::<
+                            *mut std::ffi::c_void,
+                            unsafe extern "C" fn($($argty),*) $(-> $ret)?,
+                        >(ptr),
+                        None => return Err(format!("missing `{}`", stringify!($func))),
+                    },)*
+                })
+            }
+        }
+
+        $(
+            #[allow(non_snake_case, clippy::missing_safety_doc, clippy::too_many_arguments)]
+            pub unsafe fn $func($($arg: $argty),*) $(-> $ret)? {
+                ($struct::get().$func)($($arg),*)
+            }
+        )*
+    };
+}
+
+/// Declares a table of optional entry points, each `None` when the
+/// implementation doesn't provide it.
+#[macro_export]
+macro_rules! dyn_ext_binds {
+    (
+        $qual:vis struct $struct:ident;
+        $(
+            pub fn $func:ident($($arg:ident: $argty:ty),* $(,)?) $(-> $ret:ty)?;
+        )*
+    ) => {
+        #[allow(non_snake_case)]
+        $qual struct $struct {
+            $(pub $func: Option<unsafe extern "C" fn($($argty),*) $(-> $ret)?>,)*
+        }
+
+        impl $struct {
+            #[allow(unused_variables)]
+            unsafe fn load(get: impl Fn(&str) -> Option<*mut std::ffi::c_void>) -> $struct {
+                $struct {
+                    $($func: get(stringify!($func)).map(|ptr| {
+                        std::mem::transmute::<
+                            *mut std::ffi::c_void,
+                            unsafe extern "C" fn($($argty),*) $(-> $ret)?,
+                        >(ptr)
+                    }),)*
+                }
+            }
+        }
     };
 }
@@ -4,7 +4,7 @@ use dagt_core::interface::app::{App as AppTrait, AppBuilder};
 #[derive(Default)]
 pub struct App {
     pub(crate) id: String,
-    pub(crate) window: Option<<App as AppBuilder>::PlatformWindowBuilder>,
+    pub(crate) windows: Vec<<App as AppBuilder>::PlatformWindowBuilder>,
 }
 
 impl App {
@@ -23,8 +23,10 @@ impl AppBuilder for App {
         self
     }
 
+    /// Adds a window opened at startup, can be called once per window. The
+    /// app exits once all of its windows are closed.
     fn window(mut self, window: Self::PlatformWindowBuilder) -> Self {
-        self.window = Some(window);
+        self.windows.push(window);
         self
     }
 
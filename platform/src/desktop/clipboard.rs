@@ -0,0 +1,41 @@
+use super::drag::{DndAction, DragIcon};
+use std::rc::Rc;
+
+/// Selection copied data is offered on.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub enum Selection {
+    /// Copied and pasted explicitly, with Ctrl+C and Ctrl+V.
+    Clipboard,
+    /// Selected text, pasted with a middle click.
+    Primary,
+}
+
+/// MIME types text is offered and read as, the preferred one first.
+pub const TEXT_MIME_TYPES: &[&str] = &[
+    "text/plain;charset=utf-8",
+    "UTF8_STRING",
+    "text/plain",
+    "STRING",
+    "TEXT",
+];
+
+/// Makes the contents of a selection in the requested MIME type.
+pub(crate) type SelectionData = Rc<dyn Fn(&str) -> Vec<u8>>;
+
+pub(crate) enum ClipboardRequest {
+    Set {
+        selection: Selection,
+        mime_types: Vec<String>,
+        data: SelectionData,
+    },
+    Read {
+        selection: Selection,
+        mime_type: String,
+    },
+    Drag {
+        mime_types: Vec<String>,
+        actions: Vec<DndAction>,
+        icon: Option<DragIcon>,
+        data: SelectionData,
+    },
+}
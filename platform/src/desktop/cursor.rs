@@ -0,0 +1,105 @@
+use std::cell::Cell;
+
+/// Cursor image, named after the CSS cursors.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq, Hash)]
+pub enum CursorIcon {
+    #[default]
+    Default,
+    ContextMenu,
+    Help,
+    /// Link or other interactive element.
+    Pointer,
+    /// Busy in the background, the app can still be used.
+    Progress,
+    /// Busy, the app can't be used.
+    Wait,
+    Cell,
+    Crosshair,
+    /// Selectable text.
+    Text,
+    VerticalText,
+    Alias,
+    Copy,
+    Move,
+    NoDrop,
+    NotAllowed,
+    Grab,
+    Grabbing,
+    EResize,
+    NResize,
+    NeResize,
+    NwResize,
+    SResize,
+    SeResize,
+    SwResize,
+    WResize,
+    EwResize,
+    NsResize,
+    NeswResize,
+    NwseResize,
+    ColResize,
+    RowResize,
+    AllScroll,
+    ZoomIn,
+    ZoomOut,
+}
+
+impl CursorIcon {
+    /// Names of the icon in XCursor themes, the CSS name first, then the
+    /// names of older themes.
+    pub fn names(&self) -> &'static [&'static str] {
+        use CursorIcon::*;
+        match self {
+            Default => &["default", "left_ptr"],
+            ContextMenu => &["context-menu"],
+            Help => &["help", "question_arrow", "whats_this"],
+            Pointer => &["pointer", "hand2", "hand1", "pointing_hand"],
+            Progress => &["progress", "left_ptr_watch", "half-busy"],
+            Wait => &["wait", "watch"],
+            Cell => &["cell", "plus"],
+            Crosshair => &["crosshair", "cross", "tcross"],
+            Text => &["text", "xterm", "ibeam"],
+            VerticalText => &["vertical-text"],
+            Alias => &["alias", "link", "dnd-link"],
+            Copy => &["copy", "dnd-copy"],
+            Move => &["move", "dnd-move", "fleur"],
+            NoDrop => &["no-drop", "dnd-none", "circle"],
+            NotAllowed => &["not-allowed", "crossed_circle", "forbidden", "circle"],
+            Grab => &["grab", "openhand", "hand1"],
+            Grabbing => &["grabbing", "closedhand", "dnd-none"],
+            EResize => &["e-resize", "right_side"],
+            NResize => &["n-resize", "top_side"],
+            NeResize => &["ne-resize", "top_right_corner"],
+            NwResize => &["nw-resize", "top_left_corner"],
+            SResize => &["s-resize", "bottom_side"],
+            SeResize => &["se-resize", "bottom_right_corner"],
+            SwResize => &["sw-resize", "bottom_left_corner"],
+            WResize => &["w-resize", "left_side"],
+            EwResize => &["ew-resize", "sb_h_double_arrow", "h_double_arrow"],
+            NsResize => &["ns-resize", "sb_v_double_arrow", "v_double_arrow"],
+            NeswResize => &["nesw-resize", "fd_double_arrow", "size_bdiag"],
+            NwseResize => &["nwse-resize", "bd_double_arrow", "size_fdiag"],
+            ColResize => &["col-resize", "split_h", "sb_h_double_arrow"],
+            RowResize => &["row-resize", "split_v", "sb_v_double_arrow"],
+            AllScroll => &["all-scroll", "fleur"],
+            ZoomIn => &["zoom-in"],
+            ZoomOut => &["zoom-out"],
+        }
+    }
+}
+
+thread_local! {
+    static REQUESTED: Cell<Option<CursorIcon>> = const { Cell::new(None) };
+}
+
+/// Shows `icon` while the pointer is over the widget handling the current
+/// pointer event. Widgets call it from `handle_event`, the default cursor is
+/// shown where no widget sets one.
+pub fn set(icon: CursorIcon) {
+    REQUESTED.with(|r| r.set(Some(icon)));
+}
+
+/// Takes the icon set since the last call.
+pub(crate) fn take() -> Option<CursorIcon> {
+    REQUESTED.with(|r| r.take())
+}
@@ -0,0 +1,166 @@
+use dagt_core::interface::draw::{Constraints, Resize};
+use std::{cell::Cell, collections::VecDeque};
+
+/// Oldest back buffer whose contents are still reused instead of repainted.
+const MAX_AGE: usize = 4;
+
+thread_local! {
+    static REQUESTED: Cell<Constraints> = const {
+        Cell::new(Constraints {
+            x: 0,
+            y: 0,
+            width: 0,
+            height: 0,
+            horizontal_resize: Resize::Fixed,
+            verticle_resize: Resize::Fixed,
+        })
+    };
+}
+
+/// Repaints `area` of the window, in logical pixels, in the next frame.
+/// Widgets whose look changed without their builder's state changing call it
+/// from `handle_event`, or from `draw` to be drawn again in the next frame.
+pub fn add(area: Constraints) {
+    REQUESTED.with(|r| r.set(r.get().union(area)));
+}
+
+/// Takes the area requested since the last call, `None` when it's empty.
+pub(crate) fn take() -> Option<Constraints> {
+    let area = REQUESTED.with(|r| r.take());
+    (area.width > 0 && area.height > 0).then_some(area)
+}
+
+/// Areas of the window damaged since the last frame and during the frames
+/// before it, used to find what has to be repainted in a back buffer that was
+/// last drawn `age` frames ago.
+#[derive(Default)]
+pub(crate) struct Damage {
+    pending: Constraints,
+    full: bool,
+    history: VecDeque<Constraints>,
+}
+
+impl Damage {
+    pub(crate) fn add(&mut self, area: Constraints) {
+        self.pending = self.pending.union(area);
+    }
+
+    pub(crate) fn add_full(&mut self) {
+        self.full = true;
+    }
+
+    /// Returns the area to repaint in a back buffer of `age`, and the area
+    /// that changed since the last frame to report to the compositor. `None`
+    /// when nothing in `bounds` changed, no frame is drawn then.
+    pub(crate) fn frame(
+        &mut self,
+        age: i32,
+        bounds: Constraints,
+    ) -> Option<(Constraints, Constraints)> {
+        let damage = if self.full {
+            bounds
+        } else {
+            self.pending.intersect(bounds)
+        };
+        self.full = false;
+        self.pending = Constraints::default();
+        if damage.width <= 0 || damage.height <= 0 {
+            return None;
+        }
+
+        let repaint = if age <= 0 || age as usize > self.history.len() + 1 {
+            bounds
+        } else {
+            self.history
+                .iter()
+                .take(age as usize - 1)
+                .fold(damage, |repaint, prev| repaint.union(*prev))
+        };
+
+        self.history.push_front(damage);
+        self.history.truncate(MAX_AGE);
+
+        Some((repaint, damage))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn area(x: i32, y: i32, width: i32, height: i32) -> Constraints {
+        Constraints {
+            x,
+            y,
+            width,
+            height,
+            ..Default::default()
+        }
+    }
+
+    const BOUNDS: Constraints = Constraints {
+        x: 0,
+        y: 0,
+        width: 100,
+        height: 100,
+        horizontal_resize: Resize::Fixed,
+        verticle_resize: Resize::Fixed,
+    };
+
+    #[test]
+    fn nothing_changed() {
+        let mut damage = Damage::default();
+        assert_eq!(damage.frame(1, BOUNDS), None);
+
+        // outside the window
+        damage.add(area(200, 200, 10, 10));
+        assert_eq!(damage.frame(1, BOUNDS), None);
+    }
+
+    #[test]
+    fn full() {
+        let mut damage = Damage::default();
+        damage.add(area(10, 10, 5, 5));
+        damage.add_full();
+        assert_eq!(damage.frame(1, BOUNDS), Some((BOUNDS, BOUNDS)));
+    }
+
+    #[test]
+    fn clipped_to_bounds() {
+        let mut damage = Damage::default();
+        damage.add(area(90, 90, 20, 20));
+        let clipped = area(90, 90, 10, 10);
+        assert_eq!(damage.frame(1, BOUNDS), Some((clipped, clipped)));
+    }
+
+    #[test]
+    fn unknown_age_repaints_everything() {
+        let mut damage = Damage::default();
+        damage.add(area(10, 10, 5, 5));
+        assert_eq!(damage.frame(0, BOUNDS), Some((BOUNDS, area(10, 10, 5, 5))));
+
+        // older than the history
+        damage.add(area(10, 10, 5, 5));
+        assert_eq!(damage.frame(3, BOUNDS), Some((BOUNDS, area(10, 10, 5, 5))));
+    }
+
+    #[test]
+    fn age_adds_previous_frames() {
+        let mut damage = Damage::default();
+        damage.add(area(0, 0, 10, 10));
+        damage.frame(1, BOUNDS);
+        damage.add(area(20, 20, 10, 10));
+        damage.frame(1, BOUNDS);
+
+        damage.add(area(50, 50, 10, 10));
+        let (repaint, changed) = damage.frame(3, BOUNDS).unwrap();
+        assert_eq!(changed, area(50, 50, 10, 10));
+        assert_eq!(repaint, area(0, 0, 60, 60));
+
+        // frames without changes aren't drawn, so they don't age the buffers
+        assert_eq!(damage.frame(1, BOUNDS), None);
+        damage.add(area(50, 50, 10, 10));
+        let (repaint, _) = damage.frame(2, BOUNDS).unwrap();
+        assert_eq!(repaint, area(50, 50, 10, 10));
+    }
+}
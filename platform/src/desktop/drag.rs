@@ -0,0 +1,41 @@
+use std::cell::Cell;
+
+/// What dropping the dragged data does.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum DndAction {
+    Copy,
+    /// The source deletes its data once dropped.
+    Move,
+    /// The target asks the user. Drops accepted with it resolve to copying,
+    /// or moving when the source only moves.
+    Ask,
+}
+
+/// Image shown under the pointer while dragging, in physical pixels of the
+/// window starting the drag.
+#[derive(Debug, Clone, PartialEq)]
+pub struct DragIcon {
+    pub width: u32,
+    pub height: u32,
+    /// RGBA pixels, top row first.
+    pub pixels: Vec<u8>,
+    /// Point of the image under the pointer, in its pixels.
+    pub hotspot: (i32, i32),
+}
+
+thread_local! {
+    static ACCEPTED: Cell<Option<(String, DndAction)>> = const { Cell::new(None) };
+}
+
+/// Accepts the drag over the widget handling the current drag event, to be
+/// dropped as `mime_type` with `action`. Widgets call it from `handle_event`
+/// on `DragEvent::Enter` and `DragEvent::Motion`, drags no widget accepts are
+/// refused.
+pub fn accept(mime_type: &str, action: DndAction) {
+    ACCEPTED.with(|a| a.set(Some((mime_type.to_owned(), action))));
+}
+
+/// Takes the MIME type and action accepted since the last call.
+pub(crate) fn take() -> Option<(String, DndAction)> {
+    ACCEPTED.with(|a| a.take())
+}
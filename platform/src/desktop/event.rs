@@ -1,4 +1,4 @@
-use super::wayland::app::globals::seat::keyboard::Modifiers;
+use super::{clipboard::Selection, drag::DndAction};
 use dagt_core::interface::event::Event;
 use xkeysym::Keysym;
 
@@ -7,6 +7,26 @@ pub enum Button {
     Left,
     Right,
     Middle,
+    Side,
+    Extra,
+    Forward,
+    Back,
+    /// Any other button, by its Linux input event code.
+    Other(u32),
+}
+
+/// Device a scroll comes from.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum ScrollSource {
+    Wheel,
+    /// Fingers on a touchpad or touchscreen.
+    Finger,
+    /// Continuous motion without steps, like a trackpoint.
+    Continuous,
+    /// Sideways tilt of a wheel.
+    WheelTilt,
+    /// Momentum continuing a finger scroll after the fingers were lifted.
+    Kinetic,
 }
 
 pub enum PointerEvent {
@@ -15,29 +35,180 @@ pub enum PointerEvent {
     Motion,
     ButtonPressed(Button),
     ButtonReleased(Button),
-    ButtonClicked(Button),
+    /// Button released without moving since its press. `count` is the
+    /// number of clicks in a row, `2` for a double click, `3` for a triple
+    /// click.
+    ButtonClicked {
+        button: Button,
+        count: u32,
+    },
+    /// Scroll by `dx`, `dy` pixels, positive values scroll right and down.
+    ///
+    /// `discrete` counts the wheel notches on each axis, fractional for high
+    /// resolution wheels, `None` for sources without notches. `inverted` is
+    /// set for natural scrolling, where the content follows the fingers.
+    Scroll {
+        dx: f64,
+        dy: f64,
+        source: ScrollSource,
+        discrete: Option<(f64, f64)>,
+        inverted: bool,
+    },
+}
+
+/// Finger on a touchscreen.
+pub enum TouchEvent {
+    Down,
+    Up,
+    Motion,
+    /// The compositor took the touch over, for a gesture of its own. The
+    /// point is gone without an `Up`.
+    Cancel,
+    /// Contact area of the point changed, an ellipse of `major` and `minor`
+    /// axes in pixels.
+    Shape {
+        major: f64,
+        minor: f64,
+    },
+    /// Major axis of the contact area turned, `orientation` is its angle
+    /// clockwise from the vertical in degrees.
+    Orientation(f64),
+}
+
+/// Drag and drop over the window, or of a drag it started.
+pub enum DragEvent {
+    /// A drag offering `mime_types` entered the window, the source allows
+    /// `actions`.
+    Enter {
+        mime_types: Vec<String>,
+        actions: Vec<DndAction>,
+    },
+    Motion,
+    Leave,
+    /// The drag was dropped with `action`, the contents follow in a `Data`
+    /// event at the same position.
+    Drop {
+        action: DndAction,
+    },
+    /// Contents of a drop, in the MIME type accepted.
+    Data {
+        mime_type: String,
+        data: Vec<u8>,
+    },
+    /// A drag started by the window was dropped with `action`. The source
+    /// deletes the dragged data after a move.
+    SourceFinished {
+        action: DndAction,
+    },
+    /// A drag started by the window was refused or cancelled.
+    SourceCancelled,
+}
+
+/// Modifier keys held down or locked.
+#[derive(Debug, Clone, Copy, Default, PartialEq, Eq)]
+pub struct Modifiers {
+    pub ctrl: bool,
+    pub alt: bool,
+    pub shift: bool,
+    pub logo: bool,
+    pub caps_lock: bool,
+    pub num_lock: bool,
+}
+
+/// Meaning of a key in the current layout, independent of the platform.
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub enum Key {
+    /// Key typing text, as typed with the current modifiers except Ctrl and
+    /// Alt, `" "` for the space bar.
+    Character(String),
+    Enter,
+    Tab,
+    Backspace,
+    Delete,
+    Escape,
+    Insert,
+    Home,
+    End,
+    PageUp,
+    PageDown,
+    ArrowLeft,
+    ArrowRight,
+    ArrowUp,
+    ArrowDown,
+    Shift,
+    Control,
+    Alt,
+    AltGraph,
+    Super,
+    CapsLock,
+    NumLock,
+    ScrollLock,
+    PrintScreen,
+    Pause,
+    ContextMenu,
+    /// Function key, from `F(1)` to `F(24)`.
+    F(u8),
+    /// Dead key, or key continuing a compose sequence, typing nothing until
+    /// the sequence completes.
+    Dead,
+    Unidentified,
 }
 
 pub enum KeyboardEvent {
     Enter,
     Leave,
+    /// Key pressed, or held down long enough to repeat when `repeat` is set.
+    ///
+    /// `key` is the raw key code and `logical` its meaning. `utf8` is the
+    /// text typed, the result of a completed compose sequence included.
     KeyPressed {
         key: u32,
+        logical: Key,
         keysym: Keysym,
         utf8: String,
+        modifiers: Modifiers,
+        repeat: bool,
     },
     KeyReleased {
         key: u32,
+        logical: Key,
         keysym: Keysym,
         utf8: String,
+        modifiers: Modifiers,
     },
     Modifiers(Modifiers),
 }
 
 pub enum GlobalEvent {
-    Pointer { e: PointerEvent, x: f64, y: f64 },
+    Pointer {
+        e: PointerEvent,
+        x: f64,
+        y: f64,
+    },
     Keyboard(KeyboardEvent),
+    /// Touch of the point `id`, unique among the points down. Touches no
+    /// widget handles are sent again as left button pointer events.
+    Touch {
+        e: TouchEvent,
+        id: i32,
+        x: f64,
+        y: f64,
+    },
+    Drag {
+        e: DragEvent,
+        x: f64,
+        y: f64,
+    },
+    /// Contents of a selection read for the window.
+    ClipboardData {
+        selection: Selection,
+        mime_type: String,
+        data: Vec<u8>,
+    },
     Configure,
+    /// The window is drawn at a new scale, in physical pixels per logical
+    /// pixel.
+    ScaleChanged(f64),
     Close,
 }
 
@@ -0,0 +1,129 @@
+use dagt_core::interface::draw::{Constraints, Draw};
+use dagt_fonts::{Font, DEFAULT_FONT};
+use dagt_gl::gles::profiler::FrameStats;
+use dagt_primitives::{color::Color, glyph::Glyph, rect::Rect};
+use std::{cell::RefCell, collections::HashMap, sync::OnceLock, time::Duration};
+
+const FONT_SIZE: f64 = 12.0;
+const LINE_HEIGHT: i32 = 16;
+const PADDING: i32 = 8;
+const TEXT_COLUMNS: i32 = 36;
+const BAR_WIDTH: i32 = 60;
+const BAR_HEIGHT: i32 = 10;
+
+/// Draws the profiler `stats` in the top left corner of a window of
+/// `constraints`: frame time, draw calls and a line per profiled scope
+/// with a bar of its share of the frame.
+pub(crate) fn draw(stats: &FrameStats, constraints: Constraints) {
+    static FONT: OnceLock<Font> = OnceLock::new();
+    let font = FONT.get_or_init(|| Font::load(DEFAULT_FONT));
+    // the font is monospaced
+    let advance = font.get_glyph('0').advance_width as f64 * em(font);
+
+    let mut lines = vec![
+        (format!("frame {}", times(stats.cpu, stats.gpu)), None),
+        (format!("draw calls {}", stats.draw_calls), None),
+    ];
+    for scope in &stats.scopes {
+        let share = match (scope.gpu, stats.gpu) {
+            (Some(gpu), Some(frame)) => gpu.as_secs_f32() / frame.as_secs_f32(),
+            _ => scope.cpu.as_secs_f32() / stats.cpu.as_secs_f32(),
+        };
+        let text = format!(
+            "{}{} {}",
+            "  ".repeat(scope.depth),
+            scope.name,
+            times(scope.cpu, scope.gpu)
+        );
+        lines.push((text, Some(share)));
+    }
+
+    let text_width = (TEXT_COLUMNS as f64 * advance) as i32;
+    let width = text_width + BAR_WIDTH + 2 * PADDING;
+    let height = lines.len() as i32 * LINE_HEIGHT + 2 * PADDING;
+    let top = constraints.y + constraints.height - PADDING;
+    let left = constraints.x + PADDING;
+
+    rect(Color::rgba(0, 0, 0, 180), left, top - height, width, height);
+
+    for (i, (text, share)) in lines.iter().enumerate() {
+        let y = top - PADDING - (i as i32 + 1) * LINE_HEIGHT;
+        draw_text(font, text, left + PADDING, y);
+
+        if let Some(share) = share {
+            let share = share.clamp(0.0, 1.0);
+            let bar_x = left + PADDING + text_width;
+            rect(Color::rgb(60, 60, 60), bar_x, y, BAR_WIDTH, BAR_HEIGHT);
+            rect(
+                Color::rgb(90, 180, 90),
+                bar_x,
+                y,
+                (share * BAR_WIDTH as f32) as i32,
+                BAR_HEIGHT,
+            );
+        }
+    }
+}
+
+/// CPU and GPU times in milliseconds, `-` for an unknown GPU time.
+fn times(cpu: Duration, gpu: Option<Duration>) -> String {
+    let ms = |d: Duration| format!("{:.2}", d.as_secs_f64() * 1000.0);
+    format!(
+        "{}/{} ms",
+        ms(cpu),
+        gpu.map(ms).unwrap_or_else(|| "-".to_owned())
+    )
+}
+
+fn rect(color: Color, x: i32, y: i32, width: i32, height: i32) {
+    if width <= 0 || height <= 0 {
+        return;
+    }
+    Rect {
+        bg_color: color,
+        ..Default::default()
+    }
+    .draw(Constraints {
+        x,
+        y,
+        width,
+        height,
+        ..Default::default()
+    });
+}
+
+/// Pixels per font unit.
+fn em(font: &Font) -> f64 {
+    FONT_SIZE / font.units_per_em() as f64
+}
+
+/// Draws `text` with its baseline at `y`.
+fn draw_text(font: &Font, text: &str, x: i32, y: i32) {
+    thread_local! {
+        /// Glyphs drawn so far, keeping what the backend made of them.
+        static GLYPHS: RefCell<HashMap<char, Glyph>> = RefCell::new(HashMap::new());
+    }
+
+    let em = em(font);
+    let mut pen = x as f64;
+    for c in text.chars() {
+        let glyph = font.get_glyph(c);
+        if c != ' ' {
+            let constr = Constraints {
+                x: (pen + glyph.min_x as f64 * em) as i32,
+                y: (y as f64 + glyph.min_y as f64 * em) as i32,
+                width: (glyph.width as f64 * em) as i32,
+                height: (glyph.height as f64 * em) as i32,
+                ..Default::default()
+            };
+            GLYPHS.with(|glyphs| {
+                glyphs
+                    .borrow_mut()
+                    .entry(c)
+                    .or_insert_with(|| Glyph::new(glyph.clone()))
+                    .draw(constr)
+            });
+        }
+        pen += glyph.advance_width as f64 * em;
+    }
+}
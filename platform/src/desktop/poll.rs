@@ -0,0 +1,59 @@
+use std::{
+    ffi::{c_int, c_short, c_ulong},
+    io,
+    time::Duration,
+};
+
+const POLLIN: c_short = 0x1;
+const POLLOUT: c_short = 0x4;
+
+#[repr(C)]
+pub(crate) struct PollFd {
+    fd: c_int,
+    events: c_short,
+    revents: c_short,
+}
+
+impl PollFd {
+    pub(crate) fn readable(fd: c_int) -> PollFd {
+        PollFd {
+            fd,
+            events: POLLIN,
+            revents: 0,
+        }
+    }
+
+    pub(crate) fn writable(fd: c_int) -> PollFd {
+        PollFd {
+            fd,
+            events: POLLOUT,
+            revents: 0,
+        }
+    }
+
+    pub(crate) fn is_ready(&self) -> bool {
+        self.revents != 0
+    }
+}
+
+extern "C" {
+    fn poll(fds: *mut PollFd, nfds: c_ulong, timeout: c_int) -> c_int;
+}
+
+/// Waits until one of `fds` is ready or `timeout` passed, returning whether
+/// any is ready. Waits indefinitely without a timeout.
+pub(crate) fn poll_fds(fds: &mut [PollFd], timeout: Option<Duration>) -> io::Result<bool> {
+    let timeout = timeout.map_or(-1, |t| t.as_millis().min(c_int::MAX as u128) as c_int);
+    let res = unsafe { poll(fds.as_mut_ptr(), fds.len() as c_ulong, timeout) };
+    match res {
+        n if n > 0 => Ok(true),
+        0 => Ok(false),
+        _ => {
+            let err = io::Error::last_os_error();
+            match err.kind() {
+                io::ErrorKind::Interrupted => Ok(false),
+                _ => Err(err),
+            }
+        }
+    }
+}
@@ -0,0 +1,98 @@
+use super::poll::PollFd;
+use std::{
+    ffi::{c_char, c_int, c_void, CString, OsStr},
+    io,
+    os::unix::ffi::OsStrExt,
+    path::Path,
+};
+
+const IN_NONBLOCK: c_int = 0o4000;
+const IN_CLOEXEC: c_int = 0o2000000;
+const IN_CLOSE_WRITE: u32 = 0x008;
+const IN_MOVED_TO: u32 = 0x080;
+
+#[repr(C)]
+struct InotifyEvent {
+    wd: c_int,
+    mask: u32,
+    cookie: u32,
+    len: u32,
+}
+
+extern "C" {
+    fn inotify_init1(flags: c_int) -> c_int;
+    fn inotify_add_watch(fd: c_int, pathname: *const c_char, mask: u32) -> c_int;
+    fn read(fd: c_int, buf: *mut c_void, count: usize) -> isize;
+    fn close(fd: c_int) -> c_int;
+}
+
+/// Watches the files of a directory with inotify.
+///
+/// The directory is watched rather than the files, editors often save by
+/// replacing the file.
+pub(crate) struct FileWatcher {
+    fd: c_int,
+}
+
+impl FileWatcher {
+    pub(crate) fn new(dir: &Path) -> io::Result<FileWatcher> {
+        unsafe {
+            let fd = inotify_init1(IN_NONBLOCK | IN_CLOEXEC);
+            if fd < 0 {
+                return Err(io::Error::last_os_error());
+            }
+            let watcher = FileWatcher { fd };
+
+            let path = CString::new(dir.as_os_str().as_bytes())?;
+            // files are reported once written, not when created empty
+            let mask = IN_CLOSE_WRITE | IN_MOVED_TO;
+            if inotify_add_watch(fd, path.as_ptr(), mask) < 0 {
+                return Err(io::Error::last_os_error());
+            }
+
+            Ok(watcher)
+        }
+    }
+
+    /// Poll entry waiting for changes.
+    pub(crate) fn poll_fd(&self) -> PollFd {
+        PollFd::readable(self.fd)
+    }
+
+    /// Names of the files changed since the last call, without blocking.
+    pub(crate) fn changed(&self) -> Vec<String> {
+        let mut names = Vec::new();
+        let mut buf = [0u8; 4096];
+        loop {
+            let len = unsafe { read(self.fd, buf.as_mut_ptr() as *mut c_void, buf.len()) };
+            if len <= 0 {
+                break;
+            }
+
+            let mut offset = 0;
+            while offset < len as usize {
+                let event = unsafe {
+                    std::ptr::read_unaligned(buf[offset..].as_ptr() as *const InotifyEvent)
+                };
+                let start = offset + std::mem::size_of::<InotifyEvent>();
+                let name = &buf[start..start + event.len as usize];
+                // the name is padded with nul bytes
+                let name = name.split(|b| *b == 0).next().unwrap_or_default();
+                let name = OsStr::from_bytes(name).to_string_lossy().into_owned();
+                if !name.is_empty() && !names.contains(&name) {
+                    names.push(name);
+                }
+                offset = start + event.len as usize;
+            }
+        }
+        names
+    }
+}
+
+impl Drop for FileWatcher {
+    fn drop(&mut self) {
+        unsafe {
+            close(self.fd);
+        }
+    }
+}
@@ -1,51 +1,117 @@
 use self::globals::{
-    seat::{keyboard::Keyboard, pointer::Pointer, Seat},
+    clipboard::{Clipboard, ClipboardHandler, Transfer},
+    cursor::Cursors,
+    output::{OutputHandler, Outputs},
+    seat::{
+        keyboard::Keyboard,
+        pointer::{Pointer, CLICK_DISTANCE},
+        touch::{Touch, TouchHandler},
+        Seat,
+    },
+    shm::Shm,
     Globals,
 };
-use super::window::{WaylandWindow, WindowState};
+use super::window::{WaylandWindow, WindowState, WindowSurface};
+#[cfg(feature = "hot-reload")]
+use crate::desktop::watcher::FileWatcher;
 use crate::desktop::{
     app::App as AppBuilder,
-    event::{Button, GlobalEvent, KeyboardEvent, PointerEvent},
+    clipboard::{ClipboardRequest, Selection},
+    cursor,
+    drag::{self, DndAction},
+    event::{
+        Button, DragEvent, GlobalEvent, KeyboardEvent, Modifiers, PointerEvent, ScrollSource,
+        TouchEvent,
+    },
+    poll::{self, PollFd},
     wayland::window::WindowConfigure,
+    window::Window as WindowBuilder,
 };
+#[cfg(feature = "hot-reload")]
+use dagt_core::interface::window::Window;
 use dagt_core::interface::{
     app::App,
     draw::{Constraints, Draw},
     event::EventHandler,
 };
 use dagt_gl::egl::{
-    config::attrs::{EglConfAttrs, EglSurfaceType},
-    context::attrs::EglCtxAttrs,
+    binds as egl,
+    config::{chooser::EglConfChooser, EglConfig},
+    context::{attrs::EglCtxAttrs, EglContext},
     display::EglDisplay,
     types::{NativeDisplayType, NativeWindowType},
 };
+use dagt_gl::gles::{
+    binds as gl,
+    debug::{self, GlDebugMessage, StderrLogger},
+    profiler,
+};
+#[cfg(feature = "hot-reload")]
+use dagt_primitives::shaders;
 use globals::seat::{
     keyboard::KeyboardHandler,
     pointer::{ButtonState, PointerHandler},
 };
-use std::sync::Mutex;
+use std::{
+    io,
+    os::fd::AsRawFd,
+    sync::Mutex,
+    time::{Duration, Instant},
+};
 use wayland_client::{
+    backend::WaylandError,
     delegate_dispatch,
     globals::GlobalListContents,
     protocol::{
+        wl_buffer::WlBuffer,
         wl_compositor::WlCompositor,
-        wl_keyboard::{KeyState, WlKeyboard},
+        wl_data_device::WlDataDevice,
+        wl_data_device_manager::WlDataDeviceManager,
+        wl_data_offer::WlDataOffer,
+        wl_data_source::WlDataSource,
+        wl_keyboard::WlKeyboard,
+        wl_output::WlOutput,
         wl_pointer::WlPointer,
-        wl_registry::WlRegistry,
+        wl_registry::{self, WlRegistry},
         wl_seat::WlSeat,
+        wl_shm::WlShm,
+        wl_shm_pool::WlShmPool,
         wl_surface::{self, WlSurface},
+        wl_touch::WlTouch,
     },
-    Dispatch, EventQueue, Proxy,
+    Dispatch, DispatchError, EventQueue, Proxy, QueueHandle,
 };
 use wayland_egl::WlEglSurface;
-use wayland_protocols::xdg::shell::client::{
-    xdg_surface::{self, XdgSurface},
-    xdg_toplevel::{self, XdgToplevel},
-    xdg_wm_base::{self, XdgWmBase},
+use wayland_protocols::{
+    wp::{
+        cursor_shape::v1::client::{
+            wp_cursor_shape_device_v1::WpCursorShapeDeviceV1,
+            wp_cursor_shape_manager_v1::WpCursorShapeManagerV1,
+        },
+        fractional_scale::v1::client::{
+            wp_fractional_scale_manager_v1::WpFractionalScaleManagerV1,
+            wp_fractional_scale_v1::{self, WpFractionalScaleV1},
+        },
+        primary_selection::zv1::client::{
+            zwp_primary_selection_device_manager_v1::ZwpPrimarySelectionDeviceManagerV1,
+            zwp_primary_selection_device_v1::ZwpPrimarySelectionDeviceV1,
+            zwp_primary_selection_offer_v1::ZwpPrimarySelectionOfferV1,
+            zwp_primary_selection_source_v1::ZwpPrimarySelectionSourceV1,
+        },
+        viewporter::client::{wp_viewport::WpViewport, wp_viewporter::WpViewporter},
+    },
+    xdg::shell::client::{
+        xdg_surface::{self, XdgSurface},
+        xdg_toplevel::{self, XdgToplevel},
+        xdg_wm_base::{self, XdgWmBase},
+    },
 };
 
 pub mod globals;
 
+/// Time between the steps of kinetic scrolls.
+const KINETIC_INTERVAL: Duration = Duration::from_millis(16);
+
 pub struct WaylandApp {
     state: Mutex<AppState>,
 }
@@ -61,37 +127,142 @@ impl App<AppBuilder> for WaylandApp {
     }
 }
 
-struct AppState {
+pub(super) struct AppState {
+    // dropped first, window surfaces go before the context and the connection
+    windows: Vec<WaylandWindow>,
     event_queue: Option<EventQueue<AppState>>,
+    qh: QueueHandle<AppState>,
     globals: Globals,
-    _id: String,
-    window: WaylandWindow,
+    id: String,
+    /// `None` when EGL couldn't be set up, windows are drawn on the CPU into
+    /// shared memory then.
+    egl: Option<Egl>,
+    #[cfg(feature = "hot-reload")]
+    shader_watcher: FileWatcher,
+    /// Touch point emulating the pointer, for the widgets that don't handle
+    /// touches.
+    touch_pointer: Option<TouchPointer>,
     running: bool,
 }
 
+/// Touch point sent as left button pointer events, from where it touched.
+struct TouchPointer {
+    id: i32,
+    start: (f64, f64),
+}
+
 #[derive(Clone, Copy)]
-struct AppData;
+pub(super) struct AppData;
+
+/// EGL objects all windows draw with.
+struct Egl {
+    // fields drop in order, the context goes before its display
+    context: EglContext,
+    config: EglConfig,
+    display: EglDisplay,
+    /// Samples the config was chosen for.
+    samples: i32,
+    /// Whether the context was created with the debug bit.
+    debug: bool,
+}
+
+impl Egl {
+    fn new(globals: &Globals, win_builders: &[WindowBuilder]) -> Result<Egl, String> {
+        egl::load().map_err(|e| e.to_string())?;
+        gl::load().map_err(|e| e.to_string())?;
+
+        let display =
+            EglDisplay::get(globals.connection().display().id().as_ptr() as NativeDisplayType)
+                .ok_or("failed to get EGL display")?;
+        let (major, minor) = display
+            .init()
+            .map_err(|e| format!("failed to initialize EGL display: {e}"))?;
+        println!("EGL version: {major}.{minor}");
+
+        // all windows draw with one context, so their surfaces share a config
+        let samples = win_builders.iter().map(|w| w.samples).max().unwrap_or(0);
+        let config = EglConfChooser::new()
+            .samples(samples)
+            .choose(&display)
+            .ok_or("failed to get EGL config")?;
+
+        let debug = win_builders.iter().any(|w| w.debug);
+        let context = display
+            .create_context(
+                &config,
+                None,
+                Some(
+                    &EglCtxAttrs::new()
+                        .major_version(3)
+                        .minor_version(2)
+                        .debug(debug),
+                ),
+            )
+            .map_err(|e| format!("failed to get EGL context: {e}"))?;
+
+        Ok(Egl {
+            display,
+            config,
+            context,
+            samples,
+            debug,
+        })
+    }
+}
 
 impl AppState {
     fn new(builder: AppBuilder) -> AppState {
         let (globals, eq) = Globals::new(AppData);
         let qh = eq.handle();
-        let id = builder.id;
 
-        let win_builder = builder.window.unwrap_or_default();
+        let mut win_builders = builder.windows;
+        if win_builders.is_empty() {
+            win_builders.push(Default::default());
+        }
+
+        let egl = Egl::new(&globals, &win_builders)
+            .inspect_err(|e| eprintln!("{e}, drawing on the CPU"))
+            .ok();
+
+        let mut state = AppState {
+            windows: Vec::new(),
+            event_queue: Some(eq),
+            qh: qh.clone(),
+            globals,
+            id: builder.id,
+            egl,
+            #[cfg(feature = "hot-reload")]
+            shader_watcher: FileWatcher::new(shaders::shader_dir())
+                .expect("failed to watch shader files"),
+            touch_pointer: None,
+            running: true,
+        };
+        for win_builder in win_builders {
+            state.open_window(win_builder, &qh);
+        }
+        state
+    }
+
+    /// Creates a window, it's drawn once the compositor configured it.
+    fn open_window(&mut self, win_builder: WindowBuilder, qh: &QueueHandle<AppState>) {
         let title = win_builder.title;
-        let event_handler = win_builder.event_handler;
         let widget_builder = win_builder.child;
-        let widget = match &widget_builder {
-            Some(builder) => Some(builder.build()),
-            None => None,
-        };
+        let widget = widget_builder.as_ref().map(|builder| builder.build());
 
-        let wl_surface = globals.compositor().create_surface(&qh, AppData);
-        let xdg_surface = globals.xdg().get_xdg_surface(&wl_surface, &qh, AppData);
-        let xdg_toplevel = xdg_surface.get_toplevel(&qh, AppData);
-        xdg_toplevel.set_app_id(id.clone());
+        let wl_surface = self.globals.compositor().create_surface(qh, AppData);
+        let xdg_surface = self.globals.xdg().get_xdg_surface(&wl_surface, qh, AppData);
+        let xdg_toplevel = xdg_surface.get_toplevel(qh, AppData);
+        xdg_toplevel.set_app_id(self.id.clone());
         xdg_toplevel.set_title(title.clone());
+        let (fractional_scale, viewport) = match self.globals.fractional_scale() {
+            Some((manager, viewporter)) => (
+                Some(manager.get_fractional_scale(&wl_surface, qh, AppData)),
+                Some(viewporter.get_viewport(&wl_surface, qh, AppData)),
+            ),
+            None => (None, None),
+        };
+        // asks for the first configure
+        wl_surface.commit();
 
         let constr = Constraints {
             width: 600,
@@ -99,50 +270,64 @@ impl AppState {
             ..Default::default()
         };
 
-        let display =
-            EglDisplay::get(globals.connection().display().id().as_ptr() as NativeDisplayType)
-                .expect("failed to get EGL display");
-        let (major, minor) = display.init().unwrap();
-        println!("EGL version: {major}.{minor}");
+        let surface = match self.egl.as_ref() {
+            Some(egl) => {
+                // windows opened later share what was set up at startup
+                if win_builder.samples > egl.samples {
+                    eprintln!(
+                        "window {title:?} asks for {} samples, but the config was chosen for {}",
+                        win_builder.samples, egl.samples
+                    );
+                }
+                if win_builder.debug && !egl.debug {
+                    eprintln!(
+                        "window {title:?} asks for debug output, but the context was created \
+                         without the debug bit"
+                    );
+                }
+                let window = WlEglSurface::new(wl_surface.id(), constr.width, constr.height)
+                    .expect("failed to get EGL window");
 
-        let config = display
-            .get_conf_first(Some(
-                &EglConfAttrs::new()
-                    .red_size(8)
-                    .blue_size(8)
-                    .green_size(8)
-                    .alpha_size(8)
-                    .stencil_size(8)
-                    .surface_type(EglSurfaceType::new().window()),
-            ))
-            .expect("failed to get EGL config");
-
-        let window = WlEglSurface::new(wl_surface.id(), constr.width, constr.height)
-            .expect("failed to get EGL window");
-
-        let surface = display
-            .create_window_surface(&config, window.ptr() as NativeWindowType)
-            .expect("failed to create EGL window surface");
+                let surface = egl
+                    .display
+                    .create_window_surface(&egl.config, window.ptr() as NativeWindowType)
+                    .expect("failed to create EGL window surface");
 
-        let context = display
-            .create_context(
-                &config,
-                None,
-                Some(&EglCtxAttrs::new().major_version(3).minor_version(2)),
-            )
-            .expect("failed to get EGL context");
-        context
-            .make_current(&surface, &surface)
-            .expect("failed to make EGL context current");
+                egl.context
+                    .make_current(&surface, &surface)
+                    .expect("failed to make EGL context current");
+                surface
+                    .set_swap_interval(win_builder.vsync as i32)
+                    .expect("failed to set EGL swap interval");
+                if win_builder.debug && !debug::is_enabled() {
+                    let res = match win_builder.debug_logger {
+                        Some(logger) => debug::enable(move |msg: &GlDebugMessage| logger.log(msg)),
+                        None => debug::enable(StderrLogger),
+                    };
+                    res.expect("failed to enable GL debug output");
+                }
+                if win_builder.profiler && !profiler::is_enabled() {
+                    profiler::enable().expect("failed to enable GL profiler");
+                }
+                WindowSurface::Egl { window, surface }
+            }
+            // the debug output and the profiler need GLES
+            None => WindowSurface::Shm {
+                shm: self.globals.shm().clone(),
+                qh: qh.clone(),
+                canvas: None,
+                size: (0, 0),
+                buffer: None,
+            },
+        };
 
         let window_state = WindowState {
             wl_surface,
             xdg_surface,
             xdg_toplevel,
-            display,
-            window,
             surface,
-            context,
+            fractional_scale,
+            viewport,
             title,
         };
 
@@ -151,55 +336,379 @@ impl AppState {
         };
         let pend_conf = cur_conf.clone();
 
-        let window = WaylandWindow {
+        self.windows.push(WaylandWindow {
             cur_conf,
             pend_conf,
-            event_handler,
+            event_handler: win_builder.event_handler,
             state: Mutex::new(window_state),
             widget_builder,
             widget,
-            frame_requested: true,
+            damage: Default::default(),
+            frame_requested: false,
             close_requested: false,
+            open_requested: Vec::new(),
+            clipboard_requests: Vec::new(),
+            clipboard_mime_types: self
+                .globals
+                .clipboard()
+                .mime_types(Selection::Clipboard)
+                .to_vec(),
+            primary_mime_types: self
+                .globals
+                .clipboard()
+                .mime_types(Selection::Primary)
+                .to_vec(),
+            outputs: Vec::new(),
+            buffer_scale: None,
+            fractional_scale: None,
+            scale: 1.0,
+        });
+    }
+
+    /// Redraws the windows whose scale changed.
+    fn update_scales(&mut self) {
+        let outputs = self.globals.outputs();
+        for window in self.windows.iter_mut() {
+            window.update_scale(|output| outputs.scale(output));
+        }
+    }
+
+    /// Window whose state matches `pred`.
+    fn find_window(&mut self, pred: impl Fn(&WindowState) -> bool) -> Option<&mut WaylandWindow> {
+        self.windows
+            .iter_mut()
+            .find(|window| pred(&window.state.lock().unwrap()))
+    }
+
+    fn surface_window(&mut self, surface: &WlSurface) -> Option<&mut WaylandWindow> {
+        self.find_window(|state| state.wl_surface == *surface)
+    }
+
+    fn pointer_window(&mut self) -> Option<&mut WaylandWindow> {
+        let focus = self.globals.seat().pointer().as_ref()?.focus().clone()?;
+        self.surface_window(&focus)
+    }
+
+    /// Sends `event` to the window under the pointer, then shows the cursor
+    /// its widgets set.
+    fn pointer_event(&mut self, event: GlobalEvent) {
+        cursor::take();
+        let Some(window) = self.pointer_window() else {
+            return;
         };
+        window.handle_event(&event);
+        // cursor images are integer scaled down to fractional scales
+        let scale = window.scale().ceil() as i32;
 
-        AppState {
-            event_queue: Some(eq),
-            globals,
-            _id: id,
-            window,
-            running: true,
+        let icon = cursor::take().unwrap_or_default();
+        if let (Some(pointer), cursors) = self.globals.pointer_cursors() {
+            pointer.set_cursor(icon, scale, cursors, &self.qh, AppData);
+        }
+    }
+
+    /// Sends a drag entering or moving over `surface` to its window, then
+    /// accepts it as the widget under the drag asked.
+    fn drag_event(&mut self, surface: &WlSurface, event: GlobalEvent) {
+        drag::take();
+        let Some(window) = self.surface_window(surface) else {
+            return;
+        };
+        window.handle_event(&event);
+
+        let accepted = drag::take();
+        self.globals.clipboard().accept_drag(accepted);
+    }
+
+    /// Sends a touch to the window of `surface`, returning whether a widget
+    /// handled it.
+    fn touch_event(&mut self, surface: &WlSurface, e: TouchEvent, id: i32, x: f64, y: f64) -> bool {
+        let event = GlobalEvent::Touch { e, id, x, y };
+        self.surface_window(surface)
+            .is_some_and(|window| window.handle_event(&event))
+    }
+
+    /// Sends `events` emulating the pointer with a touch point to the window
+    /// of `surface`.
+    fn emulate_pointer(&mut self, surface: &WlSurface, events: Vec<PointerEvent>, x: f64, y: f64) {
+        let Some(window) = self.surface_window(surface) else {
+            return;
+        };
+        for e in events {
+            window.handle_event(&GlobalEvent::Pointer { e, x, y });
         }
+        // touches show no cursor
+        cursor::take();
+    }
+
+    fn keyboard_window(&mut self) -> Option<&mut WaylandWindow> {
+        let focus = self.globals.seat().keyboard().as_ref()?.focus().clone()?;
+        self.surface_window(&focus)
     }
 
     fn exec(&mut self) {
         while self.running {
             let mut eq = self.event_queue.take().expect("couldn't get event queue");
 
-            eq.blocking_dispatch(self)
-                .expect("failed to dispatch event queue");
+            let res = if cfg!(feature = "hot-reload")
+                || self.next_timer().is_some()
+                || self.globals.clipboard().is_transferring()
+            {
+                self.dispatch_polling(&mut eq)
+            } else {
+                eq.blocking_dispatch(self).map(|_| ())
+            };
+            res.expect("failed to dispatch event queue");
+            self.kinetic_scroll();
+            self.repeat_key();
 
+            let qh = eq.handle();
             self.event_queue = Some(eq);
 
-            if self.window.frame_requested {
-                self.window.frame_requested = false;
-                self.window.draw(self.window.cur_conf.constraints.clone());
+            let opened = self
+                .windows
+                .iter_mut()
+                .flat_map(|window| window.open_requested.drain(..))
+                .collect::<Vec<_>>();
+            for win_builder in opened {
+                self.open_window(win_builder, &qh);
             }
-            if self.window.close_requested {
+            self.handle_clipboard_requests(&qh);
+
+            for window in self.windows.iter_mut() {
+                if window.frame_requested {
+                    window.frame_requested = false;
+                    if let Some(egl) = self.egl.as_ref() {
+                        window.make_current(&egl.context);
+                    }
+                    window.draw(window.cur_conf.constraints.clone());
+                }
+            }
+
+            self.windows.retain(|window| !window.close_requested);
+            if self.windows.is_empty() {
                 self.running = false;
             }
         }
     }
 }
 
+impl AppState {
+    /// Dispatches Wayland events like `blocking_dispatch`, also waking up to
+    /// rebuild the shader programs whose files changed, for kinetic scrolls
+    /// and key repeats, and to move the clipboard contents through their
+    /// pipes.
+    fn dispatch_polling(&mut self, eq: &mut EventQueue<AppState>) -> Result<(), DispatchError> {
+        if eq.dispatch_pending(self)? > 0 {
+            return Ok(());
+        }
+        eq.flush()?;
+
+        if let Some(guard) = eq.prepare_read() {
+            let mut fds = vec![PollFd::readable(guard.connection_fd().as_raw_fd())];
+            #[cfg(feature = "hot-reload")]
+            fds.push(self.shader_watcher.poll_fd());
+            fds.extend(self.globals.clipboard().poll_fds());
+            let timeout = self
+                .next_timer()
+                .map(|next| next.saturating_duration_since(Instant::now()));
+            poll::poll_fds(&mut fds, timeout).map_err(WaylandError::Io)?;
+
+            if fds[0].is_ready() {
+                match guard.read() {
+                    Err(WaylandError::Io(e)) if e.kind() == io::ErrorKind::WouldBlock => {}
+                    res => {
+                        res?;
+                    }
+                }
+            }
+        }
+        eq.dispatch_pending(self)?;
+
+        #[cfg(feature = "hot-reload")]
+        self.reload_shaders();
+        self.read_clipboard();
+        self.globals.clipboard().write_ready();
+        Ok(())
+    }
+
+    /// When the event loop has to wake up next without any event, right away
+    /// when a window asked for a frame while drawing.
+    fn next_timer(&mut self) -> Option<Instant> {
+        if self.windows.iter().any(|window| window.frame_requested) {
+            return Some(Instant::now());
+        }
+        let seat = self.globals.seat();
+        let kinetic = seat
+            .pointer()
+            .as_ref()
+            .filter(|pointer| pointer.is_kinetic())
+            .map(|_| Instant::now() + KINETIC_INTERVAL);
+        let repeat = seat.keyboard().as_ref().and_then(|k| k.next_repeat());
+
+        match (kinetic, repeat) {
+            (Some(a), Some(b)) => Some(a.min(b)),
+            (a, b) => a.or(b),
+        }
+    }
+
+    /// Sends the window under the pointer the scroll made by the running
+    /// kinetic scroll since the last call.
+    fn kinetic_scroll(&mut self) {
+        let Some(pointer) = self.globals.seat().pointer_mut().as_mut() else {
+            return;
+        };
+        let Some((dx, dy, inverted)) = pointer.kinetic_step() else {
+            return;
+        };
+        let (x, y) = pointer.coords();
+        let event = GlobalEvent::Pointer {
+            e: PointerEvent::Scroll {
+                dx,
+                dy,
+                source: ScrollSource::Kinetic,
+                discrete: None,
+                inverted,
+            },
+            x,
+            y,
+        };
+
+        self.pointer_event(event);
+    }
+
+    /// Sends the focused window the held key once it's due to repeat.
+    fn repeat_key(&mut self) {
+        let Some(keyboard) = self.globals.seat().keyboard_mut().as_mut() else {
+            return;
+        };
+        let Some(event) = keyboard.repeat_key(Instant::now()) else {
+            return;
+        };
+        let event = GlobalEvent::Keyboard(event);
+
+        if let Some(window) = self.keyboard_window() {
+            window.handle_event(&event);
+        }
+    }
+
+    /// Sets and reads the selections as the windows requested.
+    fn handle_clipboard_requests(&mut self, qh: &QueueHandle<AppState>) {
+        let compositor = self.globals.compositor().clone();
+        let shm = self.globals.shm().clone();
+        for window in self.windows.iter_mut() {
+            let surface = window.state.lock().unwrap().wl_surface.clone();
+            // drag icons are integer scaled down like cursors
+            let scale = window.scale().ceil() as i32;
+            let clipboard = self.globals.clipboard();
+            for request in window.clipboard_requests.drain(..) {
+                match request {
+                    ClipboardRequest::Set {
+                        selection,
+                        mime_types,
+                        data,
+                    } => clipboard.set(selection, mime_types, data, qh, AppData),
+                    ClipboardRequest::Read {
+                        selection,
+                        mime_type,
+                    } => clipboard.read(selection, mime_type, surface.clone()),
+                    ClipboardRequest::Drag {
+                        mime_types,
+                        actions,
+                        icon,
+                        data,
+                    } => clipboard.start_drag(
+                        &surface,
+                        mime_types,
+                        &actions,
+                        icon.as_ref(),
+                        scale,
+                        data,
+                        &compositor,
+                        &shm,
+                        qh,
+                        AppData,
+                    ),
+                }
+            }
+        }
+    }
+
+    /// Sends the windows the selection and drop contents read completely.
+    fn read_clipboard(&mut self) {
+        for read in self.globals.clipboard().read_ready() {
+            let event = match read.transfer {
+                Transfer::Selection(selection) => GlobalEvent::ClipboardData {
+                    selection,
+                    mime_type: read.mime_type,
+                    data: read.data,
+                },
+                Transfer::Drop { x, y } => GlobalEvent::Drag {
+                    e: DragEvent::Data {
+                        mime_type: read.mime_type,
+                        data: read.data,
+                    },
+                    x,
+                    y,
+                },
+            };
+
+            if let Some(window) = self.surface_window(&read.surface) {
+                window.handle_event(&event);
+            }
+        }
+    }
+
+    #[cfg(feature = "hot-reload")]
+    fn reload_shaders(&mut self) {
+        for file in self.shader_watcher.changed() {
+            match shaders::reload(&file) {
+                Ok(0) => {}
+                Ok(_) => self.windows.iter_mut().for_each(|window| window.frame()),
+                Err(errors) => {
+                    for e in errors {
+                        eprintln!("failed to reload `{file}`: {e}");
+                    }
+                    self.windows.iter_mut().for_each(|window| window.frame());
+                }
+            }
+        }
+    }
+}
+
 impl Dispatch<WlRegistry, GlobalListContents> for AppState {
     fn event(
-        _state: &mut Self,
-        _proxy: &WlRegistry,
-        _event: <WlRegistry as wayland_client::Proxy>::Event,
+        state: &mut Self,
+        proxy: &WlRegistry,
+        event: <WlRegistry as wayland_client::Proxy>::Event,
         _data: &GlobalListContents,
         _conn: &wayland_client::Connection,
-        _qhandle: &wayland_client::QueueHandle<Self>,
+        qhandle: &wayland_client::QueueHandle<Self>,
     ) {
+        use wl_registry::Event::*;
+        match event {
+            Global {
+                name,
+                interface,
+                version,
+            } => {
+                if interface == "wl_output" {
+                    state
+                        .globals
+                        .outputs()
+                        .add(proxy, name, version, qhandle, AppData);
+                }
+            }
+            GlobalRemove { name } => {
+                let Some(output) = state.globals.outputs().remove(name) else {
+                    return;
+                };
+                for window in state.windows.iter_mut() {
+                    window.outputs.retain(|o| *o != output);
+                }
+                state.update_scales();
+            }
+            _ => unreachable!(),
+        }
     }
 }
 
@@ -236,21 +745,86 @@ impl Dispatch<XdgWmBase, AppData> for AppState {
 
 impl Dispatch<WlSurface, AppData> for AppState {
     fn event(
-        _state: &mut AppState,
-        _proxy: &WlSurface,
+        state: &mut AppState,
+        proxy: &WlSurface,
         event: <WlSurface as wayland_client::Proxy>::Event,
         _data: &AppData,
         _conn: &wayland_client::Connection,
         _qhandle: &wayland_client::QueueHandle<AppState>,
     ) {
+        // cursor and drag icon surfaces have no window
+        let Some(window) = state.surface_window(proxy) else {
+            return;
+        };
+
         use wl_surface::Event::*;
         match event {
-            Enter { output: _ } => {}
-            Leave { output: _ } => {}
-            PreferredBufferScale { factor: _ } => {}
-            PreferredBufferTransform { transform: _ } => {}
+            Enter { output } => window.outputs.push(output),
+            Leave { output } => window.outputs.retain(|o| *o != output),
+            PreferredBufferScale { factor } => window.buffer_scale = Some(factor),
+            PreferredBufferTransform { transform: _ } => return,
             _ => unreachable!(),
         }
+        state.update_scales();
+    }
+}
+
+impl Dispatch<WpFractionalScaleManagerV1, AppData> for AppState {
+    fn event(
+        _state: &mut Self,
+        _proxy: &WpFractionalScaleManagerV1,
+        _event: <WpFractionalScaleManagerV1 as wayland_client::Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &wayland_client::QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<WpFractionalScaleV1, AppData> for AppState {
+    fn event(
+        state: &mut AppState,
+        proxy: &WpFractionalScaleV1,
+        event: <WpFractionalScaleV1 as wayland_client::Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &wayland_client::QueueHandle<AppState>,
+    ) {
+        let Some(window) = state.find_window(|w| w.fractional_scale.as_ref() == Some(proxy)) else {
+            return;
+        };
+
+        use wp_fractional_scale_v1::Event::*;
+        match event {
+            // in 120ths
+            PreferredScale { scale } => window.fractional_scale = Some(scale as f64 / 120.0),
+            _ => unreachable!(),
+        }
+        state.update_scales();
+    }
+}
+
+impl Dispatch<WpViewporter, AppData> for AppState {
+    fn event(
+        _state: &mut Self,
+        _proxy: &WpViewporter,
+        _event: <WpViewporter as wayland_client::Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &wayland_client::QueueHandle<Self>,
+    ) {
+    }
+}
+
+impl Dispatch<WpViewport, AppData> for AppState {
+    fn event(
+        _state: &mut Self,
+        _proxy: &WpViewport,
+        _event: <WpViewport as wayland_client::Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &wayland_client::QueueHandle<Self>,
+    ) {
     }
 }
 
@@ -266,7 +840,9 @@ impl Dispatch<XdgSurface, AppData> for AppState {
         use xdg_surface::Event::*;
         match event {
             Configure { serial } => {
-                state.window.handle_event(&GlobalEvent::Configure);
+                if let Some(window) = state.find_window(|w| w.xdg_surface == *proxy) {
+                    window.handle_event(&GlobalEvent::Configure);
+                }
                 proxy.ack_configure(serial);
             }
             _ => unreachable!(),
@@ -277,12 +853,16 @@ impl Dispatch<XdgSurface, AppData> for AppState {
 impl Dispatch<XdgToplevel, AppData> for AppState {
     fn event(
         state: &mut AppState,
-        _proxy: &XdgToplevel,
+        proxy: &XdgToplevel,
         event: <XdgToplevel as wayland_client::Proxy>::Event,
         _data: &AppData,
         _conn: &wayland_client::Connection,
         _qhandle: &wayland_client::QueueHandle<AppState>,
     ) {
+        let Some(window) = state.find_window(|w| w.xdg_toplevel == *proxy) else {
+            return;
+        };
+
         use xdg_toplevel::Event::*;
         match event {
             Configure {
@@ -290,11 +870,11 @@ impl Dispatch<XdgToplevel, AppData> for AppState {
                 height,
                 states: _,
             } => {
-                state.window.pend_conf.constraints.resize(width, height);
+                window.pend_conf.constraints.resize(width, height);
             }
             Close => {
                 println!("Close event");
-                state.window.handle_event(&GlobalEvent::Close);
+                window.handle_event(&GlobalEvent::Close);
             }
             ConfigureBounds {
                 width: _,
@@ -306,6 +886,112 @@ impl Dispatch<XdgToplevel, AppData> for AppState {
     }
 }
 
+impl AsMut<Clipboard> for AppState {
+    fn as_mut(&mut self) -> &mut Clipboard {
+        self.globals.clipboard()
+    }
+}
+
+impl ClipboardHandler for AppState {
+    fn selection(&mut self, selection: Selection, mime_types: Vec<String>) {
+        for window in self.windows.iter_mut() {
+            match selection {
+                Selection::Clipboard => window.clipboard_mime_types = mime_types.clone(),
+                Selection::Primary => window.primary_mime_types = mime_types.clone(),
+            }
+        }
+    }
+
+    fn drag_enter(
+        &mut self,
+        surface: WlSurface,
+        (x, y): (f64, f64),
+        mime_types: Vec<String>,
+        actions: Vec<DndAction>,
+    ) {
+        let event = GlobalEvent::Drag {
+            e: DragEvent::Enter {
+                mime_types,
+                actions,
+            },
+            x,
+            y,
+        };
+
+        self.drag_event(&surface, event);
+    }
+
+    fn drag_motion(&mut self, surface: WlSurface, (x, y): (f64, f64)) {
+        let event = GlobalEvent::Drag {
+            e: DragEvent::Motion,
+            x,
+            y,
+        };
+
+        self.drag_event(&surface, event);
+    }
+
+    fn drag_leave(&mut self, surface: WlSurface, (x, y): (f64, f64)) {
+        let event = GlobalEvent::Drag {
+            e: DragEvent::Leave,
+            x,
+            y,
+        };
+
+        if let Some(window) = self.surface_window(&surface) {
+            window.handle_event(&event);
+        }
+    }
+
+    fn drag_drop(&mut self, surface: WlSurface, (x, y): (f64, f64), action: Option<DndAction>) {
+        let event = GlobalEvent::Drag {
+            e: match action {
+                Some(action) => DragEvent::Drop { action },
+                None => DragEvent::Leave,
+            },
+            x,
+            y,
+        };
+
+        if let Some(window) = self.surface_window(&surface) {
+            window.handle_event(&event);
+        }
+    }
+
+    fn drag_source_end(&mut self, surface: WlSurface, action: Option<DndAction>) {
+        let (x, y) = self
+            .globals
+            .seat()
+            .pointer()
+            .as_ref()
+            .map_or((0.0, 0.0), |pointer| pointer.coords());
+        let event = GlobalEvent::Drag {
+            e: match action {
+                Some(action) => DragEvent::SourceFinished { action },
+                None => DragEvent::SourceCancelled,
+            },
+            x,
+            y,
+        };
+
+        if let Some(window) = self.surface_window(&surface) {
+            window.handle_event(&event);
+        }
+    }
+}
+
+impl AsMut<Outputs> for AppState {
+    fn as_mut(&mut self) -> &mut Outputs {
+        self.globals.outputs()
+    }
+}
+
+impl OutputHandler for AppState {
+    fn scale_changed(&mut self, _output: WlOutput) {
+        self.update_scales();
+    }
+}
+
 impl AsMut<Seat> for AppState {
     fn as_mut(&mut self) -> &mut Seat {
         self.globals.seat()
@@ -313,16 +999,14 @@ impl AsMut<Seat> for AppState {
 }
 
 impl PointerHandler for AppState {
-    fn enter(&mut self, _serial: u32, surface: WlSurface, x: f64, y: f64) {
+    fn enter(&mut self, _serial: u32, _surface: WlSurface, x: f64, y: f64) {
         let event = GlobalEvent::Pointer {
             e: PointerEvent::Enter,
             x,
             y,
         };
 
-        if self.window.state.lock().unwrap().wl_surface == surface {
-            self.window.handle_event(&event);
-        }
+        self.pointer_event(event);
     }
 
     fn leave(&mut self, _serial: u32, surface: WlSurface) {
@@ -333,8 +1017,8 @@ impl PointerHandler for AppState {
             y,
         };
 
-        if self.window.state.lock().unwrap().wl_surface == surface {
-            self.window.handle_event(&event);
+        if let Some(window) = self.surface_window(&surface) {
+            window.handle_event(&event);
         }
     }
 
@@ -345,78 +1029,169 @@ impl PointerHandler for AppState {
             y,
         };
 
-        self.window.handle_event(&event);
+        self.pointer_event(event);
     }
 
-    fn button(&mut self, _serial: u32, _time: u32, state: ButtonState, button: Button) {
+    fn button(&mut self, serial: u32, _time: u32, state: ButtonState, button: Button) {
+        self.globals.clipboard().set_serial(serial);
         let (x, y) = self.globals.seat().pointer().as_ref().unwrap().coords();
         let event = GlobalEvent::Pointer {
             e: match state {
                 ButtonState::Pressed => PointerEvent::ButtonPressed(button),
                 ButtonState::Released => PointerEvent::ButtonReleased(button),
-                ButtonState::Clicked => PointerEvent::ButtonClicked(button),
+                ButtonState::Clicked(count) => PointerEvent::ButtonClicked { button, count },
+            },
+            x,
+            y,
+        };
+
+        self.pointer_event(event);
+    }
+
+    fn scroll(
+        &mut self,
+        _time: u32,
+        dx: f64,
+        dy: f64,
+        source: ScrollSource,
+        discrete: Option<(f64, f64)>,
+        inverted: bool,
+    ) {
+        let (x, y) = self.globals.seat().pointer().as_ref().unwrap().coords();
+        let event = GlobalEvent::Pointer {
+            e: PointerEvent::Scroll {
+                dx,
+                dy,
+                source,
+                discrete,
+                inverted,
             },
             x,
             y,
         };
 
-        self.window.handle_event(&event);
+        self.pointer_event(event);
+    }
+}
+
+impl TouchHandler for AppState {
+    fn down(&mut self, serial: u32, _time: u32, surface: WlSurface, id: i32, x: f64, y: f64) {
+        self.globals.clipboard().set_serial(serial);
+        if self.touch_event(&surface, TouchEvent::Down, id, x, y) || self.touch_pointer.is_some() {
+            return;
+        }
+
+        self.touch_pointer = Some(TouchPointer { id, start: (x, y) });
+        let events = vec![
+            PointerEvent::Enter,
+            PointerEvent::ButtonPressed(Button::Left),
+        ];
+        self.emulate_pointer(&surface, events, x, y);
+    }
+
+    fn up(&mut self, _serial: u32, _time: u32, surface: WlSurface, id: i32, x: f64, y: f64) {
+        self.touch_event(&surface, TouchEvent::Up, id, x, y);
+        let Some(touch_pointer) = self.touch_pointer.take_if(|p| p.id == id) else {
+            return;
+        };
+
+        let (sx, sy) = touch_pointer.start;
+        let mut events = vec![PointerEvent::ButtonReleased(Button::Left)];
+        if (x - sx).hypot(y - sy) <= CLICK_DISTANCE {
+            events.push(PointerEvent::ButtonClicked {
+                button: Button::Left,
+                count: 1,
+            });
+        }
+        events.push(PointerEvent::Leave);
+        self.emulate_pointer(&surface, events, x, y);
+    }
+
+    fn motion(&mut self, _time: u32, surface: WlSurface, id: i32, x: f64, y: f64) {
+        self.touch_event(&surface, TouchEvent::Motion, id, x, y);
+        if self.touch_pointer.as_ref().is_some_and(|p| p.id == id) {
+            self.emulate_pointer(&surface, vec![PointerEvent::Motion], x, y);
+        }
+    }
+
+    fn cancel(&mut self, surface: WlSurface, id: i32, x: f64, y: f64) {
+        self.touch_event(&surface, TouchEvent::Cancel, id, x, y);
+        if self.touch_pointer.take_if(|p| p.id == id).is_some() {
+            // the button is let go without clicking
+            let events = vec![
+                PointerEvent::ButtonReleased(Button::Left),
+                PointerEvent::Leave,
+            ];
+            self.emulate_pointer(&surface, events, x, y);
+        }
+    }
+
+    fn shape(&mut self, surface: WlSurface, id: i32, x: f64, y: f64, major: f64, minor: f64) {
+        self.touch_event(&surface, TouchEvent::Shape { major, minor }, id, x, y);
+    }
+
+    fn orientation(&mut self, surface: WlSurface, id: i32, x: f64, y: f64, orientation: f64) {
+        self.touch_event(&surface, TouchEvent::Orientation(orientation), id, x, y);
     }
 }
 
 impl KeyboardHandler for AppState {
     fn enter(
         &mut self,
-        _serial: u32,
+        serial: u32,
         surface: WlSurface,
         _keys: Vec<u32>,
         _keysyms: Vec<xkeysym::Keysym>,
     ) {
+        self.globals.clipboard().set_serial(serial);
         let event = GlobalEvent::Keyboard(KeyboardEvent::Enter);
 
-        if self.window.state.lock().unwrap().wl_surface == surface {
-            self.window.handle_event(&event);
+        if let Some(window) = self.surface_window(&surface) {
+            window.handle_event(&event);
         }
     }
 
     fn leave(&mut self, _serial: u32, surface: WlSurface) {
         let event = GlobalEvent::Keyboard(KeyboardEvent::Leave);
 
-        if self.window.state.lock().unwrap().wl_surface == surface {
-            self.window.handle_event(&event);
+        if let Some(window) = self.surface_window(&surface) {
+            window.handle_event(&event);
         }
     }
 
-    fn key(
-        &mut self,
-        _serial: u32,
-        _time: u32,
-        state: KeyState,
-        key: u32,
-        keysym: xkeysym::Keysym,
-        utf8: String,
-    ) {
-        let event = GlobalEvent::Keyboard(match state {
-            KeyState::Pressed => KeyboardEvent::KeyPressed { key, keysym, utf8 },
-            KeyState::Released => KeyboardEvent::KeyReleased { key, keysym, utf8 },
-            _ => return,
-        });
+    fn key(&mut self, serial: u32, _time: u32, event: KeyboardEvent) {
+        self.globals.clipboard().set_serial(serial);
+        let event = GlobalEvent::Keyboard(event);
 
-        self.window.handle_event(&event);
+        if let Some(window) = self.keyboard_window() {
+            window.handle_event(&event);
+        }
     }
 
-    fn modifiers(
-        &mut self,
-        _serial: u32,
-        modifiers: globals::seat::keyboard::Modifiers,
-        _group: u32,
-    ) {
+    fn modifiers(&mut self, _serial: u32, modifiers: Modifiers, _group: u32) {
         let event = GlobalEvent::Keyboard(KeyboardEvent::Modifiers(modifiers));
 
-        self.window.handle_event(&event);
+        if let Some(window) = self.keyboard_window() {
+            window.handle_event(&event);
+        }
     }
 }
 
 delegate_dispatch!(AppState: [WlSeat: AppData] => Seat);
 delegate_dispatch!(AppState: [WlKeyboard: AppData] => Keyboard);
 delegate_dispatch!(AppState: [WlPointer: AppData] => Pointer);
+delegate_dispatch!(AppState: [WlTouch: AppData] => Touch);
+delegate_dispatch!(AppState: [WlOutput: AppData] => Outputs);
+delegate_dispatch!(AppState: [WlShm: AppData] => Shm);
+delegate_dispatch!(AppState: [WlShmPool: AppData] => Shm);
+delegate_dispatch!(AppState: [WlBuffer: AppData] => Shm);
+delegate_dispatch!(AppState: [WpCursorShapeManagerV1: AppData] => Cursors);
+delegate_dispatch!(AppState: [WpCursorShapeDeviceV1: AppData] => Cursors);
+delegate_dispatch!(AppState: [WlDataDeviceManager: AppData] => Clipboard);
+delegate_dispatch!(AppState: [WlDataDevice: AppData] => Clipboard);
+delegate_dispatch!(AppState: [WlDataOffer: AppData] => Clipboard);
+delegate_dispatch!(AppState: [WlDataSource: AppData] => Clipboard);
+delegate_dispatch!(AppState: [ZwpPrimarySelectionDeviceManagerV1: AppData] => Clipboard);
+delegate_dispatch!(AppState: [ZwpPrimarySelectionDeviceV1: AppData] => Clipboard);
+delegate_dispatch!(AppState: [ZwpPrimarySelectionOfferV1: AppData] => Clipboard);
+delegate_dispatch!(AppState: [ZwpPrimarySelectionSourceV1: AppData] => Clipboard);
@@ -1,19 +1,48 @@
 use wayland_client::{
     globals::{registry_queue_init, GlobalListContents},
-    protocol::{wl_compositor::WlCompositor, wl_registry::WlRegistry, wl_seat::WlSeat},
+    protocol::{
+        wl_compositor::WlCompositor, wl_data_device::WlDataDevice,
+        wl_data_device_manager::WlDataDeviceManager, wl_output::WlOutput, wl_registry::WlRegistry,
+        wl_seat::WlSeat, wl_shm::WlShm,
+    },
     Connection, Dispatch, EventQueue,
 };
-use wayland_protocols::xdg::shell::client::xdg_wm_base::XdgWmBase;
+use wayland_protocols::{
+    wp::{
+        cursor_shape::v1::client::wp_cursor_shape_manager_v1::WpCursorShapeManagerV1,
+        fractional_scale::v1::client::wp_fractional_scale_manager_v1::WpFractionalScaleManagerV1,
+        primary_selection::zv1::client::{
+            zwp_primary_selection_device_manager_v1::ZwpPrimarySelectionDeviceManagerV1,
+            zwp_primary_selection_device_v1::ZwpPrimarySelectionDeviceV1,
+        },
+        viewporter::client::wp_viewporter::WpViewporter,
+    },
+    xdg::shell::client::xdg_wm_base::XdgWmBase,
+};
 
-use self::seat::Seat;
+use self::{
+    clipboard::Clipboard,
+    cursor::Cursors,
+    output::Outputs,
+    seat::{pointer::Pointer, Seat},
+};
 
+pub mod clipboard;
+pub mod cursor;
+pub mod output;
 pub mod seat;
+pub mod shm;
 
 pub struct Globals {
     connection: Connection,
     compositor: WlCompositor,
     seat: Seat,
     xdg: XdgWmBase,
+    shm: WlShm,
+    cursors: Cursors,
+    clipboard: Clipboard,
+    outputs: Outputs,
+    fractional_scale: Option<(WpFractionalScaleManagerV1, WpViewporter)>,
 }
 
 impl Globals {
@@ -23,6 +52,15 @@ impl Globals {
             + Dispatch<WlCompositor, U>
             + Dispatch<WlSeat, U>
             + Dispatch<XdgWmBase, U>
+            + Dispatch<WlShm, U>
+            + Dispatch<WpCursorShapeManagerV1, U>
+            + Dispatch<WlDataDeviceManager, U>
+            + Dispatch<WlDataDevice, U>
+            + Dispatch<ZwpPrimarySelectionDeviceManagerV1, U>
+            + Dispatch<ZwpPrimarySelectionDeviceV1, U>
+            + Dispatch<WlOutput, U>
+            + Dispatch<WpFractionalScaleManagerV1, U>
+            + Dispatch<WpViewporter, U>
             + 'static,
         U: Send + Sync + Copy + 'static,
     {
@@ -31,17 +69,33 @@ impl Globals {
         let (globals, eq) = registry_queue_init(&connection)
             .expect("failed to init an event queue and retrieve the initial list of globals");
         let qh = eq.handle();
-        let compositor = globals
+        let compositor: WlCompositor = globals
             .bind(&qh, 6..=6, data)
             .expect("failed to bind wl_compositor");
+        // relative scroll directions are sent from version 9
         let wl_seat = globals
-            .bind(&qh, 8..=8, data)
+            .bind(&qh, 8..=9, data)
             .expect("failed to bind wl_seat");
         let xdg = globals
             .bind(&qh, 6..=6, data)
             .expect("failed to bind xdg_wm_base");
+        let shm: WlShm = globals
+            .bind(&qh, 1..=1, data)
+            .expect("failed to bind wl_shm");
+        // optional, cursors are loaded from the XCursor theme without it
+        let shape_manager = globals.bind(&qh, 1..=1, data).ok();
+        // optional too, copying, pasting and dragging do nothing without them
+        let data_manager = globals.bind(&qh, 3..=3, data).ok();
+        let primary_manager = globals.bind(&qh, 1..=1, data).ok();
+        // optional, windows are drawn at integer scales without them
+        let fractional_scale = globals.bind(&qh, 1..=1, data).ok();
+        let viewporter = globals.bind(&qh, 1..=1, data).ok();
+        let fractional_scale = fractional_scale.zip(viewporter);
+        let outputs = Outputs::new(&globals, &qh, data);
 
+        let clipboard = Clipboard::new(data_manager, primary_manager, &wl_seat, &qh, data);
         let seat = Seat::new(wl_seat);
+        let cursors = Cursors::new(compositor.clone(), shm.clone(), shape_manager);
 
         (
             Globals {
@@ -49,6 +103,11 @@ impl Globals {
                 compositor,
                 seat,
                 xdg,
+                shm,
+                cursors,
+                clipboard,
+                outputs,
+                fractional_scale,
             },
             eq,
         )
@@ -69,4 +128,27 @@ impl Globals {
     pub fn xdg(&self) -> &XdgWmBase {
         &self.xdg
     }
+
+    pub fn shm(&self) -> &WlShm {
+        &self.shm
+    }
+
+    pub fn clipboard(&mut self) -> &mut Clipboard {
+        &mut self.clipboard
+    }
+
+    pub fn outputs(&mut self) -> &mut Outputs {
+        &mut self.outputs
+    }
+
+    /// Managers of fractional scales and of the viewports scaling the
+    /// buffers drawn at them, when the compositor has both.
+    pub fn fractional_scale(&self) -> Option<&(WpFractionalScaleManagerV1, WpViewporter)> {
+        self.fractional_scale.as_ref()
+    }
+
+    /// The pointer and the cursors it can show.
+    pub fn pointer_cursors(&mut self) -> (Option<&mut Pointer>, &mut Cursors) {
+        (self.seat.pointer_mut().as_mut(), &mut self.cursors)
+    }
 }
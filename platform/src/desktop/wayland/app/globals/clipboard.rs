@@ -0,0 +1,1056 @@
+use super::shm;
+use crate::desktop::{
+    clipboard::{Selection, SelectionData},
+    drag::{DndAction, DragIcon},
+    poll::PollFd,
+    wayland::app::AppData,
+};
+use std::{
+    ffi::c_int,
+    fs::File,
+    io::{self, Read, Write},
+    os::fd::{AsFd, AsRawFd, OwnedFd},
+};
+use wayland_client::{
+    event_created_child,
+    protocol::{
+        wl_buffer::WlBuffer,
+        wl_compositor::WlCompositor,
+        wl_data_device::{self, WlDataDevice},
+        wl_data_device_manager::{DndAction as WlDndAction, WlDataDeviceManager},
+        wl_data_offer::{self, WlDataOffer},
+        wl_data_source::{self, WlDataSource},
+        wl_seat::WlSeat,
+        wl_shm::WlShm,
+        wl_shm_pool::WlShmPool,
+        wl_surface::WlSurface,
+    },
+    Dispatch, Proxy, QueueHandle, WEnum,
+};
+use wayland_protocols::wp::primary_selection::zv1::client::{
+    zwp_primary_selection_device_manager_v1::ZwpPrimarySelectionDeviceManagerV1,
+    zwp_primary_selection_device_v1::{self, ZwpPrimarySelectionDeviceV1},
+    zwp_primary_selection_offer_v1::{self, ZwpPrimarySelectionOfferV1},
+    zwp_primary_selection_source_v1::{self, ZwpPrimarySelectionSourceV1},
+};
+
+const F_GETFL: c_int = 3;
+const F_SETFL: c_int = 4;
+const O_NONBLOCK: c_int = 0o4000;
+
+extern "C" {
+    fn fcntl(fd: c_int, cmd: c_int, ...) -> c_int;
+}
+
+/// Clipboard, primary selection and drag and drop of the seat.
+pub struct Clipboard {
+    data_manager: Option<WlDataDeviceManager>,
+    data_device: Option<WlDataDevice>,
+    primary_manager: Option<ZwpPrimarySelectionDeviceManagerV1>,
+    primary_device: Option<ZwpPrimarySelectionDeviceV1>,
+    /// Serial of the last input event, selections are set and drags started
+    /// in response to input.
+    serial: u32,
+    /// Offers announced but not selected or dragged in yet.
+    offers: Vec<PendingOffer>,
+    clipboard: SelectionState,
+    primary: SelectionState,
+    drag: Option<DragOffer>,
+    drag_source: Option<DragSource>,
+    reads: Vec<PendingRead>,
+    writes: Vec<PendingWrite>,
+}
+
+struct PendingOffer {
+    offer: Offer,
+    mime_types: Vec<String>,
+    /// Actions the source of a drag allows.
+    source_actions: WlDndAction,
+}
+
+#[derive(Default)]
+struct SelectionState {
+    offer: Option<(Offer, Vec<String>)>,
+    source: Option<(Source, SelectionData)>,
+}
+
+#[derive(Clone, PartialEq)]
+enum Offer {
+    Data(WlDataOffer),
+    Primary(ZwpPrimarySelectionOfferV1),
+}
+
+impl Offer {
+    fn receive(&self, mime_type: String, fd: &OwnedFd) {
+        match self {
+            Offer::Data(offer) => offer.receive(mime_type, fd.as_fd()),
+            Offer::Primary(offer) => offer.receive(mime_type, fd.as_fd()),
+        }
+    }
+
+    fn destroy(&self) {
+        match self {
+            Offer::Data(offer) => offer.destroy(),
+            Offer::Primary(offer) => offer.destroy(),
+        }
+    }
+}
+
+#[derive(Clone, PartialEq)]
+enum Source {
+    Data(WlDataSource),
+    Primary(ZwpPrimarySelectionSourceV1),
+}
+
+impl Source {
+    fn destroy(&self) {
+        match self {
+            Source::Data(source) => source.destroy(),
+            Source::Primary(source) => source.destroy(),
+        }
+    }
+}
+
+/// Drag of another client, or one of ours, over one of our surfaces.
+struct DragOffer {
+    offer: WlDataOffer,
+    serial: u32,
+    surface: WlSurface,
+    position: (f64, f64),
+    source_actions: WlDndAction,
+    /// MIME type and action the window accepted.
+    accepted: Option<(String, DndAction)>,
+    /// Action the compositor picked from the ones of both sides.
+    action: Option<DndAction>,
+}
+
+/// Drag started from the window of `surface`.
+struct DragSource {
+    source: WlDataSource,
+    data: SelectionData,
+    surface: WlSurface,
+    icon: Option<(WlSurface, WlBuffer)>,
+    action: Option<DndAction>,
+}
+
+impl DragSource {
+    fn destroy(&self) {
+        self.source.destroy();
+        if let Some((surface, buffer)) = self.icon.as_ref() {
+            surface.destroy();
+            buffer.destroy();
+        }
+    }
+}
+
+/// What a read transfers.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Transfer {
+    Selection(Selection),
+    /// Contents of a drag dropped at a position of the surface.
+    Drop {
+        x: f64,
+        y: f64,
+    },
+}
+
+/// Contents being read from a pipe, for the window of `surface`.
+struct PendingRead {
+    surface: WlSurface,
+    transfer: Transfer,
+    mime_type: String,
+    pipe: File,
+    data: Vec<u8>,
+    /// Offer dropped, finished once read.
+    dropped: Option<WlDataOffer>,
+}
+
+/// Contents of our selection or drag being written to a pipe.
+struct PendingWrite {
+    pipe: File,
+    data: Vec<u8>,
+    written: usize,
+}
+
+/// Completed read of a selection or a drop.
+pub struct ReadDone {
+    pub surface: WlSurface,
+    pub transfer: Transfer,
+    pub mime_type: String,
+    pub data: Vec<u8>,
+}
+
+impl Clipboard {
+    pub(super) fn new<State, U>(
+        data_manager: Option<WlDataDeviceManager>,
+        primary_manager: Option<ZwpPrimarySelectionDeviceManagerV1>,
+        seat: &WlSeat,
+        qh: &QueueHandle<State>,
+        data: U,
+    ) -> Clipboard
+    where
+        State: Dispatch<WlDataDevice, U> + Dispatch<ZwpPrimarySelectionDeviceV1, U> + 'static,
+        U: Send + Sync + Copy + 'static,
+    {
+        let data_device = data_manager
+            .as_ref()
+            .map(|manager| manager.get_data_device(seat, qh, data));
+        let primary_device = primary_manager
+            .as_ref()
+            .map(|manager| manager.get_device(seat, qh, data));
+
+        Clipboard {
+            data_manager,
+            data_device,
+            primary_manager,
+            primary_device,
+            serial: 0,
+            offers: Vec::new(),
+            clipboard: Default::default(),
+            primary: Default::default(),
+            drag: None,
+            drag_source: None,
+            reads: Vec::new(),
+            writes: Vec::new(),
+        }
+    }
+
+    pub fn set_serial(&mut self, serial: u32) {
+        self.serial = serial;
+    }
+
+    pub fn mime_types(&self, selection: Selection) -> &[String] {
+        self.state(selection)
+            .offer
+            .as_ref()
+            .map_or(&[], |(_, mime_types)| mime_types)
+    }
+
+    /// Offers `mime_types` on `selection`, serving them with `selection_data`.
+    pub fn set<State, U>(
+        &mut self,
+        selection: Selection,
+        mime_types: Vec<String>,
+        selection_data: SelectionData,
+        qh: &QueueHandle<State>,
+        data: U,
+    ) where
+        State: Dispatch<WlDataSource, U> + Dispatch<ZwpPrimarySelectionSourceV1, U> + 'static,
+        U: Send + Sync + Copy + 'static,
+    {
+        let source = match selection {
+            Selection::Clipboard => {
+                let (Some(manager), Some(device)) = (&self.data_manager, &self.data_device) else {
+                    eprintln!("the compositor has no clipboard");
+                    return;
+                };
+                let source = manager.create_data_source(qh, data);
+                for mime_type in mime_types {
+                    source.offer(mime_type);
+                }
+                device.set_selection(Some(&source), self.serial);
+                Source::Data(source)
+            }
+            Selection::Primary => {
+                let (Some(manager), Some(device)) = (&self.primary_manager, &self.primary_device)
+                else {
+                    eprintln!("the compositor has no primary selection");
+                    return;
+                };
+                let source = manager.create_source(qh, data);
+                for mime_type in mime_types {
+                    source.offer(mime_type);
+                }
+                device.set_selection(Some(&source), self.serial);
+                Source::Primary(source)
+            }
+        };
+
+        let state = self.state_mut(selection);
+        if let Some((old, _)) = state.source.replace((source, selection_data)) {
+            old.destroy();
+        }
+    }
+
+    /// Starts reading `selection` as `mime_type` for the window of `surface`,
+    /// [`Clipboard::read_ready`] returns the contents once read.
+    pub fn read(&mut self, selection: Selection, mime_type: String, surface: WlSurface) {
+        let Some((offer, _)) = self.state(selection).offer.as_ref() else {
+            return;
+        };
+        let (reader, writer) = match pipe() {
+            Ok(pipe) => pipe,
+            Err(e) => {
+                eprintln!("failed to create a pipe for the clipboard: {e}");
+                return;
+            }
+        };
+        offer.receive(mime_type.clone(), &writer);
+        // the other client holds the write end until it's done writing
+        drop(writer);
+
+        self.reads.push(PendingRead {
+            surface,
+            transfer: Transfer::Selection(selection),
+            mime_type,
+            pipe: File::from(reader),
+            data: Vec::new(),
+            dropped: None,
+        });
+    }
+
+    /// Starts dragging `mime_types` from the window of `surface`, with the
+    /// button pressed last, serving the drop with `selection_data`. The
+    /// compositor shows `icon` under the pointer, drawn at `scale`.
+    #[allow(clippy::too_many_arguments)]
+    pub fn start_drag<State, U>(
+        &mut self,
+        surface: &WlSurface,
+        mime_types: Vec<String>,
+        actions: &[DndAction],
+        icon: Option<&DragIcon>,
+        scale: i32,
+        selection_data: SelectionData,
+        compositor: &WlCompositor,
+        shm: &WlShm,
+        qh: &QueueHandle<State>,
+        data: U,
+    ) where
+        State: Dispatch<WlDataSource, U>
+            + Dispatch<WlSurface, U>
+            + Dispatch<WlShmPool, U>
+            + Dispatch<WlBuffer, U>
+            + 'static,
+        U: Send + Sync + Copy + 'static,
+    {
+        let (Some(manager), Some(device)) = (&self.data_manager, &self.data_device) else {
+            eprintln!("the compositor has no drag and drop");
+            return;
+        };
+        let source = manager.create_data_source(qh, data);
+        for mime_type in mime_types {
+            source.offer(mime_type);
+        }
+        source.set_actions(
+            actions
+                .iter()
+                .fold(WlDndAction::empty(), |all, action| all | flags(*action)),
+        );
+
+        let icon = icon.and_then(|icon| {
+            let (width, height) = (icon.width as i32, icon.height as i32);
+            if icon.pixels.len() != icon.width as usize * icon.height as usize * 4 {
+                eprintln!("the drag icon isn't {width}x{height} RGBA pixels");
+                return None;
+            }
+            match shm::buffer(shm, width, height, &shm::argb(&icon.pixels), qh, data) {
+                Ok(buffer) => Some((compositor.create_surface(qh, data), buffer, icon)),
+                Err(e) => {
+                    eprintln!("failed to create the drag icon: {e}");
+                    None
+                }
+            }
+        });
+
+        device.start_drag(
+            Some(&source),
+            surface,
+            icon.as_ref().map(|(surface, _, _)| surface),
+            self.serial,
+        );
+        // the surface is an icon once the drag started
+        if let Some((icon_surface, buffer, icon)) = icon.as_ref() {
+            // the buffer scale has to divide the size
+            let scale = Some(scale.max(1))
+                .filter(|scale| icon.width % *scale as u32 == 0 && icon.height % *scale as u32 == 0)
+                .unwrap_or(1);
+            icon_surface.attach(Some(buffer), 0, 0);
+            icon_surface.set_buffer_scale(scale);
+            icon_surface.offset(-icon.hotspot.0 / scale, -icon.hotspot.1 / scale);
+            icon_surface.damage_buffer(0, 0, icon.width as i32, icon.height as i32);
+            icon_surface.commit();
+        }
+
+        let drag_source = DragSource {
+            source,
+            data: selection_data,
+            surface: surface.clone(),
+            icon: icon.map(|(surface, buffer, _)| (surface, buffer)),
+            action: None,
+        };
+        if let Some(old) = self.drag_source.replace(drag_source) {
+            old.destroy();
+        }
+    }
+
+    /// Accepts the drag over our surfaces to be dropped as `mime_type` with
+    /// `action`, or refuses it.
+    pub fn accept_drag(&mut self, accepted: Option<(String, DndAction)>) {
+        let Some(drag) = self.drag.as_mut() else {
+            return;
+        };
+        if drag.accepted == accepted {
+            return;
+        }
+        match accepted.as_ref() {
+            Some((mime_type, action)) => {
+                drag.offer.accept(drag.serial, Some(mime_type.clone()));
+                let actions = match action {
+                    // the user picks one of the actions of the source
+                    DndAction::Ask => WlDndAction::Copy | WlDndAction::Move | WlDndAction::Ask,
+                    _ => flags(*action),
+                };
+                drag.offer.set_actions(actions, flags(*action));
+            }
+            None => {
+                drag.offer.accept(drag.serial, None);
+                drag.offer
+                    .set_actions(WlDndAction::empty(), WlDndAction::empty());
+            }
+        }
+        drag.accepted = accepted;
+    }
+
+    /// Whether contents are being read or written.
+    pub fn is_transferring(&self) -> bool {
+        !self.reads.is_empty() || !self.writes.is_empty()
+    }
+
+    /// Poll entries waiting for the contents being read, and for room in the
+    /// pipes being written.
+    pub(crate) fn poll_fds(&self) -> Vec<PollFd> {
+        let reads = self
+            .reads
+            .iter()
+            .map(|read| PollFd::readable(read.pipe.as_raw_fd()));
+        let writes = self
+            .writes
+            .iter()
+            .map(|write| PollFd::writable(write.pipe.as_raw_fd()));
+        reads.chain(writes).collect()
+    }
+
+    /// Writes what the pipes have room for without blocking, closing them
+    /// once written or once the other client closed them.
+    pub fn write_ready(&mut self) {
+        self.writes.retain_mut(|write| loop {
+            if write.written == write.data.len() {
+                break false;
+            }
+            match write.pipe.write(&write.data[write.written..]) {
+                Ok(0) => break false,
+                Ok(len) => write.written += len,
+                Err(e) if e.kind() == io::ErrorKind::WouldBlock => break true,
+                Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                // the other client stopped reading
+                Err(e) if e.kind() == io::ErrorKind::BrokenPipe => break false,
+                Err(e) => {
+                    eprintln!("failed to write the selection: {e}");
+                    break false;
+                }
+            }
+        });
+    }
+
+    /// Reads what's available without blocking, returning the reads
+    /// completed.
+    pub fn read_ready(&mut self) -> Vec<ReadDone> {
+        let mut done = Vec::new();
+        let mut i = 0;
+        while i < self.reads.len() {
+            let read = &mut self.reads[i];
+            let mut buf = [0u8; 4096];
+            let finished = loop {
+                match read.pipe.read(&mut buf) {
+                    Ok(0) => break true,
+                    Ok(len) => read.data.extend_from_slice(&buf[..len]),
+                    Err(e) if e.kind() == io::ErrorKind::WouldBlock => break false,
+                    Err(e) if e.kind() == io::ErrorKind::Interrupted => {}
+                    Err(e) => {
+                        eprintln!("failed to read {:?}: {e}", read.transfer);
+                        break true;
+                    }
+                }
+            };
+
+            if finished {
+                let read = self.reads.remove(i);
+                if let Some(offer) = read.dropped {
+                    offer.finish();
+                    offer.destroy();
+                }
+                done.push(ReadDone {
+                    surface: read.surface,
+                    transfer: read.transfer,
+                    mime_type: read.mime_type,
+                    data: read.data,
+                });
+            } else {
+                i += 1;
+            }
+        }
+        done
+    }
+
+    fn state(&self, selection: Selection) -> &SelectionState {
+        match selection {
+            Selection::Clipboard => &self.clipboard,
+            Selection::Primary => &self.primary,
+        }
+    }
+
+    fn state_mut(&mut self, selection: Selection) -> &mut SelectionState {
+        match selection {
+            Selection::Clipboard => &mut self.clipboard,
+            Selection::Primary => &mut self.primary,
+        }
+    }
+
+    fn pending_offer(&mut self, offer: &Offer) -> Option<&mut PendingOffer> {
+        self.offers
+            .iter_mut()
+            .find(|pending| pending.offer == *offer)
+    }
+
+    fn take_offer(&mut self, offer: &Offer) -> Option<PendingOffer> {
+        let i = self
+            .offers
+            .iter()
+            .position(|pending| pending.offer == *offer)?;
+        Some(self.offers.remove(i))
+    }
+
+    /// Makes `offer` the contents of `selection`, returning its MIME types.
+    fn select(&mut self, selection: Selection, offer: Option<Offer>) -> Vec<String> {
+        let offer = offer
+            .and_then(|offer| self.take_offer(&offer))
+            .map(|pending| (pending.offer, pending.mime_types));
+        let mime_types = offer
+            .as_ref()
+            .map(|(_, mime_types)| mime_types.clone())
+            .unwrap_or_default();
+
+        let state = self.state_mut(selection);
+        if let Some((old, _)) = std::mem::replace(&mut state.offer, offer) {
+            old.destroy();
+        }
+        mime_types
+    }
+
+    /// Starts a drag over `surface`, returning the MIME types offered and
+    /// the actions the source allows.
+    fn drag_enter(
+        &mut self,
+        serial: u32,
+        surface: WlSurface,
+        position: (f64, f64),
+        offer: WlDataOffer,
+    ) -> (Vec<String>, Vec<DndAction>) {
+        self.drag_leave();
+
+        let pending = self.take_offer(&Offer::Data(offer.clone()));
+        let (mime_types, source_actions) = pending.map_or_else(
+            || (Vec::new(), WlDndAction::empty()),
+            |pending| (pending.mime_types, pending.source_actions),
+        );
+        self.drag = Some(DragOffer {
+            offer,
+            serial,
+            surface,
+            position,
+            source_actions,
+            accepted: None,
+            action: None,
+        });
+        (mime_types, actions(source_actions))
+    }
+
+    /// Moves the drag, returning the surface under it.
+    fn drag_motion(&mut self, position: (f64, f64)) -> Option<WlSurface> {
+        let drag = self.drag.as_mut()?;
+        drag.position = position;
+        Some(drag.surface.clone())
+    }
+
+    /// Ends the drag, returning the surface it left and its last position.
+    fn drag_leave(&mut self) -> Option<(WlSurface, (f64, f64))> {
+        let drag = self.drag.take()?;
+        drag.offer.destroy();
+        Some((drag.surface, drag.position))
+    }
+
+    /// Drops the drag, starting to read the contents when the window accepted
+    /// it. Returns the surface, the position and the action of the drop,
+    /// `None` when refused.
+    fn drop_drag(&mut self) -> Option<(WlSurface, (f64, f64), Option<DndAction>)> {
+        let drag = self.drag.take()?;
+        let refused = Some((drag.surface.clone(), drag.position, None));
+        let (Some((mime_type, _)), Some(mut action)) = (drag.accepted, drag.action) else {
+            drag.offer.destroy();
+            return refused;
+        };
+        if action == DndAction::Ask {
+            // copy unless the source only moves
+            action = if drag.source_actions.contains(WlDndAction::Copy) {
+                DndAction::Copy
+            } else {
+                DndAction::Move
+            };
+            drag.offer.set_actions(flags(action), flags(action));
+        }
+
+        let (reader, writer) = match pipe() {
+            Ok(pipe) => pipe,
+            Err(e) => {
+                eprintln!("failed to create a pipe for the drop: {e}");
+                drag.offer.destroy();
+                return refused;
+            }
+        };
+        drag.offer.receive(mime_type.clone(), writer.as_fd());
+        drop(writer);
+
+        let (x, y) = drag.position;
+        self.reads.push(PendingRead {
+            surface: drag.surface.clone(),
+            transfer: Transfer::Drop { x, y },
+            mime_type,
+            pipe: File::from(reader),
+            data: Vec::new(),
+            dropped: Some(drag.offer),
+        });
+        Some((drag.surface, drag.position, Some(action)))
+    }
+
+    /// Ends our drag, returning the surface it started from and the action
+    /// it was dropped with, `None` when cancelled.
+    fn end_drag_source(
+        &mut self,
+        source: &WlDataSource,
+        finished: bool,
+    ) -> Option<(WlSurface, Option<DndAction>)> {
+        if self.drag_source.as_ref()?.source != *source {
+            return None;
+        }
+        let drag_source = self.drag_source.take()?;
+        drag_source.destroy();
+        let action = drag_source.action.filter(|_| finished);
+        Some((drag_source.surface, action))
+    }
+
+    /// Writes the contents of our selection or drag to a client pasting or
+    /// dropping it.
+    fn send(&mut self, source: Source, mime_type: String, fd: OwnedFd) {
+        let state = [&self.clipboard, &self.primary]
+            .into_iter()
+            .find(|state| state.source.as_ref().is_some_and(|(s, _)| *s == source));
+        let drag_source = self
+            .drag_source
+            .as_ref()
+            .filter(|drag_source| match &source {
+                Source::Data(source) => drag_source.source == *source,
+                Source::Primary(_) => false,
+            });
+        let selection_data = match (state.and_then(|state| state.source.as_ref()), drag_source) {
+            (Some((_, selection_data)), _) => selection_data,
+            (None, Some(drag_source)) => &drag_source.data,
+            (None, None) => return,
+        };
+        let contents = selection_data(&mime_type);
+
+        // the other client may read slowly, or not at all
+        if let Err(e) = set_nonblocking(&fd) {
+            eprintln!("failed to write the selection: {e}");
+            return;
+        }
+        self.writes.push(PendingWrite {
+            pipe: File::from(fd),
+            data: contents,
+            written: 0,
+        });
+        self.write_ready();
+    }
+
+    /// Forgets our selection once another client replaced it.
+    fn cancel(&mut self, source: Source) {
+        for state in [&mut self.clipboard, &mut self.primary] {
+            if state.source.as_ref().is_some_and(|(s, _)| *s == source) {
+                state.source = None;
+            }
+        }
+        source.destroy();
+    }
+}
+
+impl Drop for Clipboard {
+    fn drop(&mut self) {
+        for state in [&self.clipboard, &self.primary] {
+            if let Some((source, _)) = state.source.as_ref() {
+                source.destroy();
+            }
+            if let Some((offer, _)) = state.offer.as_ref() {
+                offer.destroy();
+            }
+        }
+        for pending in &self.offers {
+            pending.offer.destroy();
+        }
+        if let Some(drag) = self.drag.as_ref() {
+            drag.offer.destroy();
+        }
+        if let Some(drag_source) = self.drag_source.as_ref() {
+            drag_source.destroy();
+        }
+        for read in &self.reads {
+            if let Some(offer) = read.dropped.as_ref() {
+                offer.destroy();
+            }
+        }
+        if let Some(device) = self.data_device.as_ref() {
+            device.release();
+        }
+        if let Some(device) = self.primary_device.as_ref() {
+            device.destroy();
+        }
+        if let Some(manager) = self.primary_manager.as_ref() {
+            manager.destroy();
+        }
+    }
+}
+
+/// Pipe whose read end doesn't block.
+fn pipe() -> io::Result<(OwnedFd, OwnedFd)> {
+    let (reader, writer) = io::pipe()?;
+    let (reader, writer) = (OwnedFd::from(reader), OwnedFd::from(writer));
+    set_nonblocking(&reader)?;
+    Ok((reader, writer))
+}
+
+fn set_nonblocking(fd: &OwnedFd) -> io::Result<()> {
+    unsafe {
+        let flags = fcntl(fd.as_raw_fd(), F_GETFL);
+        if flags < 0 || fcntl(fd.as_raw_fd(), F_SETFL, flags | O_NONBLOCK) < 0 {
+            return Err(io::Error::last_os_error());
+        }
+    }
+    Ok(())
+}
+
+fn flags(action: DndAction) -> WlDndAction {
+    match action {
+        DndAction::Copy => WlDndAction::Copy,
+        DndAction::Move => WlDndAction::Move,
+        DndAction::Ask => WlDndAction::Ask,
+    }
+}
+
+fn actions(allowed: WlDndAction) -> Vec<DndAction> {
+    [DndAction::Copy, DndAction::Move, DndAction::Ask]
+        .into_iter()
+        .filter(|action| allowed.contains(flags(*action)))
+        .collect()
+}
+
+/// The single action of `action`, as sent in action events.
+fn action(action: WEnum<WlDndAction>) -> Option<DndAction> {
+    let WEnum::Value(action) = action else {
+        return None;
+    };
+    actions(action).first().copied()
+}
+
+pub trait ClipboardHandler {
+    /// Called when the contents of `selection` changed, `mime_types` is empty
+    /// once it's cleared.
+    fn selection(&mut self, selection: Selection, mime_types: Vec<String>);
+    /// Called when a drag offering `mime_types` entered `surface`.
+    fn drag_enter(
+        &mut self,
+        surface: WlSurface,
+        position: (f64, f64),
+        mime_types: Vec<String>,
+        actions: Vec<DndAction>,
+    );
+    fn drag_motion(&mut self, surface: WlSurface, position: (f64, f64));
+    fn drag_leave(&mut self, surface: WlSurface, position: (f64, f64));
+    /// Called when the drag was dropped on `surface`, `action` is `None` when
+    /// the window refused it.
+    fn drag_drop(&mut self, surface: WlSurface, position: (f64, f64), action: Option<DndAction>);
+    /// Called when a drag started from `surface` ended, `action` is `None`
+    /// when it was cancelled.
+    fn drag_source_end(&mut self, surface: WlSurface, action: Option<DndAction>);
+}
+
+impl<State> Dispatch<WlDataDeviceManager, AppData, State> for Clipboard
+where
+    State: Dispatch<WlDataDeviceManager, AppData>,
+{
+    fn event(
+        _state: &mut State,
+        _proxy: &WlDataDeviceManager,
+        _event: <WlDataDeviceManager as Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+    }
+}
+
+impl<State> Dispatch<WlDataDevice, AppData, State> for Clipboard
+where
+    State: Dispatch<WlDataDevice, AppData>,
+    State: Dispatch<WlDataOffer, AppData>,
+    State: AsMut<Clipboard> + ClipboardHandler,
+    State: 'static,
+{
+    fn event(
+        state: &mut State,
+        _proxy: &WlDataDevice,
+        event: <WlDataDevice as Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+        let clipboard = state.as_mut();
+
+        use wl_data_device::Event;
+        match event {
+            Event::DataOffer { id } => {
+                clipboard.offers.push(PendingOffer {
+                    offer: Offer::Data(id),
+                    mime_types: Vec::new(),
+                    source_actions: WlDndAction::empty(),
+                });
+            }
+            Event::Selection { id } => {
+                let mime_types = clipboard.select(Selection::Clipboard, id.map(Offer::Data));
+                state.selection(Selection::Clipboard, mime_types);
+            }
+            Event::Enter {
+                serial,
+                surface,
+                x,
+                y,
+                id,
+            } => {
+                // drags without an offer stay within the client that started them
+                let Some(offer) = id else {
+                    return;
+                };
+                let (mime_types, actions) =
+                    clipboard.drag_enter(serial, surface.clone(), (x, y), offer);
+                state.drag_enter(surface, (x, y), mime_types, actions);
+            }
+            Event::Motion { x, y, .. } => {
+                if let Some(surface) = clipboard.drag_motion((x, y)) {
+                    state.drag_motion(surface, (x, y));
+                }
+            }
+            Event::Leave => {
+                if let Some((surface, position)) = clipboard.drag_leave() {
+                    state.drag_leave(surface, position);
+                }
+            }
+            Event::Drop => {
+                if let Some((surface, position, action)) = clipboard.drop_drag() {
+                    state.drag_drop(surface, position, action);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    event_created_child!(State, WlDataDevice, [
+        wl_data_device::EVT_DATA_OFFER_OPCODE => (WlDataOffer, AppData),
+    ]);
+}
+
+impl<State> Dispatch<WlDataOffer, AppData, State> for Clipboard
+where
+    State: Dispatch<WlDataOffer, AppData>,
+    State: AsMut<Clipboard>,
+{
+    fn event(
+        state: &mut State,
+        proxy: &WlDataOffer,
+        event: <WlDataOffer as Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+        let clipboard = state.as_mut();
+
+        use wl_data_offer::Event;
+        match event {
+            Event::Offer { mime_type } => {
+                if let Some(pending) = clipboard.pending_offer(&Offer::Data(proxy.clone())) {
+                    pending.mime_types.push(mime_type);
+                }
+            }
+            Event::SourceActions { source_actions } => {
+                if let Some(pending) = clipboard.pending_offer(&Offer::Data(proxy.clone())) {
+                    pending.source_actions =
+                        source_actions.into_result().unwrap_or(WlDndAction::empty());
+                }
+                if let Some(drag) = clipboard.drag.as_mut().filter(|drag| drag.offer == *proxy) {
+                    drag.source_actions =
+                        source_actions.into_result().unwrap_or(WlDndAction::empty());
+                }
+            }
+            Event::Action { dnd_action } => {
+                if let Some(drag) = clipboard.drag.as_mut().filter(|drag| drag.offer == *proxy) {
+                    drag.action = action(dnd_action);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<State> Dispatch<WlDataSource, AppData, State> for Clipboard
+where
+    State: Dispatch<WlDataSource, AppData>,
+    State: AsMut<Clipboard> + ClipboardHandler,
+{
+    fn event(
+        state: &mut State,
+        proxy: &WlDataSource,
+        event: <WlDataSource as Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+        let source = Source::Data(proxy.clone());
+
+        use wl_data_source::Event;
+        match event {
+            Event::Send { mime_type, fd } => state.as_mut().send(source, mime_type, fd),
+            Event::Cancelled => match state.as_mut().end_drag_source(proxy, false) {
+                Some((surface, action)) => state.drag_source_end(surface, action),
+                None => state.as_mut().cancel(source),
+            },
+            Event::DndFinished => {
+                if let Some((surface, action)) = state.as_mut().end_drag_source(proxy, true) {
+                    state.drag_source_end(surface, action);
+                }
+            }
+            Event::Action { dnd_action } => {
+                let clipboard = state.as_mut();
+                if let Some(drag_source) = clipboard
+                    .drag_source
+                    .as_mut()
+                    .filter(|drag_source| drag_source.source == *proxy)
+                {
+                    drag_source.action = action(dnd_action);
+                }
+            }
+            // the target's MIME type and the drop show in the action
+            Event::Target { .. } | Event::DndDropPerformed => {}
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<State> Dispatch<ZwpPrimarySelectionDeviceManagerV1, AppData, State> for Clipboard
+where
+    State: Dispatch<ZwpPrimarySelectionDeviceManagerV1, AppData>,
+{
+    fn event(
+        _state: &mut State,
+        _proxy: &ZwpPrimarySelectionDeviceManagerV1,
+        _event: <ZwpPrimarySelectionDeviceManagerV1 as Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+    }
+}
+
+impl<State> Dispatch<ZwpPrimarySelectionDeviceV1, AppData, State> for Clipboard
+where
+    State: Dispatch<ZwpPrimarySelectionDeviceV1, AppData>,
+    State: Dispatch<ZwpPrimarySelectionOfferV1, AppData>,
+    State: AsMut<Clipboard> + ClipboardHandler,
+    State: 'static,
+{
+    fn event(
+        state: &mut State,
+        _proxy: &ZwpPrimarySelectionDeviceV1,
+        event: <ZwpPrimarySelectionDeviceV1 as Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+        let clipboard = state.as_mut();
+
+        use zwp_primary_selection_device_v1::Event;
+        match event {
+            Event::DataOffer { offer } => {
+                clipboard.offers.push(PendingOffer {
+                    offer: Offer::Primary(offer),
+                    mime_types: Vec::new(),
+                    source_actions: WlDndAction::empty(),
+                });
+            }
+            Event::Selection { id } => {
+                let mime_types = clipboard.select(Selection::Primary, id.map(Offer::Primary));
+                state.selection(Selection::Primary, mime_types);
+            }
+            _ => unreachable!(),
+        }
+    }
+
+    event_created_child!(State, ZwpPrimarySelectionDeviceV1, [
+        zwp_primary_selection_device_v1::EVT_DATA_OFFER_OPCODE => (ZwpPrimarySelectionOfferV1, AppData),
+    ]);
+}
+
+impl<State> Dispatch<ZwpPrimarySelectionOfferV1, AppData, State> for Clipboard
+where
+    State: Dispatch<ZwpPrimarySelectionOfferV1, AppData>,
+    State: AsMut<Clipboard>,
+{
+    fn event(
+        state: &mut State,
+        proxy: &ZwpPrimarySelectionOfferV1,
+        event: <ZwpPrimarySelectionOfferV1 as Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+        use zwp_primary_selection_offer_v1::Event;
+        match event {
+            Event::Offer { mime_type } => {
+                let offer = Offer::Primary(proxy.clone());
+                if let Some(pending) = state.as_mut().pending_offer(&offer) {
+                    pending.mime_types.push(mime_type);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
+
+impl<State> Dispatch<ZwpPrimarySelectionSourceV1, AppData, State> for Clipboard
+where
+    State: Dispatch<ZwpPrimarySelectionSourceV1, AppData>,
+    State: AsMut<Clipboard>,
+{
+    fn event(
+        state: &mut State,
+        proxy: &ZwpPrimarySelectionSourceV1,
+        event: <ZwpPrimarySelectionSourceV1 as Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+        let source = Source::Primary(proxy.clone());
+
+        use zwp_primary_selection_source_v1::Event;
+        match event {
+            Event::Send { mime_type, fd } => state.as_mut().send(source, mime_type, fd),
+            Event::Cancelled => state.as_mut().cancel(source),
+            _ => unreachable!(),
+        }
+    }
+}
@@ -0,0 +1,381 @@
+use super::shm;
+use crate::desktop::{cursor::CursorIcon, wayland::app::AppData};
+use std::{collections::HashMap, fs, io, path::PathBuf};
+use wayland_client::{
+    protocol::{
+        wl_buffer::WlBuffer, wl_compositor::WlCompositor, wl_shm::WlShm, wl_shm_pool::WlShmPool,
+        wl_surface::WlSurface,
+    },
+    Dispatch, QueueHandle,
+};
+use wayland_protocols::wp::cursor_shape::v1::client::{
+    wp_cursor_shape_device_v1::{Shape, WpCursorShapeDeviceV1},
+    wp_cursor_shape_manager_v1::WpCursorShapeManagerV1,
+};
+
+/// Chunk type of the images in XCursor files.
+const XCURSOR_IMAGE: u32 = 0xfffd0002;
+const XCURSOR_DEFAULT_SIZE: u32 = 24;
+const XCURSOR_DEFAULT_PATH: &str =
+    "~/.local/share/icons:~/.icons:/usr/share/icons:/usr/share/pixmaps";
+
+/// Sets the cursor images, with the cursor shape protocol when the
+/// compositor supports it, else from the XCursor theme on a cursor surface.
+pub struct Cursors {
+    compositor: WlCompositor,
+    shm: WlShm,
+    shape_manager: Option<WpCursorShapeManagerV1>,
+    surface: Option<WlSurface>,
+    theme: Option<XCursorTheme>,
+}
+
+/// Cursor image of the theme, loaded in a buffer.
+struct ThemedCursor {
+    buffer: WlBuffer,
+    width: i32,
+    height: i32,
+    /// Buffer scale of the image.
+    scale: i32,
+    /// Hotspot in surface pixels.
+    hotspot: (i32, i32),
+}
+
+impl Cursors {
+    pub(super) fn new(
+        compositor: WlCompositor,
+        shm: WlShm,
+        shape_manager: Option<WpCursorShapeManagerV1>,
+    ) -> Cursors {
+        Cursors {
+            compositor,
+            shm,
+            shape_manager,
+            surface: None,
+            theme: None,
+        }
+    }
+
+    pub fn shape_manager(&self) -> Option<&WpCursorShapeManagerV1> {
+        self.shape_manager.as_ref()
+    }
+
+    /// Cursor surface showing `icon` from the XCursor theme for a window
+    /// drawn at `scale`, with its hotspot. The theme is loaded on first use.
+    pub fn themed<State, U>(
+        &mut self,
+        icon: CursorIcon,
+        scale: i32,
+        qh: &QueueHandle<State>,
+        data: U,
+    ) -> Option<(&WlSurface, (i32, i32))>
+    where
+        State: Dispatch<WlSurface, U> + Dispatch<WlShmPool, U> + Dispatch<WlBuffer, U> + 'static,
+        U: Send + Sync + Copy + 'static,
+    {
+        let theme = self.theme.get_or_insert_with(XCursorTheme::from_env);
+        let cursor = theme.cursor(icon, scale, &self.shm, qh, data)?;
+
+        let surface = self
+            .surface
+            .get_or_insert_with(|| self.compositor.create_surface(qh, data));
+        surface.attach(Some(&cursor.buffer), 0, 0);
+        surface.set_buffer_scale(cursor.scale);
+        surface.damage_buffer(0, 0, cursor.width, cursor.height);
+        surface.commit();
+
+        Some((surface, cursor.hotspot))
+    }
+}
+
+impl Drop for Cursors {
+    fn drop(&mut self) {
+        if let Some(theme) = self.theme.as_ref() {
+            for cursor in theme.cursors.values().flatten() {
+                cursor.buffer.destroy();
+            }
+        }
+        if let Some(surface) = self.surface.as_ref() {
+            surface.destroy();
+        }
+        if let Some(manager) = self.shape_manager.as_ref() {
+            manager.destroy();
+        }
+    }
+}
+
+/// Shape of `icon` in the cursor shape protocol.
+pub fn shape(icon: CursorIcon) -> Shape {
+    use CursorIcon::*;
+    match icon {
+        Default => Shape::Default,
+        ContextMenu => Shape::ContextMenu,
+        Help => Shape::Help,
+        Pointer => Shape::Pointer,
+        Progress => Shape::Progress,
+        Wait => Shape::Wait,
+        Cell => Shape::Cell,
+        Crosshair => Shape::Crosshair,
+        Text => Shape::Text,
+        VerticalText => Shape::VerticalText,
+        Alias => Shape::Alias,
+        Copy => Shape::Copy,
+        Move => Shape::Move,
+        NoDrop => Shape::NoDrop,
+        NotAllowed => Shape::NotAllowed,
+        Grab => Shape::Grab,
+        Grabbing => Shape::Grabbing,
+        EResize => Shape::EResize,
+        NResize => Shape::NResize,
+        NeResize => Shape::NeResize,
+        NwResize => Shape::NwResize,
+        SResize => Shape::SResize,
+        SeResize => Shape::SeResize,
+        SwResize => Shape::SwResize,
+        WResize => Shape::WResize,
+        EwResize => Shape::EwResize,
+        NsResize => Shape::NsResize,
+        NeswResize => Shape::NeswResize,
+        NwseResize => Shape::NwseResize,
+        ColResize => Shape::ColResize,
+        RowResize => Shape::RowResize,
+        AllScroll => Shape::AllScroll,
+        ZoomIn => Shape::ZoomIn,
+        ZoomOut => Shape::ZoomOut,
+    }
+}
+
+/// XCursor theme set by `XCURSOR_THEME` and `XCURSOR_SIZE`, searched in
+/// `XCURSOR_PATH` like libXcursor does.
+struct XCursorTheme {
+    name: String,
+    /// Size in logical pixels, images are loaded at this size times the
+    /// scale.
+    size: u32,
+    path: Vec<PathBuf>,
+    /// Loaded cursors by icon and scale, `None` for the icons the theme
+    /// lacks.
+    cursors: HashMap<(CursorIcon, i32), Option<ThemedCursor>>,
+}
+
+impl XCursorTheme {
+    fn from_env() -> XCursorTheme {
+        let name = std::env::var("XCURSOR_THEME").unwrap_or_else(|_| "default".to_owned());
+        let size = std::env::var("XCURSOR_SIZE")
+            .ok()
+            .and_then(|size| size.parse().ok())
+            .filter(|size| *size > 0)
+            .unwrap_or(XCURSOR_DEFAULT_SIZE);
+        let path = std::env::var("XCURSOR_PATH").unwrap_or_else(|_| XCURSOR_DEFAULT_PATH.into());
+        let home = std::env::var("HOME").unwrap_or_default();
+        let path = path
+            .split(':')
+            .filter(|dir| !dir.is_empty())
+            .map(|dir| match dir.strip_prefix('~') {
+                Some(rest) => PathBuf::from(format!("{home}{rest}")),
+                None => PathBuf::from(dir),
+            })
+            .collect();
+
+        XCursorTheme {
+            name,
+            size,
+            path,
+            cursors: HashMap::new(),
+        }
+    }
+
+    fn cursor<State, U>(
+        &mut self,
+        icon: CursorIcon,
+        scale: i32,
+        shm: &WlShm,
+        qh: &QueueHandle<State>,
+        data: U,
+    ) -> Option<&ThemedCursor>
+    where
+        State: Dispatch<WlShmPool, U> + Dispatch<WlBuffer, U> + 'static,
+        U: Send + Sync + Copy + 'static,
+    {
+        let scale = scale.max(1);
+        if !self.cursors.contains_key(&(icon, scale)) {
+            // icons missing from the theme fall back to the default cursor
+            let size = self.size * scale as u32;
+            let image = [icon, CursorIcon::Default]
+                .iter()
+                .flat_map(|icon| icon.names())
+                .find_map(|name| self.load(name, size));
+            let cursor = match image.map(|image| image.upload(scale, shm, qh, data)) {
+                Some(Ok(cursor)) => Some(cursor),
+                Some(Err(e)) => {
+                    eprintln!("failed to load the {icon:?} cursor: {e}");
+                    None
+                }
+                None => {
+                    eprintln!("cursor theme {} has no {icon:?} cursor", self.name);
+                    None
+                }
+            };
+            self.cursors.insert((icon, scale), cursor);
+        }
+        self.cursors[&(icon, scale)].as_ref()
+    }
+
+    /// Image of the cursor file `name` closest to `size`, in the theme or the
+    /// themes it inherits.
+    fn load(&self, name: &str, size: u32) -> Option<XCursorImage> {
+        let mut themes = vec![self.name.clone()];
+        let mut i = 0;
+        while i < themes.len() {
+            for dir in &self.path {
+                let theme = dir.join(&themes[i]);
+                if let Ok(data) = fs::read(theme.join("cursors").join(name)) {
+                    if let Some(image) = XCursorImage::parse(&data, size) {
+                        return Some(image);
+                    }
+                }
+                let Ok(index) = fs::read_to_string(theme.join("index.theme")) else {
+                    continue;
+                };
+                for inherited in inherits(&index) {
+                    if !themes.contains(&inherited) {
+                        themes.push(inherited);
+                    }
+                }
+            }
+            i += 1;
+        }
+        None
+    }
+}
+
+/// Themes listed by the `Inherits` key of an `index.theme`.
+fn inherits(index: &str) -> Vec<String> {
+    index
+        .lines()
+        .filter_map(|line| line.trim().strip_prefix("Inherits"))
+        .filter_map(|rest| rest.trim_start().strip_prefix('='))
+        .flat_map(|themes| themes.split([',', ';']))
+        .map(|theme| theme.trim().to_owned())
+        .filter(|theme| !theme.is_empty())
+        .collect()
+}
+
+/// Image of an XCursor file, premultiplied ARGB in native order.
+struct XCursorImage {
+    width: u32,
+    height: u32,
+    hotspot: (u32, u32),
+    pixels: Vec<u8>,
+}
+
+impl XCursorImage {
+    /// First image of the size closest to `size`, animated cursors are shown
+    /// still.
+    fn parse(data: &[u8], size: u32) -> Option<XCursorImage> {
+        let u32_at = |offset: usize| -> Option<u32> {
+            let bytes = data.get(offset..offset + 4)?;
+            Some(u32::from_le_bytes(bytes.try_into().unwrap()))
+        };
+
+        if data.get(0..4)? != b"Xcur" {
+            return None;
+        }
+        let header = u32_at(4)? as usize;
+        let count = u32_at(12)? as usize;
+
+        // table of contents entries are type, nominal size and position
+        let images: Vec<(u32, usize)> = (0..count)
+            .filter_map(|i| {
+                let entry = header + i * 12;
+                if u32_at(entry)? != XCURSOR_IMAGE {
+                    return None;
+                }
+                Some((u32_at(entry + 4)?, u32_at(entry + 8)? as usize))
+            })
+            .collect();
+        let nearest = images
+            .iter()
+            .map(|(s, _)| *s)
+            .min_by_key(|s| s.abs_diff(size))?;
+        let (_, position) = images.iter().find(|(s, _)| *s == nearest)?;
+
+        // image header, then pixels as little endian ARGB words
+        let width = u32_at(position + 16)?;
+        let height = u32_at(position + 20)?;
+        let hotspot = (u32_at(position + 24)?, u32_at(position + 28)?);
+        let start = position + u32_at(*position)? as usize;
+        let len = width as usize * height as usize * 4;
+        let pixels = data.get(start..start + len)?;
+        let pixels = pixels
+            .chunks(4)
+            .flat_map(|p| u32::from_le_bytes(p.try_into().unwrap()).to_ne_bytes())
+            .collect();
+
+        Some(XCursorImage {
+            width,
+            height,
+            hotspot,
+            pixels,
+        })
+    }
+
+    /// Copies the image to a shared memory buffer shown at `scale`, or at
+    /// `1` when its size isn't a multiple of the scale.
+    fn upload<State, U>(
+        &self,
+        scale: i32,
+        shm: &WlShm,
+        qh: &QueueHandle<State>,
+        data: U,
+    ) -> io::Result<ThemedCursor>
+    where
+        State: Dispatch<WlShmPool, U> + Dispatch<WlBuffer, U> + 'static,
+        U: Send + Sync + Copy + 'static,
+    {
+        let (width, height) = (self.width as i32, self.height as i32);
+        let buffer = shm::buffer(shm, width, height, &self.pixels, qh, data)?;
+        let scale = if width % scale == 0 && height % scale == 0 {
+            scale
+        } else {
+            1
+        };
+
+        Ok(ThemedCursor {
+            buffer,
+            width,
+            height,
+            scale,
+            hotspot: (self.hotspot.0 as i32 / scale, self.hotspot.1 as i32 / scale),
+        })
+    }
+}
+
+impl<State> Dispatch<WpCursorShapeManagerV1, AppData, State> for Cursors
+where
+    State: Dispatch<WpCursorShapeManagerV1, AppData>,
+{
+    fn event(
+        _state: &mut State,
+        _proxy: &WpCursorShapeManagerV1,
+        _event: <WpCursorShapeManagerV1 as wayland_client::Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+    }
+}
+
+impl<State> Dispatch<WpCursorShapeDeviceV1, AppData, State> for Cursors
+where
+    State: Dispatch<WpCursorShapeDeviceV1, AppData>,
+{
+    fn event(
+        _state: &mut State,
+        _proxy: &WpCursorShapeDeviceV1,
+        _event: <WpCursorShapeDeviceV1 as wayland_client::Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+    }
+}
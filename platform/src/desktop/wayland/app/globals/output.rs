@@ -0,0 +1,140 @@
+use crate::desktop::wayland::app::AppData;
+use wayland_client::{
+    globals::GlobalList,
+    protocol::{
+        wl_output::{self, WlOutput},
+        wl_registry::WlRegistry,
+    },
+    Dispatch, Proxy, QueueHandle,
+};
+
+/// Outputs of the compositor and their scale, outputs come and go as
+/// monitors are plugged.
+pub struct Outputs {
+    outputs: Vec<Output>,
+}
+
+struct Output {
+    /// Name of the output's global in the registry.
+    name: u32,
+    wl_output: WlOutput,
+    scale: i32,
+    /// Scale sent since the last `done` event.
+    pending_scale: i32,
+}
+
+impl Outputs {
+    pub(super) fn new<State, U>(globals: &GlobalList, qh: &QueueHandle<State>, data: U) -> Outputs
+    where
+        State: Dispatch<WlOutput, U> + 'static,
+        U: Send + Sync + Copy + 'static,
+    {
+        let mut outputs = Outputs {
+            outputs: Vec::new(),
+        };
+        for global in globals.contents().clone_list() {
+            if global.interface == "wl_output" {
+                outputs.add(globals.registry(), global.name, global.version, qh, data);
+            }
+        }
+        outputs
+    }
+
+    /// Binds the output announced as `name`.
+    pub fn add<State, U>(
+        &mut self,
+        registry: &WlRegistry,
+        name: u32,
+        version: u32,
+        qh: &QueueHandle<State>,
+        data: U,
+    ) where
+        State: Dispatch<WlOutput, U> + 'static,
+        U: Send + Sync + Copy + 'static,
+    {
+        // scales are applied on `done` from version 2
+        if version < 2 {
+            return;
+        }
+        let wl_output = registry.bind(name, version.min(4), qh, data);
+        self.outputs.push(Output {
+            name,
+            wl_output,
+            scale: 1,
+            pending_scale: 1,
+        });
+    }
+
+    /// Releases the output whose global `name` was removed, returning it.
+    pub fn remove(&mut self, name: u32) -> Option<WlOutput> {
+        let i = self.outputs.iter().position(|output| output.name == name)?;
+        let output = self.outputs.remove(i);
+        if output.wl_output.version() >= 3 {
+            output.wl_output.release();
+        }
+        Some(output.wl_output)
+    }
+
+    /// Integer scale of `output`, `1` for outputs we don't know.
+    pub fn scale(&self, output: &WlOutput) -> i32 {
+        self.outputs
+            .iter()
+            .find(|o| o.wl_output == *output)
+            .map_or(1, |o| o.scale)
+    }
+}
+
+impl Drop for Outputs {
+    fn drop(&mut self) {
+        for output in &self.outputs {
+            if output.wl_output.version() >= 3 {
+                output.wl_output.release();
+            }
+        }
+    }
+}
+
+pub trait OutputHandler {
+    /// Called when the scale of `output` changed.
+    fn scale_changed(&mut self, output: WlOutput);
+}
+
+impl<State> Dispatch<WlOutput, AppData, State> for Outputs
+where
+    State: Dispatch<WlOutput, AppData>,
+    State: AsMut<Outputs> + OutputHandler,
+{
+    fn event(
+        state: &mut State,
+        proxy: &WlOutput,
+        event: <WlOutput as wayland_client::Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+        let Some(output) = state
+            .as_mut()
+            .outputs
+            .iter_mut()
+            .find(|output| output.wl_output == *proxy)
+        else {
+            return;
+        };
+
+        use wl_output::Event;
+        match event {
+            Event::Scale { factor } => output.pending_scale = factor,
+            Event::Done => {
+                if output.scale != output.pending_scale {
+                    output.scale = output.pending_scale;
+                    state.scale_changed(proxy.clone());
+                }
+            }
+            Event::Geometry { .. }
+            | Event::Mode { .. }
+            | Event::Name { .. }
+            | Event::Description { .. } => {}
+            _ => unreachable!(),
+        }
+    }
+}
@@ -1,23 +1,27 @@
 use self::keyboard::Keyboard;
 use crate::desktop::wayland::app::AppData;
 use pointer::Pointer;
+use touch::Touch;
 use wayland_client::{
     protocol::{
         wl_keyboard::WlKeyboard,
         wl_pointer::WlPointer,
         wl_seat::{self, Capability, WlSeat},
+        wl_touch::WlTouch,
     },
     Dispatch,
 };
 
 pub mod keyboard;
 pub mod pointer;
+pub mod touch;
 
 pub struct Seat {
     wl_seat: WlSeat,
     name: String,
     keyboard: Option<Keyboard>,
     pointer: Option<Pointer>,
+    touch: Option<Touch>,
 }
 
 impl Seat {
@@ -27,6 +31,7 @@ impl Seat {
             name: String::new(),
             keyboard: None,
             pointer: None,
+            touch: None,
         }
     }
 
@@ -34,9 +39,21 @@ impl Seat {
         &self.pointer
     }
 
+    pub fn pointer_mut(&mut self) -> &mut Option<Pointer> {
+        &mut self.pointer
+    }
+
     pub fn keyboard(&self) -> &Option<Keyboard> {
         &self.keyboard
     }
+
+    pub fn keyboard_mut(&mut self) -> &mut Option<Keyboard> {
+        &mut self.keyboard
+    }
+
+    pub fn touch(&self) -> &Option<Touch> {
+        &self.touch
+    }
 }
 
 impl<State> Dispatch<WlSeat, AppData, State> for Seat
@@ -44,6 +61,7 @@ where
     State: Dispatch<WlSeat, AppData>,
     State: Dispatch<WlKeyboard, AppData>,
     State: Dispatch<WlPointer, AppData>,
+    State: Dispatch<WlTouch, AppData>,
     State: AsMut<Seat>,
     State: 'static,
 {
@@ -62,6 +80,7 @@ where
 
                 let keyboard = capabilities.contains(Capability::Keyboard);
                 let pointer = capabilities.contains(Capability::Pointer);
+                let touch = capabilities.contains(Capability::Touch);
 
                 let seat = state.as_mut();
 
@@ -77,6 +96,12 @@ where
                 } else if !pointer && seat.pointer.is_some() {
                     seat.pointer = None;
                 }
+
+                if touch && seat.touch.is_none() {
+                    seat.touch = Some(Touch::new(seat.wl_seat.get_touch(qhandle, AppData)));
+                } else if !touch && seat.touch.is_some() {
+                    seat.touch = None;
+                }
             }
             Name { name } => {
                 state.as_mut().name = name;
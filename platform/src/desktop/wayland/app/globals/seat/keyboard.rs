@@ -1,6 +1,13 @@
 use super::Seat;
-use crate::desktop::wayland::app::AppData;
+use crate::desktop::{
+    event::{Key, KeyboardEvent, Modifiers},
+    wayland::app::AppData,
+};
 use core::panic;
+use std::{
+    ffi::OsString,
+    time::{Duration, Instant},
+};
 use wayland_client::{
     protocol::{
         wl_keyboard::{self, KeyState, KeymapFormat, WlKeyboard},
@@ -8,21 +15,136 @@ use wayland_client::{
     },
     Dispatch, WEnum,
 };
-use xkbcommon::xkb;
-use xkeysym::{KeyCode, Keysym};
+use xkbcommon::xkb::{self, compose};
+use xkeysym::{key, KeyCode, Keysym};
 
 pub struct Keyboard {
     wl_keyboard: WlKeyboard,
     xkb_context: xkb::Context,
     xkb_state: Option<xkb::State>,
+    compose: Option<compose::State>,
+    focus: Option<WlSurface>,
+    repeat_info: RepeatInfo,
+    repeat: Option<Repeat>,
+}
+
+/// Key repeat settings of the compositor.
+#[derive(Debug, Clone, Copy)]
+struct RepeatInfo {
+    /// Repeats per second, `0` disables repeat.
+    rate: i32,
+    delay: Duration,
+}
+
+/// Key held down, repeated from `next` on.
+struct Repeat {
+    key: u32,
+    next: Instant,
 }
 
 impl Keyboard {
     pub fn new(wl_keyboard: WlKeyboard) -> Keyboard {
+        let xkb_context = xkb::Context::new(xkb::CONTEXT_NO_FLAGS);
+        let compose = compose_state(&xkb_context);
         Keyboard {
             wl_keyboard,
-            xkb_context: xkb::Context::new(xkb::CONTEXT_NO_FLAGS),
+            xkb_context,
             xkb_state: None,
+            compose,
+            focus: None,
+            // used by compositors before version 4 of wl_keyboard
+            repeat_info: RepeatInfo {
+                rate: 25,
+                delay: Duration::from_millis(600),
+            },
+            repeat: None,
+        }
+    }
+
+    pub fn focus(&self) -> &Option<WlSurface> {
+        &self.focus
+    }
+
+    /// When the held key repeats next, if one is held.
+    pub fn next_repeat(&self) -> Option<Instant> {
+        self.repeat.as_ref().map(|repeat| repeat.next)
+    }
+
+    /// Returns the press of the held key when it's due to repeat at `now`.
+    pub fn repeat_key(&mut self, now: Instant) -> Option<KeyboardEvent> {
+        let repeat = self.repeat.as_mut()?;
+        if repeat.next > now {
+            return None;
+        }
+        let interval = Duration::from_secs(1) / self.repeat_info.rate as u32;
+        repeat.next += interval;
+        // skip the repeats missed while the event loop was busy
+        if repeat.next < now {
+            repeat.next = now + interval;
+        }
+
+        let key = repeat.key;
+        self.key_event(key, KeyState::Pressed, true)
+    }
+
+    /// Event of `key` changing to `key_state` with the current modifiers,
+    /// feeding presses to the compose sequence.
+    fn key_event(&mut self, key: u32, key_state: KeyState, repeat: bool) -> Option<KeyboardEvent> {
+        let xkb_state = self.xkb_state.as_ref()?;
+        let keycode = KeyCode::new(key + 8);
+        let mut keysym = xkb_state.key_get_one_sym(keycode);
+        let mut utf8 = xkb_state.key_get_utf8(keycode);
+        let modifiers = modifiers(xkb_state);
+
+        let mut composing = false;
+        let mut cancelled = false;
+        let compose = self.compose.as_mut().filter(|_| !repeat);
+        if let (KeyState::Pressed, Some(compose)) = (key_state, compose) {
+            if compose.feed(keysym) == compose::FeedResult::Accepted {
+                match compose.status() {
+                    compose::Status::Composing => composing = true,
+                    compose::Status::Composed => {
+                        keysym = compose.keysym().unwrap_or(keysym);
+                        utf8 = compose.utf8().unwrap_or_default();
+                        compose.reset();
+                    }
+                    // the key cancelling the sequence types nothing
+                    compose::Status::Cancelled => {
+                        cancelled = true;
+                        compose.reset();
+                    }
+                    compose::Status::Nothing => {}
+                }
+            }
+        }
+
+        let logical = if composing {
+            utf8.clear();
+            Key::Dead
+        } else if cancelled {
+            utf8.clear();
+            Key::Unidentified
+        } else {
+            logical_key(keysym, &utf8)
+        };
+
+        match key_state {
+            KeyState::Pressed => Some(KeyboardEvent::KeyPressed {
+                key,
+                logical,
+                keysym,
+                utf8,
+                modifiers,
+                repeat,
+            }),
+            KeyState::Released => Some(KeyboardEvent::KeyReleased {
+                key,
+                logical,
+                keysym,
+                utf8,
+                modifiers,
+            }),
+            _ => None,
         }
     }
 }
@@ -33,36 +155,78 @@ impl Drop for Keyboard {
     }
 }
 
-pub struct Modifiers {
-    pub ctrl: bool,
-    pub alt: bool,
-    pub shift: bool,
-    pub logo: bool,
+fn modifiers(state: &xkb::State) -> Modifiers {
+    let active = |name: &str| state.mod_name_is_active(name, xkb::STATE_MODS_EFFECTIVE);
+    Modifiers {
+        ctrl: active(xkb::MOD_NAME_CTRL),
+        alt: active(xkb::MOD_NAME_ALT),
+        shift: active(xkb::MOD_NAME_SHIFT),
+        logo: active(xkb::MOD_NAME_LOGO),
+        caps_lock: active(xkb::MOD_NAME_CAPS),
+        num_lock: active(xkb::MOD_NAME_NUM),
+    }
 }
 
-impl Modifiers {
-    fn new(state: &xkb::State) -> Modifiers {
-        Modifiers {
-            ctrl: state.mod_name_is_active(xkb::MOD_NAME_CTRL, xkb::STATE_MODS_EFFECTIVE),
-            alt: state.mod_name_is_active(xkb::MOD_NAME_ALT, xkb::STATE_MODS_EFFECTIVE),
-            shift: state.mod_name_is_active(xkb::MOD_NAME_SHIFT, xkb::STATE_MODS_EFFECTIVE),
-            logo: state.mod_name_is_active(xkb::MOD_NAME_LOGO, xkb::STATE_MODS_EFFECTIVE),
+/// Compose sequences of the user's locale, as set by `LC_ALL`, `LC_CTYPE`
+/// or `LANG`.
+fn compose_state(context: &xkb::Context) -> Option<compose::State> {
+    let locale = ["LC_ALL", "LC_CTYPE", "LANG"]
+        .iter()
+        .find_map(|var| std::env::var_os(var).filter(|value| !value.is_empty()))
+        .unwrap_or_else(|| OsString::from("C"));
+    let table =
+        compose::Table::new_from_locale(context, &locale, compose::COMPILE_NO_FLAGS).ok()?;
+    Some(compose::State::new(&table, compose::STATE_NO_FLAGS))
+}
+
+fn logical_key(keysym: Keysym, utf8: &str) -> Key {
+    match keysym.raw() {
+        key::Return | key::KP_Enter => Key::Enter,
+        key::Tab | key::KP_Tab | key::ISO_Left_Tab => Key::Tab,
+        key::BackSpace => Key::Backspace,
+        key::Delete | key::KP_Delete => Key::Delete,
+        key::Escape => Key::Escape,
+        key::Insert | key::KP_Insert => Key::Insert,
+        key::Home | key::KP_Home => Key::Home,
+        key::End | key::KP_End => Key::End,
+        key::Page_Up | key::KP_Page_Up => Key::PageUp,
+        key::Page_Down | key::KP_Page_Down => Key::PageDown,
+        key::Left | key::KP_Left => Key::ArrowLeft,
+        key::Right | key::KP_Right => Key::ArrowRight,
+        key::Up | key::KP_Up => Key::ArrowUp,
+        key::Down | key::KP_Down => Key::ArrowDown,
+        key::Shift_L | key::Shift_R => Key::Shift,
+        key::Control_L | key::Control_R => Key::Control,
+        key::Alt_L | key::Alt_R | key::Meta_L | key::Meta_R => Key::Alt,
+        key::ISO_Level3_Shift => Key::AltGraph,
+        key::Super_L | key::Super_R => Key::Super,
+        key::Caps_Lock => Key::CapsLock,
+        key::Num_Lock => Key::NumLock,
+        key::Scroll_Lock => Key::ScrollLock,
+        key::Print => Key::PrintScreen,
+        key::Pause => Key::Pause,
+        key::Menu => Key::ContextMenu,
+        raw @ key::F1..=key::F24 => Key::F((raw - key::F1 + 1) as u8),
+        // the dead keysyms, around the AccessX ones
+        key::dead_grave..=key::dead_currency
+        | key::dead_a..=key::dead_greek
+        | key::dead_lowline..=key::dead_longsolidusoverlay => Key::Dead,
+        _ if !utf8.is_empty() && !utf8.chars().any(char::is_control) => {
+            Key::Character(utf8.to_owned())
         }
+        // text typed with Ctrl is a control character
+        _ => match keysym.key_char().filter(|c| !c.is_control()) {
+            Some(c) => Key::Character(c.to_string()),
+            None => Key::Unidentified,
+        },
     }
 }
 
 pub trait KeyboardHandler {
     fn enter(&mut self, serial: u32, surface: WlSurface, keys: Vec<u32>, keysyms: Vec<Keysym>);
     fn leave(&mut self, serial: u32, surface: WlSurface);
-    fn key(
-        &mut self,
-        serial: u32,
-        time: u32,
-        state: KeyState,
-        key: u32,
-        keysym: Keysym,
-        utf8: String,
-    );
+    /// `event` is a `KeyPressed` or a `KeyReleased`.
+    fn key(&mut self, serial: u32, time: u32, event: KeyboardEvent);
     fn modifiers(&mut self, serial: u32, modifiers: Modifiers, group: u32);
 }
 
@@ -90,6 +254,10 @@ where
                         println!("wl_keyboard: non-xkb ketmap");
                     }
                     KeymapFormat::XkbV1 => {
+                        keyboard.repeat = None;
+                        if let Some(compose) = keyboard.compose.as_mut() {
+                            compose.reset();
+                        }
                         let context = &keyboard.xkb_context;
                         match unsafe {
                             xkb::Keymap::new_from_fd(
@@ -136,9 +304,15 @@ where
                     .map(|raw| xkb_state.key_get_one_sym(KeyCode::new(raw + 8)))
                     .collect::<Vec<_>>();
 
+                keyboard.focus = Some(surface.clone());
                 state.enter(serial, surface, keys, keysyms);
             }
             Event::Leave { serial, surface } => {
+                keyboard.focus = None;
+                keyboard.repeat = None;
+                if let Some(compose) = keyboard.compose.as_mut() {
+                    compose.reset();
+                }
                 state.leave(serial, surface);
             }
             Event::Key {
@@ -149,12 +323,30 @@ where
             } => match key_state {
                 WEnum::Value(key_state) => {
                     let xkb_state = &keyboard.xkb_state.as_ref().unwrap();
-
                     let keycode = KeyCode::new(key + 8);
-                    let keysym = xkb_state.key_get_one_sym(keycode);
-                    let utf8 = xkb_state.key_get_utf8(keycode);
 
-                    state.key(serial, time, key_state, key, keysym, utf8);
+                    match key_state {
+                        // modifiers and other keys the keymap marks don't repeat
+                        KeyState::Pressed
+                            if keyboard.repeat_info.rate > 0
+                                && xkb_state.get_keymap().key_repeats(keycode) =>
+                        {
+                            keyboard.repeat = Some(Repeat {
+                                key,
+                                next: Instant::now() + keyboard.repeat_info.delay,
+                            });
+                        }
+                        KeyState::Released
+                            if keyboard.repeat.as_ref().is_some_and(|r| r.key == key) =>
+                        {
+                            keyboard.repeat = None;
+                        }
+                        _ => {}
+                    }
+
+                    if let Some(event) = keyboard.key_event(key, key_state, false) {
+                        state.key(serial, time, event);
+                    }
                 }
 
                 WEnum::Unknown(value) => {
@@ -175,10 +367,18 @@ where
 
                 xkb_state.update_mask(mods_depressed, mods_latched, mods_locked, 0, 0, group);
 
-                let modifiers = Modifiers::new(xkb_state);
+                let modifiers = modifiers(xkb_state);
                 state.modifiers(serial, modifiers, group);
             }
-            Event::RepeatInfo { rate: _, delay: _ } => {}
+            Event::RepeatInfo { rate, delay } => {
+                keyboard.repeat_info = RepeatInfo {
+                    rate,
+                    delay: Duration::from_millis(delay.max(0) as u64),
+                };
+                if rate <= 0 {
+                    keyboard.repeat = None;
+                }
+            }
             _ => unreachable!(),
         }
     }
@@ -1,19 +1,188 @@
 use super::Seat;
-use crate::desktop::{event::Button, wayland::app::AppData};
+use crate::desktop::{
+    cursor::CursorIcon,
+    event::{Button, ScrollSource},
+    wayland::app::{
+        globals::cursor::{self, Cursors},
+        AppData,
+    },
+};
+use std::time::Instant;
 use wayland_client::{
     protocol::{
-        wl_pointer::{self, WlPointer},
+        wl_buffer::WlBuffer,
+        wl_pointer::{self, Axis, AxisRelativeDirection, AxisSource, WlPointer},
+        wl_shm_pool::WlShmPool,
         wl_surface::WlSurface,
     },
-    Dispatch, WEnum,
+    Dispatch, QueueHandle, WEnum,
 };
+use wayland_protocols::wp::cursor_shape::v1::client::wp_cursor_shape_device_v1::WpCursorShapeDeviceV1;
+
+/// Time constant of the exponential slowdown of kinetic scrolls, in ms.
+const KINETIC_TIME_CONSTANT: f64 = 325.0;
+/// Finger speed needed to start a kinetic scroll, in pixels per ms.
+const KINETIC_START_SPEED: f64 = 0.1;
+/// Speed a kinetic scroll ends at, in pixels per ms.
+const KINETIC_END_SPEED: f64 = 0.01;
+/// Pause after which a finger is considered resting, in ms.
+const FINGER_REST_TIME: u32 = 50;
+/// Distance a button can move while pressed and still click, also the
+/// distance between the clicks of a double click, in pixels.
+pub(crate) const CLICK_DISTANCE: f64 = 4.0;
+/// Largest time between the presses of a double click, in ms.
+const MULTI_CLICK_TIME: u32 = 400;
 
 pub struct Pointer {
     wl_pointer: WlPointer,
     focus: Option<WlSurface>,
+    enter_serial: u32,
+    /// Cursor shown over the focused surface and the scale it was shown at,
+    /// `None` until one is set after entering it.
+    cursor: Option<(CursorIcon, i32)>,
+    shape_device: Option<WpCursorShapeDeviceV1>,
     coords: (f64, f64),
     accumulated_events: Vec<wl_pointer::Event>,
-    last_button: Option<Button>,
+    clicks: Clicks,
+    scroll: ScrollFrame,
+    finger: Finger,
+    kinetic: Option<Kinetic>,
+}
+
+/// Axis events of the current frame.
+#[derive(Default)]
+struct ScrollFrame {
+    active: bool,
+    time: u32,
+    delta: (f64, f64),
+    value120: Option<(i32, i32)>,
+    source: Option<ScrollSource>,
+    inverted: bool,
+    stop: Option<u32>,
+}
+
+/// Button press that clicks when released, as click `count` in a row.
+#[derive(Clone, Copy)]
+struct Press {
+    button: Button,
+    time: u32,
+    coords: (f64, f64),
+    count: u32,
+}
+
+/// Presses of the buttons held and the last click, turning releases into
+/// clicks.
+#[derive(Default)]
+struct Clicks {
+    presses: Vec<Press>,
+    last: Option<Press>,
+}
+
+impl Clicks {
+    /// Clicks in a row a press of `button` at `time` and `coords` makes,
+    /// counting the previous clicks when close enough in time and space.
+    fn count(&self, button: Button, time: u32, coords: (f64, f64)) -> u32 {
+        match self.last {
+            Some(last)
+                if last.button == button
+                    && time.wrapping_sub(last.time) <= MULTI_CLICK_TIME
+                    && distance(last.coords, coords) <= CLICK_DISTANCE =>
+            {
+                last.count + 1
+            }
+            _ => 1,
+        }
+    }
+
+    fn press(&mut self, button: Button, time: u32, coords: (f64, f64)) {
+        let count = self.count(button, time, coords);
+        self.presses.retain(|press| press.button != button);
+        self.presses.push(Press {
+            button,
+            time,
+            coords,
+            count,
+        });
+    }
+
+    /// Forgets the presses moved too far from, they're drags, not clicks.
+    fn motion(&mut self, coords: (f64, f64)) {
+        self.presses
+            .retain(|press| distance(press.coords, coords) <= CLICK_DISTANCE);
+    }
+
+    /// Clicks in a row the release of `button` makes, if it clicks.
+    fn release(&mut self, button: Button) -> Option<u32> {
+        let i = self
+            .presses
+            .iter()
+            .position(|press| press.button == button)?;
+        let press = self.presses.remove(i);
+        self.last = Some(press);
+        Some(press.count)
+    }
+}
+
+/// Momentum continuing a finger scroll, slowing down exponentially.
+struct Kinetic {
+    velocity: (f64, f64),
+    inverted: bool,
+    last: Instant,
+}
+
+impl Kinetic {
+    /// Slows down for `dt` ms, returning the distance scrolled meanwhile and
+    /// whether it's still moving.
+    fn advance(&mut self, dt: f64) -> ((f64, f64), bool) {
+        let decay = (-dt / KINETIC_TIME_CONSTANT).exp();
+        let (vx, vy) = self.velocity;
+        // integral of the velocity over the step
+        let dist = KINETIC_TIME_CONSTANT * (1.0 - decay);
+
+        self.velocity = (vx * decay, vy * decay);
+        let moving = vx.hypot(vy) * decay >= KINETIC_END_SPEED;
+        ((vx * dist, vy * dist), moving)
+    }
+}
+
+/// Speed of the fingers scrolling, in pixels per ms.
+#[derive(Default)]
+struct Finger {
+    velocity: (f64, f64),
+    last_time: Option<u32>,
+}
+
+impl Finger {
+    /// Follows the finger speed over a scroll frame, returning the velocity
+    /// of the kinetic scroll to start when the fingers are lifted while
+    /// moving.
+    fn track(&mut self, scroll: &ScrollFrame) -> Option<(f64, f64)> {
+        if let Some(stop) = scroll.stop {
+            let moving = self
+                .last_time
+                .is_some_and(|last| stop.wrapping_sub(last) < FINGER_REST_TIME);
+            let (vx, vy) = self.velocity;
+            let velocity = self.velocity;
+            *self = Finger::default();
+            return (moving && vx.hypot(vy) >= KINETIC_START_SPEED).then_some(velocity);
+        }
+
+        let dt = self
+            .last_time
+            .map(|last| scroll.time.wrapping_sub(last))
+            .unwrap_or(0);
+        if dt > 0 && dt < FINGER_REST_TIME {
+            let (dx, dy) = scroll.delta;
+            let (vx, vy) = self.velocity;
+            let dt = dt as f64;
+            // smoothed, single events are noisy
+            self.velocity = (0.8 * dx / dt + 0.2 * vx, 0.8 * dy / dt + 0.2 * vy);
+        } else {
+            self.velocity = (0.0, 0.0);
+        }
+        self.last_time = Some(scroll.time);
+        None
+    }
 }
 
 impl Pointer {
@@ -21,9 +190,15 @@ impl Pointer {
         Pointer {
             wl_pointer,
             focus: None,
+            enter_serial: 0,
+            cursor: None,
+            shape_device: None,
             coords: (0.0, 0.0),
             accumulated_events: Vec::new(),
-            last_button: None,
+            clicks: Default::default(),
+            scroll: Default::default(),
+            finger: Default::default(),
+            kinetic: None,
         }
     }
 
@@ -34,10 +209,154 @@ impl Pointer {
     pub fn coords(&self) -> (f64, f64) {
         self.coords
     }
+
+    /// Shows `icon` over the focused surface, with the cursor shape protocol
+    /// when the compositor supports it, else from the theme at `scale`.
+    pub fn set_cursor<State, U>(
+        &mut self,
+        icon: CursorIcon,
+        scale: i32,
+        cursors: &mut Cursors,
+        qh: &QueueHandle<State>,
+        data: U,
+    ) where
+        State: Dispatch<WpCursorShapeDeviceV1, U>
+            + Dispatch<WlSurface, U>
+            + Dispatch<WlShmPool, U>
+            + Dispatch<WlBuffer, U>
+            + 'static,
+        U: Send + Sync + Copy + 'static,
+    {
+        if self.focus.is_none() || self.cursor == Some((icon, scale)) {
+            return;
+        }
+
+        match cursors.shape_manager() {
+            Some(manager) => {
+                let device = self
+                    .shape_device
+                    .get_or_insert_with(|| manager.get_pointer(&self.wl_pointer, qh, data));
+                device.set_shape(self.enter_serial, cursor::shape(icon));
+            }
+            None => {
+                let Some((surface, (x, y))) = cursors.themed(icon, scale, qh, data) else {
+                    return;
+                };
+                self.wl_pointer
+                    .set_cursor(self.enter_serial, Some(surface), x, y);
+            }
+        }
+        self.cursor = Some((icon, scale));
+    }
+
+    /// Whether a kinetic scroll is running, [`Pointer::kinetic_step`] has to
+    /// be called regularly until it ends.
+    pub fn is_kinetic(&self) -> bool {
+        self.kinetic.is_some()
+    }
+
+    /// Advances the kinetic scroll, returning the distance scrolled since the
+    /// last step and whether scrolling is inverted. Ends the scroll once it
+    /// slowed down.
+    pub fn kinetic_step(&mut self) -> Option<(f64, f64, bool)> {
+        let kinetic = self.kinetic.as_mut()?;
+        let now = Instant::now();
+        let dt = now.duration_since(kinetic.last).as_secs_f64() * 1000.0;
+        kinetic.last = now;
+
+        let inverted = kinetic.inverted;
+        let ((dx, dy), moving) = kinetic.advance(dt);
+        if !moving {
+            self.kinetic = None;
+        }
+        Some((dx, dy, inverted))
+    }
+
+    /// Applies an event of a frame to the pointer and passes it on.
+    fn apply<State>(state: &mut State, event: wl_pointer::Event)
+    where
+        State: AsMut<Seat> + PointerHandler,
+    {
+        let pointer = state.as_mut().pointer.as_mut().unwrap();
+
+        use wl_pointer::Event;
+        match event {
+            Event::Enter {
+                serial,
+                surface,
+                surface_x,
+                surface_y,
+            } => {
+                pointer.focus = Some(surface.clone());
+                pointer.enter_serial = serial;
+                // the cursor is undefined until set again
+                pointer.cursor = None;
+                pointer.coords = (surface_x, surface_y);
+                state.enter(serial, surface, surface_x, surface_y);
+            }
+            Event::Leave { serial, surface } => {
+                pointer.focus = None;
+                pointer.cursor = None;
+                pointer.clicks.presses.clear();
+                pointer.kinetic = None;
+                state.leave(serial, surface);
+            }
+            Event::Motion {
+                time,
+                surface_x,
+                surface_y,
+            } => {
+                pointer.coords = (surface_x, surface_y);
+                pointer.clicks.motion(pointer.coords);
+                state.motion(time, surface_x, surface_y);
+            }
+            Event::Button {
+                serial,
+                time,
+                button,
+                state: button_state,
+            } => {
+                let button = button_from_code(button);
+                match button_state {
+                    WEnum::Value(wl_pointer::ButtonState::Pressed) => {
+                        pointer.clicks.press(button, time, pointer.coords);
+                        state.button(serial, time, ButtonState::Pressed, button);
+                    }
+                    WEnum::Value(wl_pointer::ButtonState::Released) => {
+                        let count = pointer.clicks.release(button);
+                        state.button(serial, time, ButtonState::Released, button);
+                        if let Some(count) = count {
+                            state.button(serial, time, ButtonState::Clicked(count), button);
+                        }
+                    }
+                    WEnum::Value(_) => {}
+                    WEnum::Unknown(value) => {
+                        println!("wl_pointer: unknown button state: {value}")
+                    }
+                }
+            }
+            _ => {}
+        }
+    }
+
+    /// Follows the finger speed, starting a kinetic scroll when the fingers
+    /// are lifted while moving.
+    fn track_finger(&mut self, scroll: &ScrollFrame) {
+        if let Some(velocity) = self.finger.track(scroll) {
+            self.kinetic = Some(Kinetic {
+                velocity,
+                inverted: scroll.inverted,
+                last: Instant::now(),
+            });
+        }
+    }
 }
 
 impl Drop for Pointer {
     fn drop(&mut self) {
+        if let Some(device) = self.shape_device.as_ref() {
+            device.destroy();
+        }
         self.wl_pointer.release();
     }
 }
@@ -46,7 +365,8 @@ impl Drop for Pointer {
 pub enum ButtonState {
     Pressed,
     Released,
-    Clicked,
+    /// Released after a press in place, with the number of clicks in a row.
+    Clicked(u32),
 }
 
 pub trait PointerHandler {
@@ -54,6 +374,15 @@ pub trait PointerHandler {
     fn leave(&mut self, serial: u32, surface: WlSurface);
     fn motion(&mut self, time: u32, x: f64, y: f64);
     fn button(&mut self, serial: u32, time: u32, state: ButtonState, button: Button);
+    fn scroll(
+        &mut self,
+        time: u32,
+        dx: f64,
+        dy: f64,
+        source: ScrollSource,
+        discrete: Option<(f64, f64)>,
+        inverted: bool,
+    );
 }
 
 impl<State> Dispatch<WlPointer, AppData, State> for Pointer
@@ -74,144 +403,347 @@ where
 
         use wl_pointer::Event;
         match event {
-            Event::Enter {
-                serial,
-                surface,
-                surface_x,
-                surface_y,
-            } => {
-                pointer.focus = Some(surface.clone());
-                pointer.coords = (surface_x, surface_y);
-                pointer.accumulated_events.push(wl_pointer::Event::Enter {
-                    serial,
-                    surface,
-                    surface_x,
-                    surface_y,
-                });
-            }
-            Event::Leave { serial, surface } => {
-                pointer.focus = None;
-                pointer
-                    .accumulated_events
-                    .push(wl_pointer::Event::Leave { serial, surface });
-            }
-            Event::Motion {
-                time,
-                surface_x,
-                surface_y,
-            } => {
-                pointer.coords = (surface_x, surface_y);
-                pointer.accumulated_events.push(wl_pointer::Event::Motion {
-                    time,
-                    surface_x,
-                    surface_y,
-                });
+            // applied in order once the frame is complete
+            Event::Enter { .. } | Event::Leave { .. } | Event::Motion { .. } => {
+                pointer.accumulated_events.push(event);
             }
-            Event::Button {
-                serial,
-                time,
-                button,
-                state,
-            } => {
-                pointer.accumulated_events.push(wl_pointer::Event::Button {
-                    serial,
-                    time,
-                    button,
-                    state,
-                });
+            Event::Button { .. } => {
+                pointer.kinetic = None;
+                pointer.accumulated_events.push(event);
             }
             Event::Frame => {
-                if pointer.accumulated_events.len() != 1 {
-                    pointer.accumulated_events.clear();
-                    return;
+                let events = std::mem::take(&mut pointer.accumulated_events);
+                for event in events {
+                    Pointer::apply(state, event);
                 }
 
-                match pointer.accumulated_events[0] {
-                    Event::Button {
-                        serial: _,
-                        time: _,
-                        button: _,
-                        state: _,
-                    } => (),
-                    _ => pointer.last_button = None,
-                };
+                let pointer = state.as_mut().pointer.as_mut().unwrap();
+                let scroll = std::mem::take(&mut pointer.scroll);
+                if scroll.active {
+                    let discrete = scroll
+                        .value120
+                        .map(|(x, y)| (x as f64 / 120.0, y as f64 / 120.0));
+                    let source = scroll.source.unwrap_or(match discrete {
+                        Some(_) => ScrollSource::Wheel,
+                        None => ScrollSource::Continuous,
+                    });
+                    if source == ScrollSource::Finger {
+                        pointer.track_finger(&scroll);
+                    }
 
-                match pointer.accumulated_events.pop().unwrap() {
-                    Event::Enter {
-                        serial,
-                        surface,
-                        surface_x,
-                        surface_y,
-                    } => state.enter(serial, surface, surface_x, surface_y),
-                    Event::Leave { serial, surface } => state.leave(serial, surface),
-                    Event::Motion {
-                        time,
-                        surface_x,
-                        surface_y,
-                    } => state.motion(time, surface_x, surface_y),
-                    Event::Button {
-                        serial,
-                        time,
-                        button,
-                        state: button_state,
-                    } => match button_state {
-                        WEnum::Value(button_state) => {
-                            let button = match button {
-                                272 => Button::Left,
-                                273 => Button::Right,
-                                274 => Button::Middle,
-                                _ => return,
-                            };
-
-                            let button_state = match button_state {
-                                wl_pointer::ButtonState::Pressed => ButtonState::Pressed,
-                                wl_pointer::ButtonState::Released => ButtonState::Released,
-                                _ => return,
-                            };
-
-                            let last = match button_state {
-                                ButtonState::Pressed => {
-                                    pointer.last_button = Some(button);
-                                    None
-                                }
-                                ButtonState::Released => pointer.last_button.take(),
-                                _ => None,
-                            };
-
-                            state.button(serial, time, button_state, button);
-                            if let Some(last) = last {
-                                if last == button {
-                                    state.button(serial, time, ButtonState::Clicked, button);
-                                }
-                            }
-                        }
-                        WEnum::Unknown(value) => {
-                            println!("wl_pointer: unknown button state: {value}")
-                        }
-                    },
-                    _ => (),
+                    let (dx, dy) = scroll.delta;
+                    if dx != 0.0 || dy != 0.0 || discrete.is_some() {
+                        state.scroll(scroll.time, dx, dy, source, discrete, scroll.inverted);
+                    }
+                }
+            }
+            Event::Axis { time, axis, value } => {
+                pointer.kinetic = None;
+                pointer.scroll.active = true;
+                pointer.scroll.time = time;
+                match axis {
+                    WEnum::Value(Axis::HorizontalScroll) => pointer.scroll.delta.0 += value,
+                    WEnum::Value(Axis::VerticalScroll) => pointer.scroll.delta.1 += value,
+                    _ => {}
+                }
+            }
+            Event::AxisSource { axis_source } => {
+                pointer.scroll.source = match axis_source {
+                    WEnum::Value(AxisSource::Wheel) => Some(ScrollSource::Wheel),
+                    WEnum::Value(AxisSource::Finger) => Some(ScrollSource::Finger),
+                    WEnum::Value(AxisSource::Continuous) => Some(ScrollSource::Continuous),
+                    WEnum::Value(AxisSource::WheelTilt) => Some(ScrollSource::WheelTilt),
+                    _ => None,
+                };
+            }
+            Event::AxisStop { time, axis: _ } => {
+                pointer.scroll.active = true;
+                pointer.scroll.stop = Some(time);
+            }
+            // sent before version 8 only, in whole notches
+            Event::AxisDiscrete { axis, discrete } => {
+                pointer.scroll.active = true;
+                let value120 = pointer.scroll.value120.get_or_insert((0, 0));
+                match axis {
+                    WEnum::Value(Axis::HorizontalScroll) => value120.0 += discrete * 120,
+                    WEnum::Value(Axis::VerticalScroll) => value120.1 += discrete * 120,
+                    _ => {}
+                }
+            }
+            Event::AxisValue120 { axis, value120 } => {
+                pointer.scroll.active = true;
+                let sum = pointer.scroll.value120.get_or_insert((0, 0));
+                match axis {
+                    WEnum::Value(Axis::HorizontalScroll) => sum.0 += value120,
+                    WEnum::Value(Axis::VerticalScroll) => sum.1 += value120,
+                    _ => {}
+                }
+            }
+            Event::AxisRelativeDirection { axis, direction } => {
+                if axis == WEnum::Value(Axis::VerticalScroll) {
+                    pointer.scroll.inverted =
+                        direction == WEnum::Value(AxisRelativeDirection::Inverted);
                 }
             }
-            Event::Axis {
-                time: _,
-                axis: _,
-                value: _,
-            } => {}
-            Event::AxisSource { axis_source: _ } => {}
-            Event::AxisStop { time: _, axis: _ } => {}
-            Event::AxisDiscrete {
-                axis: _,
-                discrete: _,
-            } => {}
-            Event::AxisValue120 {
-                axis: _,
-                value120: _,
-            } => {}
-            Event::AxisRelativeDirection {
-                axis: _,
-                direction: _,
-            } => {}
             _ => unreachable!(),
         }
     }
 }
+
+fn distance((x1, y1): (f64, f64), (x2, y2): (f64, f64)) -> f64 {
+    (x2 - x1).hypot(y2 - y1)
+}
+
+/// Button of a Linux input event code.
+fn button_from_code(code: u32) -> Button {
+    match code {
+        0x110 => Button::Left,
+        0x111 => Button::Right,
+        0x112 => Button::Middle,
+        0x113 => Button::Side,
+        0x114 => Button::Extra,
+        0x115 => Button::Forward,
+        0x116 => Button::Back,
+        code => Button::Other(code),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Presses and releases `button` at `time` and `coords`, returning the
+    /// click count.
+    fn click(clicks: &mut Clicks, button: Button, time: u32, coords: (f64, f64)) -> Option<u32> {
+        clicks.press(button, time, coords);
+        clicks.release(button)
+    }
+
+    #[test]
+    fn double_and_triple_click() {
+        let mut clicks = Clicks::default();
+        assert_eq!(
+            click(&mut clicks, Button::Left, 1000, (10.0, 10.0)),
+            Some(1)
+        );
+        assert_eq!(
+            click(&mut clicks, Button::Left, 1200, (12.0, 10.0)),
+            Some(2)
+        );
+        assert_eq!(
+            click(
+                &mut clicks,
+                Button::Left,
+                1200 + MULTI_CLICK_TIME,
+                (12.0, 12.0)
+            ),
+            Some(3)
+        );
+    }
+
+    #[test]
+    fn click_count_resets_after_time() {
+        let mut clicks = Clicks::default();
+        click(&mut clicks, Button::Left, 1000, (10.0, 10.0));
+        assert_eq!(
+            click(
+                &mut clicks,
+                Button::Left,
+                1001 + MULTI_CLICK_TIME,
+                (10.0, 10.0)
+            ),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn click_count_resets_on_distance() {
+        let mut clicks = Clicks::default();
+        click(&mut clicks, Button::Left, 1000, (10.0, 10.0));
+        assert_eq!(
+            click(
+                &mut clicks,
+                Button::Left,
+                1100,
+                (10.0, 11.0 + CLICK_DISTANCE)
+            ),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn click_count_resets_on_other_button() {
+        let mut clicks = Clicks::default();
+        click(&mut clicks, Button::Left, 1000, (10.0, 10.0));
+        assert_eq!(
+            click(&mut clicks, Button::Right, 1100, (10.0, 10.0)),
+            Some(1)
+        );
+        // the right click is the last one now
+        assert_eq!(
+            click(&mut clicks, Button::Left, 1200, (10.0, 10.0)),
+            Some(1)
+        );
+    }
+
+    #[test]
+    fn click_count_time_wraps() {
+        let mut clicks = Clicks::default();
+        click(&mut clicks, Button::Left, u32::MAX - 100, (10.0, 10.0));
+        assert_eq!(click(&mut clicks, Button::Left, 100, (10.0, 10.0)), Some(2));
+    }
+
+    #[test]
+    fn drag_does_not_click() {
+        let mut clicks = Clicks::default();
+        clicks.press(Button::Left, 1000, (10.0, 10.0));
+        clicks.motion((10.0 + CLICK_DISTANCE, 10.0));
+        clicks.motion((11.0 + CLICK_DISTANCE, 10.0));
+        assert_eq!(clicks.release(Button::Left), None);
+    }
+
+    #[test]
+    fn small_motion_still_clicks() {
+        let mut clicks = Clicks::default();
+        clicks.press(Button::Left, 1000, (10.0, 10.0));
+        clicks.motion((10.0 + CLICK_DISTANCE, 10.0));
+        assert_eq!(clicks.release(Button::Left), Some(1));
+    }
+
+    #[test]
+    fn drag_forgets_only_moved_away_presses() {
+        let mut clicks = Clicks::default();
+        clicks.press(Button::Left, 1000, (10.0, 10.0));
+        clicks.press(Button::Right, 1000, (30.0, 10.0));
+        clicks.motion((30.0, 10.0));
+        assert_eq!(clicks.release(Button::Left), None);
+        assert_eq!(clicks.release(Button::Right), Some(1));
+    }
+
+    fn kinetic(velocity: (f64, f64)) -> Kinetic {
+        Kinetic {
+            velocity,
+            inverted: false,
+            last: Instant::now(),
+        }
+    }
+
+    fn finger_frame(time: u32, delta: (f64, f64)) -> ScrollFrame {
+        ScrollFrame {
+            active: true,
+            time,
+            delta,
+            source: Some(ScrollSource::Finger),
+            ..Default::default()
+        }
+    }
+
+    fn lift(time: u32) -> ScrollFrame {
+        ScrollFrame {
+            stop: Some(time),
+            ..finger_frame(time, (0.0, 0.0))
+        }
+    }
+
+    #[test]
+    fn kinetic_step_is_velocity_integral() {
+        let mut kinetic = kinetic((2.0, -1.0));
+        let ((dx, dy), moving) = kinetic.advance(KINETIC_TIME_CONSTANT);
+
+        let dist = KINETIC_TIME_CONSTANT * (1.0 - (-1.0f64).exp());
+        assert!(moving);
+        assert!((dx - 2.0 * dist).abs() < 1e-9);
+        assert!((dy + dist).abs() < 1e-9);
+    }
+
+    #[test]
+    fn kinetic_velocity_decays() {
+        let mut kinetic = kinetic((1.0, 0.0));
+        let (first, _) = kinetic.advance(16.0);
+        assert!((kinetic.velocity.0 - (-16.0 / KINETIC_TIME_CONSTANT).exp()).abs() < 1e-9);
+
+        let (second, _) = kinetic.advance(16.0);
+        assert!(second.0 < first.0);
+        assert_eq!(kinetic.velocity.1, 0.0);
+    }
+
+    #[test]
+    fn kinetic_steps_add_up_independent_of_frame_rate() {
+        let mut fast = kinetic((1.0, 0.0));
+        let mut slow = kinetic((1.0, 0.0));
+        let fast_dist: f64 = (0..4).map(|_| fast.advance(8.0).0 .0).sum();
+        let slow_dist = slow.advance(32.0).0 .0;
+
+        assert!((fast_dist - slow_dist).abs() < 1e-9);
+    }
+
+    #[test]
+    fn kinetic_stops_below_end_speed() {
+        let mut kinetic = kinetic((KINETIC_START_SPEED, 0.0));
+        // time for the start speed to decay to the end speed
+        let end = KINETIC_TIME_CONSTANT * (KINETIC_START_SPEED / KINETIC_END_SPEED).ln();
+
+        assert!(kinetic.advance(end - 1.0).1);
+        assert!(!kinetic.advance(2.0).1);
+    }
+
+    #[test]
+    fn finger_velocity_follows_deltas() {
+        let mut finger = Finger::default();
+        assert_eq!(finger.track(&finger_frame(100, (0.0, 10.0))), None);
+        // first event has no speed to measure
+        assert_eq!(finger.velocity, (0.0, 0.0));
+
+        finger.track(&finger_frame(110, (0.0, 10.0)));
+        assert!((finger.velocity.1 - 0.8).abs() < 1e-9);
+
+        finger.track(&finger_frame(120, (0.0, 10.0)));
+        assert!((finger.velocity.1 - 0.96).abs() < 1e-9);
+    }
+
+    #[test]
+    fn finger_lift_while_moving_starts_kinetic() {
+        let mut finger = Finger::default();
+        finger.track(&finger_frame(100, (5.0, 0.0)));
+        finger.track(&finger_frame(110, (5.0, 0.0)));
+
+        let velocity = finger.track(&lift(120)).unwrap();
+        assert!((velocity.0 - 0.4).abs() < 1e-9);
+        // reset for the next scroll
+        assert_eq!(finger.velocity, (0.0, 0.0));
+        assert_eq!(finger.last_time, None);
+    }
+
+    #[test]
+    fn finger_rest_resets_velocity() {
+        let mut finger = Finger::default();
+        finger.track(&finger_frame(100, (5.0, 0.0)));
+        finger.track(&finger_frame(110, (5.0, 0.0)));
+        finger.track(&finger_frame(110 + FINGER_REST_TIME, (5.0, 0.0)));
+        assert_eq!(finger.velocity, (0.0, 0.0));
+    }
+
+    #[test]
+    fn finger_lift_after_rest_stops() {
+        let mut finger = Finger::default();
+        finger.track(&finger_frame(100, (5.0, 0.0)));
+        finger.track(&finger_frame(110, (5.0, 0.0)));
+        assert_eq!(finger.track(&lift(110 + FINGER_REST_TIME)), None);
+    }
+
+    #[test]
+    fn finger_lift_when_slow_stops() {
+        let mut finger = Finger::default();
+        finger.track(&finger_frame(100, (0.1, 0.0)));
+        finger.track(&finger_frame(110, (0.1, 0.0)));
+        assert_eq!(finger.track(&lift(115)), None);
+    }
+
+    #[test]
+    fn finger_time_wraps() {
+        let mut finger = Finger::default();
+        finger.track(&finger_frame(u32::MAX - 4, (5.0, 0.0)));
+        finger.track(&finger_frame(5, (5.0, 0.0)));
+        assert!((finger.velocity.0 - 0.4).abs() < 1e-9);
+    }
+}
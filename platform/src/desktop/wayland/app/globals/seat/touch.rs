@@ -0,0 +1,165 @@
+use super::Seat;
+use crate::desktop::wayland::app::AppData;
+use wayland_client::{
+    protocol::{
+        wl_surface::WlSurface,
+        wl_touch::{self, WlTouch},
+    },
+    Dispatch,
+};
+
+pub struct Touch {
+    wl_touch: WlTouch,
+    /// Points down, in the order they touched.
+    points: Vec<TouchPoint>,
+    accumulated_events: Vec<wl_touch::Event>,
+}
+
+/// Finger on the touchscreen, `id` tells it apart from the other fingers
+/// until it's lifted.
+struct TouchPoint {
+    id: i32,
+    surface: WlSurface,
+    coords: (f64, f64),
+}
+
+impl Touch {
+    pub fn new(wl_touch: WlTouch) -> Touch {
+        Touch {
+            wl_touch,
+            points: Vec::new(),
+            accumulated_events: Vec::new(),
+        }
+    }
+
+    /// Surface and coordinates of the point `id`.
+    pub fn point(&self, id: i32) -> Option<(WlSurface, (f64, f64))> {
+        self.points
+            .iter()
+            .find(|point| point.id == id)
+            .map(|point| (point.surface.clone(), point.coords))
+    }
+
+    /// Applies an event of a frame to the points and passes it on.
+    fn apply<State>(state: &mut State, event: wl_touch::Event)
+    where
+        State: AsMut<Seat> + TouchHandler,
+    {
+        let touch = state.as_mut().touch.as_mut().unwrap();
+
+        use wl_touch::Event;
+        match event {
+            Event::Down {
+                serial,
+                time,
+                surface,
+                id,
+                x,
+                y,
+            } => {
+                touch.points.retain(|point| point.id != id);
+                touch.points.push(TouchPoint {
+                    id,
+                    surface: surface.clone(),
+                    coords: (x, y),
+                });
+                state.down(serial, time, surface, id, x, y);
+            }
+            Event::Up { serial, time, id } => {
+                let Some(i) = touch.points.iter().position(|point| point.id == id) else {
+                    return;
+                };
+                let point = touch.points.remove(i);
+                let (x, y) = point.coords;
+                state.up(serial, time, point.surface, id, x, y);
+            }
+            Event::Motion { time, id, x, y } => {
+                let Some(point) = touch.points.iter_mut().find(|point| point.id == id) else {
+                    return;
+                };
+                point.coords = (x, y);
+                let surface = point.surface.clone();
+                state.motion(time, surface, id, x, y);
+            }
+            Event::Shape { id, major, minor } => {
+                let Some((surface, (x, y))) = touch.point(id) else {
+                    return;
+                };
+                state.shape(surface, id, x, y, major, minor);
+            }
+            Event::Orientation { id, orientation } => {
+                let Some((surface, (x, y))) = touch.point(id) else {
+                    return;
+                };
+                state.orientation(surface, id, x, y, orientation);
+            }
+            _ => {}
+        }
+    }
+}
+
+impl Drop for Touch {
+    fn drop(&mut self) {
+        self.wl_touch.release();
+    }
+}
+
+pub trait TouchHandler {
+    fn down(&mut self, serial: u32, time: u32, surface: WlSurface, id: i32, x: f64, y: f64);
+    fn up(&mut self, serial: u32, time: u32, surface: WlSurface, id: i32, x: f64, y: f64);
+    fn motion(&mut self, time: u32, surface: WlSurface, id: i32, x: f64, y: f64);
+    /// Called for each point when the compositor took the touch sequence
+    /// over, the points are gone without being lifted.
+    fn cancel(&mut self, surface: WlSurface, id: i32, x: f64, y: f64);
+    /// Called when the contact area of a point, an ellipse of `major` and
+    /// `minor` axes, changed.
+    fn shape(&mut self, surface: WlSurface, id: i32, x: f64, y: f64, major: f64, minor: f64);
+    /// Called when the major axis of the contact area of a point turned,
+    /// `orientation` is its angle clockwise from the vertical, in degrees.
+    fn orientation(&mut self, surface: WlSurface, id: i32, x: f64, y: f64, orientation: f64);
+}
+
+impl<State> Dispatch<WlTouch, AppData, State> for Touch
+where
+    State: Dispatch<WlTouch, AppData>,
+    State: AsMut<Seat>,
+    State: TouchHandler,
+{
+    fn event(
+        state: &mut State,
+        _proxy: &WlTouch,
+        event: <WlTouch as wayland_client::Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &wayland_client::QueueHandle<State>,
+    ) {
+        let touch = state.as_mut().touch.as_mut().unwrap();
+
+        use wl_touch::Event;
+        match event {
+            // applied in order once the frame is complete
+            Event::Down { .. }
+            | Event::Up { .. }
+            | Event::Motion { .. }
+            | Event::Shape { .. }
+            | Event::Orientation { .. } => {
+                touch.accumulated_events.push(event);
+            }
+            Event::Frame => {
+                let events = std::mem::take(&mut touch.accumulated_events);
+                for event in events {
+                    Touch::apply(state, event);
+                }
+            }
+            Event::Cancel => {
+                touch.accumulated_events.clear();
+                let points = std::mem::take(&mut touch.points);
+                for point in points {
+                    let (x, y) = point.coords;
+                    state.cancel(point.surface, point.id, x, y);
+                }
+            }
+            _ => unreachable!(),
+        }
+    }
+}
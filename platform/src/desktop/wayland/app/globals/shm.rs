@@ -0,0 +1,121 @@
+use crate::desktop::wayland::app::AppData;
+use std::{
+    ffi::{c_char, c_int, c_uint},
+    fs::File,
+    io::{self, Write},
+    os::fd::{AsFd, FromRawFd},
+};
+use wayland_client::{
+    protocol::{
+        wl_buffer::WlBuffer,
+        wl_shm::{self, WlShm},
+        wl_shm_pool::WlShmPool,
+    },
+    Dispatch, QueueHandle,
+};
+
+const MFD_CLOEXEC: c_uint = 0x1;
+
+extern "C" {
+    fn memfd_create(name: *const c_char, flags: c_uint) -> c_int;
+}
+
+/// Shared memory buffers, for the images drawn without EGL.
+pub struct Shm;
+
+/// Buffer of `width` by `height` premultiplied ARGB pixels, as native
+/// endian words.
+pub fn buffer<State, U>(
+    shm: &WlShm,
+    width: i32,
+    height: i32,
+    pixels: &[u8],
+    qh: &QueueHandle<State>,
+    data: U,
+) -> io::Result<WlBuffer>
+where
+    State: Dispatch<WlShmPool, U> + Dispatch<WlBuffer, U> + 'static,
+    U: Send + Sync + Copy + 'static,
+{
+    let fd = unsafe { memfd_create(c"dagt-shm".as_ptr(), MFD_CLOEXEC) };
+    if fd < 0 {
+        return Err(io::Error::last_os_error());
+    }
+    let mut file = unsafe { File::from_raw_fd(fd) };
+    file.write_all(pixels)?;
+
+    let pool = shm.create_pool(file.as_fd(), pixels.len() as i32, qh, data);
+    let buffer = pool.create_buffer(
+        0,
+        width,
+        height,
+        width * 4,
+        wl_shm::Format::Argb8888,
+        qh,
+        data,
+    );
+    // the buffer keeps the memory alive
+    pool.destroy();
+
+    Ok(buffer)
+}
+
+/// RGBA pixels as premultiplied ARGB, in native order.
+pub fn argb(rgba: &[u8]) -> Vec<u8> {
+    rgba.chunks_exact(4)
+        .flat_map(|p| {
+            let alpha = p[3] as u32;
+            let premultiply = |c: u8| (c as u32 * alpha + 127) / 255;
+            let pixel =
+                alpha << 24 | premultiply(p[0]) << 16 | premultiply(p[1]) << 8 | premultiply(p[2]);
+            pixel.to_ne_bytes()
+        })
+        .collect()
+}
+
+impl<State> Dispatch<WlShm, AppData, State> for Shm
+where
+    State: Dispatch<WlShm, AppData>,
+{
+    fn event(
+        _state: &mut State,
+        _proxy: &WlShm,
+        _event: <WlShm as wayland_client::Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+        // argb8888 is always supported
+    }
+}
+
+impl<State> Dispatch<WlShmPool, AppData, State> for Shm
+where
+    State: Dispatch<WlShmPool, AppData>,
+{
+    fn event(
+        _state: &mut State,
+        _proxy: &WlShmPool,
+        _event: <WlShmPool as wayland_client::Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+    }
+}
+
+impl<State> Dispatch<WlBuffer, AppData, State> for Shm
+where
+    State: Dispatch<WlBuffer, AppData>,
+{
+    fn event(
+        _state: &mut State,
+        _proxy: &WlBuffer,
+        _event: <WlBuffer as wayland_client::Proxy>::Event,
+        _data: &AppData,
+        _conn: &wayland_client::Connection,
+        _qhandle: &QueueHandle<State>,
+    ) {
+        // buffers are never written again, releases don't matter
+    }
+}
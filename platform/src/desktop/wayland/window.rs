@@ -1,20 +1,42 @@
-use crate::desktop::event::GlobalEvent;
+use super::app::{globals::shm, AppData, AppState};
+use crate::desktop::{
+    clipboard::{ClipboardRequest, Selection, TEXT_MIME_TYPES},
+    damage::{self, Damage},
+    drag::{DndAction, DragIcon},
+    event::GlobalEvent,
+    overlay,
+    window::Window as WindowBuilder,
+};
 use dagt_core::interface::{
     draw::{Constraints, Draw},
     event::EventHandler,
     widget::{Widget, WidgetBuilder},
     window::Window,
 };
-use dagt_gl::egl::{context::EglContext, display::EglDisplay, surface::EglSurface};
+use dagt_gl::egl::{context::EglContext, surface::EglSurface};
 use dagt_gl::gles::{
-    func as gl,
+    debug::GlDebugGroup,
+    func as gl, profiler,
     types::{GlBlendFact, GlCap},
 };
-use dagt_primitives::{color::Color, rect::Rect};
-use std::sync::Mutex;
-use wayland_client::protocol::wl_surface::WlSurface;
+use dagt_primitives::{
+    backend::{self, software::SoftwareBackend, Backend},
+    color::Color,
+    rect::Rect,
+};
+use std::{rc::Rc, sync::Mutex};
+use wayland_client::{
+    protocol::{wl_buffer::WlBuffer, wl_output::WlOutput, wl_shm::WlShm, wl_surface::WlSurface},
+    QueueHandle,
+};
 use wayland_egl::WlEglSurface;
-use wayland_protocols::xdg::shell::client::{xdg_surface::XdgSurface, xdg_toplevel::XdgToplevel};
+use wayland_protocols::{
+    wp::{
+        fractional_scale::v1::client::wp_fractional_scale_v1::WpFractionalScaleV1,
+        viewporter::client::wp_viewport::WpViewport,
+    },
+    xdg::shell::client::{xdg_surface::XdgSurface, xdg_toplevel::XdgToplevel},
+};
 
 pub struct WaylandWindow {
     pub(super) cur_conf: WindowConfigure,
@@ -23,12 +45,161 @@ pub struct WaylandWindow {
     pub(super) state: Mutex<WindowState>,
     pub(super) widget_builder: Option<Box<dyn WidgetBuilder<GlobalEvent>>>,
     pub(super) widget: Option<Box<dyn Widget<GlobalEvent>>>,
+    pub(super) damage: Damage,
     pub(super) frame_requested: bool,
     pub(super) close_requested: bool,
+    pub(super) open_requested: Vec<WindowBuilder>,
+    pub(super) clipboard_requests: Vec<ClipboardRequest>,
+    pub(super) clipboard_mime_types: Vec<String>,
+    pub(super) primary_mime_types: Vec<String>,
+    /// Outputs showing the window.
+    pub(super) outputs: Vec<WlOutput>,
+    /// Integer scale the compositor prefers for the buffers.
+    pub(super) buffer_scale: Option<i32>,
+    /// Scale the compositor prefers, when it supports fractional scales.
+    pub(super) fractional_scale: Option<f64>,
+    pub(super) scale: f64,
+}
+
+impl WaylandWindow {
+    /// Opens another window once the current events are handled.
+    pub fn open(&mut self, window: WindowBuilder) {
+        self.open_requested.push(window);
+    }
+
+    /// Offers `mime_types` on `selection`. `data` makes the contents in one of
+    /// the types when another client pastes them, it's called again for each
+    /// paste.
+    pub fn set_clipboard(
+        &mut self,
+        selection: Selection,
+        mime_types: &[&str],
+        data: impl Fn(&str) -> Vec<u8> + 'static,
+    ) {
+        self.clipboard_requests.push(ClipboardRequest::Set {
+            selection,
+            mime_types: mime_types.iter().map(|t| t.to_string()).collect(),
+            data: Rc::new(data),
+        });
+    }
+
+    pub fn set_clipboard_text(&mut self, selection: Selection, text: &str) {
+        let text = text.to_owned();
+        self.set_clipboard(selection, TEXT_MIME_TYPES, move |_| {
+            text.clone().into_bytes()
+        });
+    }
+
+    /// MIME types offered on `selection`, by this app or another client.
+    pub fn clipboard_mime_types(&self, selection: Selection) -> &[String] {
+        match selection {
+            Selection::Clipboard => &self.clipboard_mime_types,
+            Selection::Primary => &self.primary_mime_types,
+        }
+    }
+
+    /// Requests the contents of `selection` as `mime_type`, they're sent to
+    /// the window in a [`GlobalEvent::ClipboardData`] event once read.
+    pub fn read_clipboard(&mut self, selection: Selection, mime_type: &str) {
+        self.clipboard_requests.push(ClipboardRequest::Read {
+            selection,
+            mime_type: mime_type.to_owned(),
+        });
+    }
+
+    /// Requests the contents of `selection` as text, in the preferred text
+    /// type offered. Returns `false` when no text is offered.
+    pub fn read_clipboard_text(&mut self, selection: Selection) -> bool {
+        let offered = self.clipboard_mime_types(selection);
+        let Some(mime_type) = TEXT_MIME_TYPES
+            .iter()
+            .find(|t| offered.iter().any(|o| o == *t))
+        else {
+            return false;
+        };
+        self.read_clipboard(selection, mime_type);
+        true
+    }
+
+    /// Starts dragging `mime_types` with the pointer button just pressed,
+    /// showing `icon` under the pointer. `data` makes the contents when
+    /// dropped, the window gets a [`DragEvent::SourceFinished`] or
+    /// [`DragEvent::SourceCancelled`] event when the drag ends.
+    ///
+    /// [`DragEvent::SourceFinished`]: crate::desktop::event::DragEvent::SourceFinished
+    /// [`DragEvent::SourceCancelled`]: crate::desktop::event::DragEvent::SourceCancelled
+    pub fn start_drag(
+        &mut self,
+        mime_types: &[&str],
+        actions: &[DndAction],
+        icon: Option<DragIcon>,
+        data: impl Fn(&str) -> Vec<u8> + 'static,
+    ) {
+        self.clipboard_requests.push(ClipboardRequest::Drag {
+            mime_types: mime_types.iter().map(|t| t.to_string()).collect(),
+            actions: actions.to_vec(),
+            icon,
+            data: Rc::new(data),
+        });
+    }
+
+    /// Physical pixels per logical pixel the window is drawn with.
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    /// Draws at the scale [`choose_scale`] picks.
+    pub(super) fn update_scale(&mut self, output_scale: impl Fn(&WlOutput) -> i32) {
+        let viewport = self.state.lock().unwrap().viewport.is_some();
+        let scale = choose_scale(
+            self.fractional_scale,
+            self.buffer_scale,
+            self.outputs.iter().map(output_scale).max(),
+            viewport,
+        );
+        if scale != self.scale {
+            self.handle_event(&GlobalEvent::ScaleChanged(scale));
+        }
+    }
+
+    /// Sizes the EGL window to the configured size in physical pixels, and
+    /// tells the compositor how its buffers map to the surface. Shared memory
+    /// buffers are sized when drawn.
+    fn resize_surface(&self) {
+        let constr = self.cur_conf.constraints;
+        let physical = constr.to_physical(self.scale);
+        let state = self.state.lock().expect("failed to get window state");
+        match state.viewport.as_ref() {
+            // buffers are scaled down to the surface size
+            Some(viewport) => viewport.set_destination(constr.width, constr.height),
+            // whole scale without a viewport
+            None => state.wl_surface.set_buffer_scale(self.scale.ceil() as i32),
+        }
+        if let WindowSurface::Egl { window, .. } = &state.surface {
+            window.resize(physical.width, physical.height, 0, 0);
+        }
+    }
+
+    /// Makes `context` current with the window's surface, all windows draw
+    /// with the app's context.
+    pub(super) fn make_current(&self, context: &EglContext) {
+        let state = self.state.lock().expect("failed to get window state");
+        if let WindowSurface::Egl { surface, .. } = &state.surface {
+            context
+                .make_current(surface, surface)
+                .expect("failed to make EGL context current");
+        }
+    }
 }
 
 impl Window<GlobalEvent> for WaylandWindow {
     fn frame(&mut self) {
+        self.damage.add_full();
+        self.frame_requested = true;
+    }
+
+    fn damage(&mut self, area: Constraints) {
+        self.damage.add(area);
         self.frame_requested = true;
     }
 
@@ -52,13 +223,17 @@ impl EventHandler<GlobalEvent> for WaylandWindow {
         match event {
             Configure => {
                 self.cur_conf = self.pend_conf;
-                self.state
-                    .lock()
-                    .map(|state| {
-                        let constr = &self.cur_conf.constraints;
-                        state.window.resize(constr.width, constr.height, 0, 0);
-                    })
-                    .expect("failed to get window state");
+                self.resize_surface();
+                self.frame();
+                return true;
+            }
+            ScaleChanged(scale) => {
+                self.scale = *scale;
+                self.resize_surface();
+                // text is laid out again at the new scale
+                if let Some(builder) = self.widget_builder.as_ref() {
+                    self.widget = Some(builder.build());
+                }
                 self.frame();
                 return true;
             }
@@ -73,9 +248,15 @@ impl EventHandler<GlobalEvent> for WaylandWindow {
             }
         };
 
+        if let Some(area) = damage::take() {
+            self.damage(area);
+        }
+
+        // the whole widget tree is replaced
         self.widget_builder.as_ref().map(|builder| {
             if builder.state_changed() {
                 self.widget = Some(builder.build());
+                self.damage.add_full();
                 self.frame_requested = true;
             }
         });
@@ -86,9 +267,55 @@ impl EventHandler<GlobalEvent> for WaylandWindow {
 
 impl Draw for WaylandWindow {
     fn draw(&mut self, constraints: Constraints) -> bool {
-        self.state
-            .lock()
-            .map(|state| {
+        let mut guard = self.state.lock().expect("failed to get window state");
+        let state = &mut *guard;
+
+        let profiling = profiler::is_enabled();
+        if profiling {
+            // the overlay changes every frame
+            self.damage.add_full();
+        }
+
+        // widgets are laid out in logical pixels, the buffer is in physical
+        // pixels
+        backend::set_scale(self.scale);
+        let physical = constraints.to_physical(self.scale);
+
+        let age = match &mut state.surface {
+            WindowSurface::Egl { surface, .. } => surface.buffer_age().unwrap_or(0),
+            // the canvas keeps the last frame until it's resized
+            WindowSurface::Shm { canvas, size, .. } => {
+                (canvas.is_some() && *size == (physical.width, physical.height)) as i32
+            }
+        };
+        let Some((repaint, damage)) = self.damage.frame(
+            age,
+            Constraints {
+                width: constraints.width,
+                height: constraints.height,
+                ..Default::default()
+            },
+        ) else {
+            // nothing changed, the buffer shown stays
+            return true;
+        };
+        if profiling {
+            profiler::begin_frame();
+        }
+        if let (WindowSurface::Shm { canvas, size, .. }, 0) = (&mut state.surface, age) {
+            let backend = SoftwareBackend::new(physical.width, physical.height);
+            *canvas = Some(Box::new(backend));
+            *size = (physical.width, physical.height);
+        }
+        let repaint = repaint.to_physical(self.scale);
+        let damage = damage.to_physical(self.scale);
+
+        // the software backend draws everything, which is what was there
+        // outside the damage anyway
+        let prev_backend = match &mut state.surface {
+            WindowSurface::Egl { .. } => {
+                gl::enable(GlCap::ScissorTest);
+                gl::scissor(repaint.x, repaint.y, repaint.width, repaint.height).unwrap();
                 gl::enable(GlCap::Blend);
                 gl::blend_func_separate(
                     GlBlendFact::SrcAlpha,
@@ -96,34 +323,101 @@ impl Draw for WaylandWindow {
                     GlBlendFact::One,
                     GlBlendFact::One,
                 );
-                gl::view_port(0, 0, constraints.width, constraints.height).unwrap();
+                gl::view_port(0, 0, physical.width, physical.height).unwrap();
+                None
+            }
+            WindowSurface::Shm { canvas, .. } => {
+                let canvas = canvas.take().expect("the canvas was just created");
+                Some(backend::set(canvas))
+            }
+        };
 
-                Rect {
-                    bg_color: Color::rgb(30, 30, 30),
-                    bd_color: Color::rgb(46, 46, 46),
-                    bd_width: 2,
-                    bd_radius: 10,
-                }
-                .draw(constraints);
-
-                if let Some(child) = self.widget.as_mut() {
-                    child.draw(Constraints {
-                        width: constraints.width - 4,
-                        height: constraints.height - 4,
-                        x: 2,
-                        y: 2,
-                        ..Default::default()
-                    });
-                }
+        {
+            let _group = GlDebugGroup::push("window background");
+            let _scope = profiler::scope("window background");
+            Rect {
+                bg_color: Color::rgb(30, 30, 30),
+                bd_color: Color::rgb(46, 46, 46),
+                bd_width: 2,
+                bd_radius: 10,
+            }
+            .draw(constraints);
+        }
+
+        if let Some(child) = self.widget.as_mut() {
+            let _group = GlDebugGroup::push("widgets");
+            let _scope = profiler::scope("widgets");
+            child.draw(Constraints {
+                width: constraints.width - 4,
+                height: constraints.height - 4,
+                x: 2,
+                y: 2,
+                ..Default::default()
+            });
+        }
+
+        if profiling {
+            profiler::end_frame();
+            if let Some(stats) = profiler::last_frame() {
+                let _group = GlDebugGroup::push("profiler overlay");
+                overlay::draw(&stats, constraints);
+            }
+        }
 
+        match &mut state.surface {
+            WindowSurface::Egl { surface, .. } => {
                 gl::disable(GlCap::Blend);
+                gl::disable(GlCap::ScissorTest);
 
-                state
-                    .surface
-                    .swap()
+                surface
+                    .swap_with_damage(&[[damage.x, damage.y, damage.width, damage.height]])
                     .expect("failed to swap EGL surface buffers");
-            })
-            .expect("failed to get window state");
+            }
+            WindowSurface::Shm {
+                shm,
+                qh,
+                canvas,
+                size: (width, height),
+                buffer,
+            } => {
+                let mut drawn = match prev_backend.flatten() {
+                    Some(prev) => backend::set(prev),
+                    None => backend::reset(),
+                }
+                .expect("the canvas was set as the backend");
+                let pixels = drawn.read_pixels(Constraints {
+                    width: *width,
+                    height: *height,
+                    ..Default::default()
+                });
+                *canvas = Some(drawn);
+
+                match shm::buffer(shm, *width, *height, &shm::argb(&pixels), qh, AppData) {
+                    Ok(new) => {
+                        state.wl_surface.attach(Some(&new), 0, 0);
+                        // buffers start with the top row
+                        let y = *height - damage.y - damage.height;
+                        state
+                            .wl_surface
+                            .damage_buffer(damage.x, y, damage.width, damage.height);
+                        state.wl_surface.commit();
+                        // its memory is never written again, so it can go
+                        // before the compositor releases it
+                        if let Some(old) = buffer.replace(new) {
+                            old.destroy();
+                        }
+                    }
+                    Err(e) => eprintln!("failed to create the window buffer: {e}"),
+                }
+            }
+        }
+
+        drop(guard);
+
+        // damaged while drawing, drawn again in the next frame
+        if let Some(area) = damage::take() {
+            self.damage(area);
+        }
         true
     }
 }
@@ -137,9 +431,96 @@ pub(super) struct WindowState {
     pub(super) wl_surface: WlSurface,
     pub(super) xdg_surface: XdgSurface,
     pub(super) xdg_toplevel: XdgToplevel,
-    pub(super) display: EglDisplay,
-    pub(super) window: WlEglSurface,
-    pub(super) surface: EglSurface,
-    pub(super) context: EglContext,
+    pub(super) surface: WindowSurface,
+    pub(super) fractional_scale: Option<WpFractionalScaleV1>,
+    pub(super) viewport: Option<WpViewport>,
     pub(super) title: String,
 }
+
+impl Drop for WindowState {
+    fn drop(&mut self) {
+        match &self.surface {
+            WindowSurface::Egl { surface, .. } => {
+                let _ = surface.term();
+            }
+            WindowSurface::Shm { buffer, .. } => {
+                if let Some(buffer) = buffer.as_ref() {
+                    buffer.destroy();
+                }
+            }
+        }
+        if let Some(fractional_scale) = self.fractional_scale.as_ref() {
+            fractional_scale.destroy();
+        }
+        if let Some(viewport) = self.viewport.as_ref() {
+            viewport.destroy();
+        }
+        self.xdg_toplevel.destroy();
+        self.xdg_surface.destroy();
+        self.wl_surface.destroy();
+    }
+}
+
+/// How the frames of a window reach the compositor.
+pub(super) enum WindowSurface {
+    Egl {
+        window: WlEglSurface,
+        surface: EglSurface,
+    },
+    /// Drawn on the CPU into shared memory, when EGL couldn't be set up.
+    Shm {
+        shm: WlShm,
+        qh: QueueHandle<AppState>,
+        /// Software backend keeping the last frame, set as the backend while
+        /// drawing.
+        canvas: Option<Box<dyn Backend>>,
+        size: (i32, i32),
+        /// Buffer attached to the surface.
+        buffer: Option<WlBuffer>,
+    },
+}
+
+/// Scale to draw at: the fractional scale the compositor prefers if it sent
+/// one, else its preferred buffer scale, else the largest scale of the outputs
+/// showing the window. Buffers can only be scaled by whole numbers without a
+/// `viewport`, fractional scales are rounded up then.
+fn choose_scale(
+    fractional: Option<f64>,
+    buffer: Option<i32>,
+    output: Option<i32>,
+    viewport: bool,
+) -> f64 {
+    let scale = fractional
+        .or(buffer.map(f64::from))
+        .unwrap_or(output.unwrap_or(1) as f64);
+    if viewport {
+        scale
+    } else {
+        scale.ceil()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn fractional_scale_first() {
+        assert_eq!(choose_scale(Some(1.5), Some(2), Some(3), true), 1.5);
+        assert_eq!(choose_scale(None, Some(2), Some(3), true), 2.0);
+        assert_eq!(choose_scale(None, None, Some(3), true), 3.0);
+    }
+
+    #[test]
+    fn unscaled_without_preference() {
+        assert_eq!(choose_scale(None, None, None, true), 1.0);
+        assert_eq!(choose_scale(None, None, None, false), 1.0);
+    }
+
+    #[test]
+    fn whole_scale_without_viewport() {
+        assert_eq!(choose_scale(Some(1.25), None, None, false), 2.0);
+        assert_eq!(choose_scale(Some(2.0), None, None, false), 2.0);
+        assert_eq!(choose_scale(None, Some(2), None, false), 2.0);
+    }
+}
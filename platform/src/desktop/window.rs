@@ -1,9 +1,14 @@
 use super::{event::GlobalEvent, PlatformWindow};
 use dagt_core::interface::{widget::WidgetBuilder, window::WindowBuilder};
+use dagt_gl::gles::debug::GlDebugLogger;
 
-#[derive(Default)]
 pub struct Window {
     pub(crate) title: String,
+    pub(crate) samples: i32,
+    pub(crate) vsync: bool,
+    pub(crate) debug: bool,
+    pub(crate) debug_logger: Option<Box<dyn GlDebugLogger>>,
+    pub(crate) profiler: bool,
     pub(crate) child: Option<Box<dyn WidgetBuilder<GlobalEvent>>>,
     pub(crate) event_handler: Option<Box<dyn Fn(&mut PlatformWindow, &GlobalEvent) -> bool>>,
 }
@@ -12,6 +17,63 @@ impl Window {
     pub fn new() -> Window {
         Default::default()
     }
+
+    /// Samples per pixel for multisample anti-aliasing, `0` disables it.
+    /// Lowered automatically if the display doesn't support it.
+    ///
+    /// All windows share one EGL config, chosen for the most samples asked
+    /// by the windows opened at startup. Windows opened later can't ask for
+    /// more, a warning is printed when they do.
+    pub fn samples(mut self, samples: i32) -> Self {
+        self.samples = samples;
+        self
+    }
+
+    /// Syncs buffer swaps to the display refresh, on by default.
+    pub fn vsync(mut self, vsync: bool) -> Self {
+        self.vsync = vsync;
+        self
+    }
+
+    /// Creates a debug GL context and prints its debug output to stderr.
+    ///
+    /// All windows share one context, created with the debug bit when a
+    /// window opened at startup asks for it. Windows opened later only
+    /// enable the debug output, drivers may report nothing without the bit,
+    /// a warning is printed then.
+    pub fn debug(mut self, debug: bool) -> Self {
+        self.debug = debug;
+        self
+    }
+
+    /// Profiles frames and shows their timings in an overlay.
+    pub fn profiler(mut self, profiler: bool) -> Self {
+        self.profiler = profiler;
+        self
+    }
+
+    /// Creates a debug GL context like [`Window::debug`], routing its
+    /// debug output to `logger` instead of stderr.
+    pub fn debug_logger(mut self, logger: impl GlDebugLogger + 'static) -> Self {
+        self.debug = true;
+        self.debug_logger = Some(Box::new(logger));
+        self
+    }
+}
+
+impl Default for Window {
+    fn default() -> Self {
+        Window {
+            title: String::new(),
+            samples: 0,
+            vsync: true,
+            debug: false,
+            debug_logger: None,
+            profiler: false,
+            child: None,
+            event_handler: None,
+        }
+    }
 }
 
 impl WindowBuilder<GlobalEvent, PlatformWindow> for Window {
@@ -11,6 +11,15 @@ pub mod desktop {
     type PlatformWindow = wayland::window::WaylandWindow;
 
     pub mod app;
+    pub mod clipboard;
+    pub mod cursor;
+    pub mod damage;
+    pub mod drag;
     pub mod event;
     pub mod window;
+
+    mod overlay;
+    mod poll;
+    #[cfg(feature = "hot-reload")]
+    mod watcher;
 }
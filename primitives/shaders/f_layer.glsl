@@ -0,0 +1,18 @@
+#version 320 es
+precision mediump float;
+
+uniform sampler2D uTexture;
+uniform float uOpacity;
+
+in vec2 fCoord;
+out vec4 fColor;
+
+void main() {
+    vec4 color = texture(uTexture, fCoord * 0.5 + 0.5);
+
+    // layer contents are blended onto a transparent target, so color is premultiplied
+    if (color.a > 0.0)
+        color.rgb /= color.a;
+
+    fColor = vec4(color.rgb, color.a * uOpacity);
+}
@@ -0,0 +1,66 @@
+use crate::{circle::Circle, color::Color, glyph::Glyph, layer::Layer, path::Path, rect::Rect};
+use dagt_core::Constraints;
+use gl::GlBackend;
+use std::cell::{Cell, RefCell};
+
+pub mod gl;
+pub mod software;
+
+/// Renderer of the primitives. Coordinates are in physical pixels with the
+/// origin in the bottom left corner of the target.
+pub trait Backend {
+    fn clear(&mut self, color: Color);
+    fn rect(&mut self, rect: &Rect, loc: Constraints);
+    fn circle(&mut self, circle: &Circle, loc: Constraints);
+    /// Strokes `path`, whose points span `-1.0..1.0` of `loc`.
+    fn path(&mut self, path: &mut Path, loc: Constraints);
+    /// Fills the contours of `glyph` with the even-odd rule.
+    fn glyph(&mut self, glyph: &mut Glyph, loc: Constraints);
+    /// Redirects drawing to an offscreen target the size of `loc`, with its
+    /// origin at `0, 0`, until [`Backend::end_layer`].
+    fn begin_layer(&mut self, layer: &mut Layer, loc: Constraints);
+    /// Composites what was drawn since [`Backend::begin_layer`] at `loc`.
+    fn end_layer(&mut self, layer: &mut Layer, loc: Constraints);
+    /// RGBA pixels of `area`, top row first.
+    fn read_pixels(&mut self, area: Constraints) -> Vec<u8>;
+}
+
+thread_local! {
+    static BACKEND: RefCell<Option<Box<dyn Backend>>> = const { RefCell::new(None) };
+    static SCALE: Cell<f64> = const { Cell::new(1.0) };
+}
+
+/// Replaces the backend primitives are drawn with on this thread, returning
+/// the previous one. [`GlBackend`] is used until a backend is set.
+pub fn set(backend: Box<dyn Backend>) -> Option<Box<dyn Backend>> {
+    BACKEND.with(|b| b.borrow_mut().replace(backend))
+}
+
+/// Goes back to [`GlBackend`], returning the backend that was set.
+pub fn reset() -> Option<Box<dyn Backend>> {
+    BACKEND.with(|b| b.borrow_mut().take())
+}
+
+/// Sets the physical pixels per logical pixel of the target drawn on this
+/// thread. Primitives are placed in logical pixels and drawn in physical
+/// pixels, `1.0` until set.
+pub fn set_scale(scale: f64) {
+    SCALE.with(|s| s.set(scale));
+}
+
+pub fn scale() -> f64 {
+    SCALE.with(|s| s.get())
+}
+
+/// `len` logical pixels in physical pixels, at the scale of this thread.
+pub fn to_physical(len: i32) -> i32 {
+    (len as f64 * scale()).round() as i32
+}
+
+/// Runs `f` with the backend of this thread.
+pub fn with<R>(f: impl FnOnce(&mut dyn Backend) -> R) -> R {
+    BACKEND.with(|b| {
+        let mut b = b.borrow_mut();
+        f(b.get_or_insert_with(|| Box::new(GlBackend::new())).as_mut())
+    })
+}
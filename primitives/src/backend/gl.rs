@@ -0,0 +1,313 @@
+use super::Backend;
+use crate::{
+    circle::Circle, color::Color, glyph::Glyph, layer::Layer, path::Path, rect::Rect,
+    shaders::ShaderProgram, vertex::Vertex,
+};
+use dagt_core::Constraints;
+use dagt_gl::gles::{
+    framebuffer::{GlFramebuffer, GlFramebufferBinding, GlRenderbuffer},
+    func as gl,
+    program::Uniform,
+    texture::{GlTexUnit, GlTexture},
+    types::{
+        GlAttachment, GlBoolean, GlCap, GlClearMask, GlDrawMode, GlFramebufferTarget,
+        GlPixelFormat, GlPixelType, GlStencilFunc, GlStencilOp, GlTexFilter, GlTexFormat,
+        GlTexTarget, GlTexWrap,
+    },
+    vertex::GlVArray,
+};
+use std::sync::OnceLock;
+
+/// Backend drawing with the GLES context current on the thread.
+pub struct GlBackend {
+    layers: Vec<GlFramebufferBinding>,
+}
+
+impl GlBackend {
+    pub fn new() -> GlBackend {
+        GlBackend { layers: Vec::new() }
+    }
+}
+
+impl Default for GlBackend {
+    fn default() -> Self {
+        GlBackend::new()
+    }
+}
+
+/// Quad covering the viewport.
+fn quad() -> &'static GlVArray {
+    static VAO: OnceLock<GlVArray> = OnceLock::new();
+
+    VAO.get_or_init(|| {
+        let mut vao = GlVArray::create().unwrap();
+        vao.add_v_buffer(&[
+            Vertex::new(-1.0, 1.0),
+            Vertex::new(1.0, 1.0),
+            Vertex::new(-1.0, -1.0),
+            Vertex::new(1.0, -1.0),
+        ])
+        .unwrap();
+        vao
+    })
+}
+
+impl Backend for GlBackend {
+    fn clear(&mut self, color: Color) {
+        let [r, g, b, a] = color.as_arr().map(|c| (c * 255.0) as u8);
+        gl::clear_color((r, g, b, a));
+        gl::clear(GlClearMask::new().color().stencil()).unwrap();
+    }
+
+    fn rect(&mut self, rect: &Rect, loc: Constraints) {
+        static PROG: OnceLock<&ShaderProgram> = OnceLock::new();
+
+        let prog = PROG
+            .get_or_init(|| ShaderProgram::load("v.glsl", "f_rect.glsl"))
+            .get();
+
+        gl::view_port(loc.x, loc.y, loc.width, loc.height).unwrap();
+
+        quad().bind();
+        prog.bind();
+
+        prog.set_uniform("uRes", &[loc.width as f32, loc.height as f32])
+            .unwrap();
+        prog.set_uniform("uSize", &[loc.width as f32, loc.height as f32])
+            .unwrap();
+
+        prog.set_uniform("uBGColor", rect.bg_color.as_arr())
+            .unwrap();
+        prog.set_uniform("uBDColor", rect.bd_color.as_arr())
+            .unwrap();
+        prog.set_uniform("uBDWidth", rect.bd_width as f32).unwrap();
+        prog.set_uniform("uBDRadius", rect.bd_radius as f32)
+            .unwrap();
+
+        gl::draw_arrays(GlDrawMode::TriangleStrip, 0, 4).unwrap();
+    }
+
+    fn circle(&mut self, circle: &Circle, loc: Constraints) {
+        static PROG: OnceLock<&ShaderProgram> = OnceLock::new();
+
+        let prog = PROG
+            .get_or_init(|| ShaderProgram::load("v.glsl", "f_circle.glsl"))
+            .get();
+
+        gl::view_port(loc.x, loc.y, loc.width, loc.height).unwrap();
+
+        quad().bind();
+        prog.bind();
+
+        prog.set_uniform("uRes", &[loc.width as f32, loc.height as f32])
+            .unwrap();
+        prog.set_uniform("uSize", &[loc.width as f32, loc.height as f32])
+            .unwrap();
+
+        prog.set_uniform("uBGColor", circle.bg_color.as_arr())
+            .unwrap();
+        prog.set_uniform("uBDColor", circle.bd_color.as_arr())
+            .unwrap();
+        prog.set_uniform("uBDWidth", circle.bd_width as f32)
+            .unwrap();
+
+        gl::draw_arrays(GlDrawMode::TriangleStrip, 0, 4).unwrap();
+    }
+
+    fn path(&mut self, path: &mut Path, loc: Constraints) {
+        static PROG: OnceLock<&ShaderProgram> = OnceLock::new();
+
+        let prog = PROG
+            .get_or_init(|| ShaderProgram::load("v.glsl", "f.glsl"))
+            .get();
+
+        let vao = path.vao.get_or_insert_with(|| {
+            let mut vao = GlVArray::create().unwrap();
+            vao.add_v_buffer(&path.vbo).unwrap();
+            vao
+        });
+
+        gl::view_port(loc.x, loc.y, loc.width, loc.height).unwrap();
+
+        vao.bind();
+        prog.bind();
+
+        prog.set_uniform("uRes", &[loc.width as f32, loc.height as f32])
+            .unwrap();
+        prog.set_uniform("uSize", &[loc.width as f32, loc.height as f32])
+            .unwrap();
+
+        let mode = if path.cycled {
+            GlDrawMode::LineLoop
+        } else {
+            GlDrawMode::LineStrip
+        };
+
+        gl::draw_arrays(mode, 0, path.vbo.len() as i32).unwrap();
+    }
+
+    /// Counts how many contours cover each pixel in the stencil, inverting
+    /// it with a fan per contour, then covers the odd pixels.
+    fn glyph(&mut self, glyph: &mut Glyph, loc: Constraints) {
+        static PROG: OnceLock<&ShaderProgram> = OnceLock::new();
+
+        let prog = PROG
+            .get_or_init(|| ShaderProgram::load("v.glsl", "f.glsl"))
+            .get();
+
+        if glyph.vao.is_none() {
+            let contours = glyph.contours();
+            let mut ranges = Vec::with_capacity(contours.len());
+            let mut first = 0;
+            for contour in &contours {
+                ranges.push((first, contour.len() as i32));
+                first += contour.len() as i32;
+            }
+            let mut vao = GlVArray::create().unwrap();
+            vao.add_v_buffer(&contours.concat()).unwrap();
+            glyph.vao = Some((vao, ranges));
+        }
+        let Some((vao, ranges)) = glyph.vao.as_ref() else {
+            return;
+        };
+
+        gl::view_port(loc.x, loc.y, loc.width, loc.height).unwrap();
+
+        prog.bind();
+        prog.set_uniform("uRes", &[loc.width as f32, loc.height as f32])
+            .unwrap();
+        prog.set_uniform("uSize", &[loc.width as f32, loc.height as f32])
+            .unwrap();
+
+        gl::enable(GlCap::StencilTest);
+        gl::color_mask(
+            GlBoolean::False,
+            GlBoolean::False,
+            GlBoolean::False,
+            GlBoolean::False,
+        );
+        gl::stencil_func(GlStencilFunc::Always, 0, 0xff).unwrap();
+        gl::stencil_op(GlStencilOp::Keep, GlStencilOp::Keep, GlStencilOp::Invert).unwrap();
+        vao.bind();
+        for (first, count) in ranges {
+            gl::draw_arrays(GlDrawMode::TriangleFan, *first, *count).unwrap();
+        }
+
+        // clears the stencil again while covering
+        gl::color_mask(
+            GlBoolean::True,
+            GlBoolean::True,
+            GlBoolean::True,
+            GlBoolean::True,
+        );
+        gl::stencil_func(GlStencilFunc::NotEqual, 0, 0xff).unwrap();
+        gl::stencil_op(GlStencilOp::Zero, GlStencilOp::Zero, GlStencilOp::Zero).unwrap();
+        quad().bind();
+        gl::draw_arrays(GlDrawMode::TriangleStrip, 0, 4).unwrap();
+        gl::disable(GlCap::StencilTest);
+    }
+
+    fn begin_layer(&mut self, layer: &mut Layer, loc: Constraints) {
+        let target = match layer.target.take() {
+            Some(t) if t.width == loc.width && t.height == loc.height => t,
+            _ => LayerTarget::new(loc.width, loc.height),
+        };
+
+        self.layers.push(GlFramebufferBinding::current());
+        target.fbo.bind(GlFramebufferTarget::Framebuffer);
+        gl::view_port(0, 0, loc.width, loc.height).unwrap();
+        gl::clear_color((0, 0, 0, 0));
+        gl::clear(GlClearMask::new().color().stencil()).unwrap();
+
+        layer.target = Some(target);
+    }
+
+    fn end_layer(&mut self, layer: &mut Layer, loc: Constraints) {
+        static PROG: OnceLock<&ShaderProgram> = OnceLock::new();
+
+        if let Some(prev) = self.layers.pop() {
+            prev.restore();
+        }
+        let Some(target) = layer.target.as_ref() else {
+            return;
+        };
+
+        let prog = PROG
+            .get_or_init(|| ShaderProgram::load("v.glsl", "f_layer.glsl"))
+            .get();
+
+        gl::view_port(loc.x, loc.y, loc.width, loc.height).unwrap();
+
+        quad().bind();
+        prog.bind();
+
+        target.texture.bind_unit(GlTexUnit(0)).unwrap();
+        prog.set_uniform("uTexture", GlTexUnit(0)).unwrap();
+        prog.set_uniform("uOpacity", layer.opacity.clamp(0.0, 1.0))
+            .unwrap();
+
+        gl::draw_arrays(GlDrawMode::TriangleStrip, 0, 4).unwrap();
+    }
+
+    fn read_pixels(&mut self, area: Constraints) -> Vec<u8> {
+        let pixels = gl::read_pixels(area.x, area.y, area.width, area.height).unwrap();
+        // GL reads bottom row first
+        let row = area.width.max(0) as usize * 4;
+        if row == 0 {
+            return pixels;
+        }
+        pixels.chunks(row).rev().flatten().copied().collect()
+    }
+}
+
+/// Offscreen target of a [`Layer`], kept between frames and only reallocated
+/// when the size changes.
+pub(crate) struct LayerTarget {
+    fbo: GlFramebuffer,
+    texture: GlTexture,
+    _stencil: GlRenderbuffer,
+    width: i32,
+    height: i32,
+}
+
+impl LayerTarget {
+    fn new(width: i32, height: i32) -> LayerTarget {
+        let texture = GlTexture::create(GlTexTarget::Texture2D).unwrap();
+        texture
+            .image_2d(
+                0,
+                GlTexFormat::Rgba8,
+                width,
+                height,
+                GlPixelFormat::Rgba,
+                GlPixelType::UnsignedByte,
+                None,
+            )
+            .unwrap();
+        texture
+            .set_filter(GlTexFilter::Linear, GlTexFilter::Linear)
+            .unwrap();
+        texture
+            .set_wrap(GlTexWrap::ClampToEdge, GlTexWrap::ClampToEdge)
+            .unwrap();
+
+        let stencil = GlRenderbuffer::create().unwrap();
+        stencil
+            .storage(GlTexFormat::Depth24Stencil8, width, height, 0)
+            .unwrap();
+
+        let fbo = GlFramebuffer::create().unwrap();
+        fbo.attach_texture(GlAttachment::Color0, &texture).unwrap();
+        fbo.attach_renderbuffer(GlAttachment::DepthStencil, &stencil)
+            .unwrap();
+        fbo.check().unwrap();
+
+        LayerTarget {
+            fbo,
+            texture,
+            _stencil: stencil,
+            width,
+            height,
+        }
+    }
+}
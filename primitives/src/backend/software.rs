@@ -0,0 +1,300 @@
+use super::Backend;
+use crate::{circle::Circle, color::Color, glyph::Glyph, layer::Layer, path::Path, rect::Rect};
+use dagt_core::Constraints;
+
+/// Rows of subsamples per pixel when filling glyphs.
+const GLYPH_SUBSAMPLES: usize = 4;
+
+/// Backend rasterizing on the CPU into an RGBA buffer, for environments
+/// without GL and for deterministic rendering in tests.
+///
+/// Shading follows the GL shaders, blending is source-over with
+/// straight alpha.
+pub struct SoftwareBackend {
+    canvas: Canvas,
+    layers: Vec<Canvas>,
+}
+
+impl SoftwareBackend {
+    pub fn new(width: i32, height: i32) -> SoftwareBackend {
+        SoftwareBackend {
+            canvas: Canvas::new(width, height),
+            layers: Vec::new(),
+        }
+    }
+
+    pub fn width(&self) -> i32 {
+        self.canvas.width
+    }
+
+    pub fn height(&self) -> i32 {
+        self.canvas.height
+    }
+
+    /// RGBA pixels of the whole buffer, top row first.
+    pub fn pixels(&self) -> &[u8] {
+        &self.canvas.pixels
+    }
+
+    /// Resizes the buffer, clearing it.
+    pub fn resize(&mut self, width: i32, height: i32) {
+        self.canvas = Canvas::new(width, height);
+    }
+}
+
+impl Backend for SoftwareBackend {
+    fn clear(&mut self, color: Color) {
+        let pixel = color.as_arr().map(to_u8);
+        for p in self.canvas.pixels.chunks_mut(4) {
+            p.copy_from_slice(&pixel);
+        }
+    }
+
+    fn rect(&mut self, rect: &Rect, loc: Constraints) {
+        let size = (loc.width as f32, loc.height as f32);
+        let hb = rect.bd_width as f32 / 2.0;
+        let r = rect.bd_radius as f32 - hb;
+        let half = (size.0 / 2.0 - hb, size.1 / 2.0 - hb);
+        let bg = *rect.bg_color.as_arr();
+        let bd = *rect.bd_color.as_arr();
+
+        self.canvas.shade(loc, |x, y| {
+            let p = (x * size.0 / 2.0, y * size.1 / 2.0);
+            let d = rounded_rect_sdf(p, half, r);
+            let color = if d < 0.0 { bg } else { [0.0; 4] };
+            let blend = smoothstep(-1.0, 1.0, d.abs() - hb);
+            Some(mix(bd, color, blend))
+        });
+    }
+
+    fn circle(&mut self, circle: &Circle, loc: Constraints) {
+        let (w, h) = (loc.width as f32, loc.height as f32);
+        let fade = 3.0 / w.min(h);
+        let scale = (
+            1.0 + circle.bd_width as f32 / w * 2.0,
+            1.0 + circle.bd_width as f32 / h * 2.0,
+        );
+        let bg = *circle.bg_color.as_arr();
+        let bd = *circle.bd_color.as_arr();
+
+        self.canvas.shade(loc, |x, y| {
+            let d_out = smoothstep(0.0, fade, 1.0 - x.hypot(y));
+            if d_out == 0.0 {
+                return None;
+            }
+            let d_in = smoothstep(1.0 - fade, 1.0, (x * scale.0).hypot(y * scale.1));
+            let d_whole = d_out * d_in;
+            let mut color = [0.0, 0.0, 0.0, d_out];
+            for i in 0..3 {
+                color[i] = bg[i] * (1.0 - d_in) + bd[i] * d_whole;
+            }
+            Some(color)
+        });
+    }
+
+    fn path(&mut self, path: &mut Path, loc: Constraints) {
+        let to_px = |i: usize| {
+            let v = path.vbo[i];
+            (
+                loc.x as f32 + (v.x() + 1.0) / 2.0 * loc.width as f32,
+                loc.y as f32 + (v.y() + 1.0) / 2.0 * loc.height as f32,
+            )
+        };
+
+        let n = path.vbo.len();
+        let segments = if path.cycled { n } else { n.saturating_sub(1) };
+        for i in 0..segments {
+            self.canvas
+                .line(to_px(i), to_px((i + 1) % n), [1.0; 4], loc);
+        }
+    }
+
+    fn glyph(&mut self, glyph: &mut Glyph, loc: Constraints) {
+        let (w, h) = (loc.width.max(0) as usize, loc.height.max(0) as usize);
+        let edges: Vec<_> = glyph
+            .contours()
+            .iter()
+            .flat_map(|c| {
+                let to_px = |i: usize| {
+                    let v = c[i % c.len()];
+                    (
+                        (v.x() + 1.0) / 2.0 * w as f32,
+                        (v.y() + 1.0) / 2.0 * h as f32,
+                    )
+                };
+                (0..c.len()).map(move |i| (to_px(i), to_px(i + 1)))
+            })
+            .collect();
+
+        let mut coverage = vec![0.0f32; w];
+        let mut xs = Vec::new();
+        for row in 0..h {
+            coverage.iter_mut().for_each(|c| *c = 0.0);
+            for sub in 0..GLYPH_SUBSAMPLES {
+                let y = row as f32 + (sub as f32 + 0.5) / GLYPH_SUBSAMPLES as f32;
+                xs.clear();
+                for ((x0, y0), (x1, y1)) in &edges {
+                    if (*y0 <= y) != (*y1 <= y) {
+                        xs.push(x0 + (y - y0) / (y1 - y0) * (x1 - x0));
+                    }
+                }
+                xs.sort_by(|a, b| a.total_cmp(b));
+                for span in xs.chunks_exact(2) {
+                    add_span(
+                        &mut coverage,
+                        span[0],
+                        span[1],
+                        1.0 / GLYPH_SUBSAMPLES as f32,
+                    );
+                }
+            }
+            for (col, c) in coverage.iter().enumerate() {
+                if *c > 0.0 {
+                    let (x, y) = (loc.x + col as i32, loc.y + row as i32);
+                    self.canvas.blend(x, y, [1.0, 1.0, 1.0, c.min(1.0)]);
+                }
+            }
+        }
+    }
+
+    fn begin_layer(&mut self, _layer: &mut Layer, loc: Constraints) {
+        let canvas = std::mem::replace(&mut self.canvas, Canvas::new(loc.width, loc.height));
+        self.layers.push(canvas);
+    }
+
+    fn end_layer(&mut self, layer: &mut Layer, loc: Constraints) {
+        let Some(canvas) = self.layers.pop() else {
+            return;
+        };
+        let content = std::mem::replace(&mut self.canvas, canvas);
+        let opacity = layer.opacity.clamp(0.0, 1.0);
+
+        for y in 0..content.height {
+            for x in 0..content.width {
+                let mut color = content.get(x, y);
+                color[3] *= opacity;
+                self.canvas.blend(loc.x + x, loc.y + y, color);
+            }
+        }
+    }
+
+    fn read_pixels(&mut self, area: Constraints) -> Vec<u8> {
+        let mut pixels = Vec::with_capacity((area.width.max(0) * area.height.max(0) * 4) as usize);
+        for y in (area.y..area.y + area.height).rev() {
+            for x in area.x..area.x + area.width {
+                pixels.extend(self.canvas.get(x, y).map(to_u8));
+            }
+        }
+        pixels
+    }
+}
+
+/// RGBA buffer with the origin in the bottom left corner, stored top row
+/// first.
+struct Canvas {
+    width: i32,
+    height: i32,
+    pixels: Vec<u8>,
+}
+
+impl Canvas {
+    fn new(width: i32, height: i32) -> Canvas {
+        let (width, height) = (width.max(0), height.max(0));
+        Canvas {
+            width,
+            height,
+            pixels: vec![0; (width * height * 4) as usize],
+        }
+    }
+
+    fn index(&self, x: i32, y: i32) -> Option<usize> {
+        if x < 0 || y < 0 || x >= self.width || y >= self.height {
+            return None;
+        }
+        Some((((self.height - 1 - y) * self.width + x) * 4) as usize)
+    }
+
+    fn get(&self, x: i32, y: i32) -> [f32; 4] {
+        match self.index(x, y) {
+            Some(i) => [0, 1, 2, 3].map(|c| self.pixels[i + c] as f32 / 255.0),
+            None => [0.0; 4],
+        }
+    }
+
+    fn blend(&mut self, x: i32, y: i32, src: [f32; 4]) {
+        let Some(i) = self.index(x, y) else {
+            return;
+        };
+        let a = src[3].clamp(0.0, 1.0);
+        let dst = self.get(x, y);
+        let out_a = a + dst[3] * (1.0 - a);
+        for c in 0..3 {
+            self.pixels[i + c] = to_u8(src[c] * a + dst[c] * (1.0 - a));
+        }
+        self.pixels[i + 3] = to_u8(out_a);
+    }
+
+    /// Blends `f(x, y)` over every pixel of `loc`, where `x, y` is the pixel
+    /// center in `-1.0..1.0` of `loc` like the shaders' `fCoord`.
+    fn shade(&mut self, loc: Constraints, f: impl Fn(f32, f32) -> Option<[f32; 4]>) {
+        let x_range = loc.x.max(0)..(loc.x + loc.width).min(self.width);
+        for py in loc.y.max(0)..(loc.y + loc.height).min(self.height) {
+            let y = ((py - loc.y) as f32 + 0.5) / loc.height as f32 * 2.0 - 1.0;
+            for px in x_range.clone() {
+                let x = ((px - loc.x) as f32 + 0.5) / loc.width as f32 * 2.0 - 1.0;
+                if let Some(color) = f(x, y) {
+                    self.blend(px, py, color);
+                }
+            }
+        }
+    }
+
+    /// One pixel wide line from `a` to `b`, clipped to `clip` like the GL
+    /// viewport.
+    fn line(&mut self, a: (f32, f32), b: (f32, f32), color: [f32; 4], clip: Constraints) {
+        let steps = (b.0 - a.0).abs().max((b.1 - a.1).abs()).ceil().max(1.0) as i32;
+        for i in 0..=steps {
+            let t = i as f32 / steps as f32;
+            let x = (a.0 + (b.0 - a.0) * t).floor() as i32;
+            let y = (a.1 + (b.1 - a.1) * t).floor() as i32;
+            if x >= clip.x && y >= clip.y && x < clip.x + clip.width && y < clip.y + clip.height {
+                self.blend(x, y, color);
+            }
+        }
+    }
+}
+
+/// Adds `weight` times the part of each pixel covered by `x0..x1`.
+fn add_span(coverage: &mut [f32], x0: f32, x1: f32, weight: f32) {
+    let x0 = x0.max(0.0);
+    let x1 = x1.min(coverage.len() as f32);
+    if x1 <= x0 {
+        return;
+    }
+    let (first, last) = (
+        x0.floor() as usize,
+        (x1.ceil() as usize).min(coverage.len()),
+    );
+    for (col, c) in coverage.iter_mut().enumerate().take(last).skip(first) {
+        let covered = (x1.min(col as f32 + 1.0) - x0.max(col as f32)).max(0.0);
+        *c += covered * weight;
+    }
+}
+
+fn rounded_rect_sdf(p: (f32, f32), b: (f32, f32), r: f32) -> f32 {
+    let d = (p.0.abs() - b.0 + r, p.1.abs() - b.1 + r);
+    d.0.max(d.1).min(0.0) + d.0.max(0.0).hypot(d.1.max(0.0)) - r
+}
+
+fn smoothstep(edge0: f32, edge1: f32, x: f32) -> f32 {
+    let t = ((x - edge0) / (edge1 - edge0)).clamp(0.0, 1.0);
+    t * t * (3.0 - 2.0 * t)
+}
+
+fn mix(a: [f32; 4], b: [f32; 4], t: f32) -> [f32; 4] {
+    [0, 1, 2, 3].map(|i| a[i] * (1.0 - t) + b[i] * t)
+}
+
+fn to_u8(c: f32) -> u8 {
+    (c.clamp(0.0, 1.0) * 255.0).round() as u8
+}
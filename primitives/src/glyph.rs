@@ -1,24 +1,28 @@
-use crate::path::Path;
+use crate::{backend, vertex::Vertex};
 use dagt_core::{Constraints, Draw};
 use dagt_fonts::GlyphData;
+use dagt_gl::gles::vertex::GlVArray;
 
 pub struct Glyph {
     glyph: GlyphData,
+    /// Contours one after another and the `(first, count)` vertices of each,
+    /// created by the GL backend on first draw.
+    pub(crate) vao: Option<(GlVArray, Vec<(i32, i32)>)>,
 }
 
 impl Glyph {
     pub fn new(glyph: GlyphData) -> Glyph {
-        Glyph { glyph }
+        Glyph { glyph, vao: None }
     }
-}
 
-impl Draw for Glyph {
-    fn draw(&mut self, constraints: Constraints) -> bool {
+    /// Closed contours of the glyph, spanning `-1.0..1.0` of its box.
+    pub fn contours(&self) -> Vec<Vec<Vertex>> {
         let width = self.glyph.width;
         let height = self.glyph.height;
         let x_offset = -self.glyph.min_x;
         let y_offset = -self.glyph.min_y;
 
+        let mut contours = Vec::new();
         let mut start = 0usize;
         for end in &self.glyph.contour_indices {
             let end = *end as usize;
@@ -33,9 +37,23 @@ impl Draw for Glyph {
             }
             start = end + 1;
 
-            Path::new(&v, true).draw(Constraints { ..constraints });
+            contours.push(v);
         }
 
+        contours
+    }
+}
+
+impl Clone for Glyph {
+    fn clone(&self) -> Self {
+        Glyph::new(self.glyph.clone())
+    }
+}
+
+impl Draw for Glyph {
+    fn draw(&mut self, constraints: Constraints) -> bool {
+        let loc = constraints.to_physical(backend::scale());
+        backend::with(|b| b.glyph(self, loc));
         true
     }
 }
@@ -0,0 +1,34 @@
+use crate::backend::{self, gl::LayerTarget};
+use dagt_core::{Constraints, Draw};
+
+/// Draws a subtree into an offscreen target and composites it with `opacity`.
+///
+/// The target is kept between frames and only reallocated when the size changes.
+pub struct Layer {
+    pub opacity: f32,
+    pub(crate) target: Option<LayerTarget>,
+}
+
+impl Layer {
+    pub fn new(opacity: f32) -> Layer {
+        Layer {
+            opacity,
+            target: None,
+        }
+    }
+
+    /// Draws `child` offscreen at the origin, then composites the result at `constraints`.
+    pub fn draw_child(&mut self, child: &mut dyn Draw, constraints: Constraints) -> bool {
+        let loc = constraints.check();
+        if loc.width == 0 || loc.height == 0 {
+            return true;
+        }
+
+        let physical = loc.to_physical(backend::scale());
+        backend::with(|b| b.begin_layer(self, physical));
+        let res = child.draw(Constraints { x: 0, y: 0, ..loc });
+        backend::with(|b| b.end_layer(self, physical));
+
+        res
+    }
+}
@@ -1,4 +1,6 @@
+pub mod backend;
 pub mod color;
+pub mod shaders;
 pub mod vertex;
 
 pub mod circle;
@@ -7,3 +9,5 @@ pub mod rect;
 pub mod path;
 
 pub mod glyph;
+
+pub mod layer;
@@ -0,0 +1,94 @@
+use dagt_gl::gles::{program::GlProgram, shader::GlShader, types::GlShaderType};
+use std::{
+    fs,
+    path::Path,
+    sync::{Mutex, RwLock, RwLockReadGuard},
+};
+
+/// Directory the shader files of the primitives are read from.
+pub fn shader_dir() -> &'static Path {
+    Path::new(concat!(env!("CARGO_MANIFEST_DIR"), "/shaders"))
+}
+
+/// Program linked from a vertex and a fragment shader file of [`shader_dir`],
+/// rebuilt by [`reload`] when one of them changes.
+pub(crate) struct ShaderProgram {
+    vertex: &'static str,
+    fragment: &'static str,
+    prog: RwLock<GlProgram>,
+}
+
+static PROGRAMS: Mutex<Vec<&'static ShaderProgram>> = Mutex::new(Vec::new());
+
+impl ShaderProgram {
+    /// Builds the program, panicking if its shaders don't compile.
+    pub(crate) fn load(vertex: &'static str, fragment: &'static str) -> &'static ShaderProgram {
+        let prog = build(vertex, fragment).unwrap_or_else(|e| panic!("{e}"));
+        let prog = Box::leak(Box::new(ShaderProgram {
+            vertex,
+            fragment,
+            prog: RwLock::new(prog),
+        }));
+        PROGRAMS.lock().unwrap().push(prog);
+        prog
+    }
+
+    pub(crate) fn get(&self) -> RwLockReadGuard<'_, GlProgram> {
+        self.prog.read().unwrap()
+    }
+}
+
+fn build(vertex: &str, fragment: &str) -> Result<GlProgram, String> {
+    let compile = |shader_type, file: &str| {
+        let src =
+            fs::read_to_string(shader_dir().join(file)).map_err(|e| format!("{file}: {e}"))?;
+        let shader = GlShader::create(shader_type, &src).map_err(|e| format!("{file}: {e}"))?;
+        shader.compile().map_err(|e| format!("{file}: {e}"))?;
+        Ok::<_, String>(shader)
+    };
+    let v = compile(GlShaderType::Vertex, vertex)?;
+    let f = compile(GlShaderType::Fragment, fragment)?;
+
+    let name = format!("{vertex} + {fragment}");
+    let link = || {
+        let mut prog = GlProgram::create()?;
+        prog.set_label(&name)?;
+        prog.attach(&v)?;
+        prog.attach(&f)?;
+        prog.link()?;
+        Ok(prog)
+    };
+    link().map_err(|e: dagt_gl::error::Error| format!("{name}: {e}"))
+}
+
+/// Rebuilds the programs using shader `file`, returning how many there are.
+///
+/// Programs that fail to build keep their last good version, the errors are
+/// returned instead. Must be called with the context the programs were
+/// created in current.
+pub fn reload(file: &str) -> Result<usize, Vec<String>> {
+    let programs: Vec<_> = PROGRAMS
+        .lock()
+        .unwrap()
+        .iter()
+        .copied()
+        .filter(|p| p.vertex == file || p.fragment == file)
+        .collect();
+
+    let errors: Vec<_> = programs
+        .iter()
+        .filter_map(|p| match build(p.vertex, p.fragment) {
+            Ok(prog) => {
+                *p.prog.write().unwrap() = prog;
+                None
+            }
+            Err(e) => Some(e),
+        })
+        .collect();
+
+    if errors.is_empty() {
+        Ok(programs.len())
+    } else {
+        Err(errors)
+    }
+}
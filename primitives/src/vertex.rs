@@ -22,6 +22,14 @@ impl Vertex {
     pub fn new(x: f32, y: f32) -> Self {
         Vertex { pos: [x, y] }
     }
+
+    pub fn x(&self) -> f32 {
+        self.pos[0]
+    }
+
+    pub fn y(&self) -> f32 {
+        self.pos[1]
+    }
 }
 
 impl GlVertex for Vertex {
@@ -0,0 +1,395 @@
+//! Snapshot testing: draws a widget tree offscreen and compares the pixels
+//! with a golden PNG.
+//!
+//! Goldens are rewritten instead of compared when `DAGT_BLESS` is set. On a
+//! mismatch the rendered image and a diff are written to the output
+//! directory as `<name>.actual.png` and `<name>.diff.png`, never next to the
+//! goldens.
+
+use dagt_core::{Constraints, Draw};
+use dagt_gl::{
+    egl::{
+        binds as egl,
+        config::{attrs::EglSurfaceType, chooser::EglConfChooser, EglConfig},
+        context::{attrs::EglCtxAttrs, EglContext},
+        display::EglDisplay,
+        surface::attrs::EglSurfAttrs,
+    },
+    error::Result,
+    gles::{
+        binds as gl, func,
+        types::{GlBlendFact, GlCap},
+    },
+};
+use dagt_primitives::{
+    backend::{self, gl::GlBackend, software::SoftwareBackend, Backend},
+    color::Color,
+};
+use std::{
+    fs, io,
+    path::{Path, PathBuf},
+    sync::Mutex,
+};
+
+pub mod png;
+
+/// Environment variable turning [`Snapshot::assert_matches`] into an update
+/// of the golden.
+pub const BLESS_VAR: &str = "DAGT_BLESS";
+
+/// RGBA image, top row first.
+#[derive(Debug, Clone, PartialEq)]
+pub struct Image {
+    pub width: u32,
+    pub height: u32,
+    pub pixels: Vec<u8>,
+}
+
+impl Image {
+    pub fn read_png(path: impl AsRef<Path>) -> io::Result<Image> {
+        let (width, height, pixels) = png::decode(&fs::read(path)?)?;
+        Ok(Image {
+            width,
+            height,
+            pixels,
+        })
+    }
+
+    pub fn write_png(&self, path: impl AsRef<Path>) -> io::Result<()> {
+        if let Some(dir) = path.as_ref().parent() {
+            fs::create_dir_all(dir)?;
+        }
+        fs::write(path, png::encode(self.width, self.height, &self.pixels))
+    }
+
+    /// Counts the pixels with a channel differing from `other` by more than
+    /// `tolerance`, with an image showing them in red over a faded copy of
+    /// `self`. Both images must have the same size.
+    pub fn diff(&self, other: &Image, tolerance: u8) -> (usize, Image) {
+        assert_eq!(
+            (self.width, self.height),
+            (other.width, other.height),
+            "diffed images must have the same size"
+        );
+
+        let mut count = 0;
+        let mut pixels = Vec::with_capacity(self.pixels.len());
+        for (a, b) in self.pixels.chunks(4).zip(other.pixels.chunks(4)) {
+            let differs = a.iter().zip(b).any(|(a, b)| a.abs_diff(*b) > tolerance);
+            if differs {
+                count += 1;
+                pixels.extend_from_slice(&[255, 0, 0, 255]);
+            } else {
+                let gray = ((a[0] as u32 + a[1] as u32 + a[2] as u32) / 3) as u8;
+                pixels.extend_from_slice(&[gray, gray, gray, a[3] / 4]);
+            }
+        }
+
+        let diff = Image {
+            width: self.width,
+            height: self.height,
+            pixels,
+        };
+        (count, diff)
+    }
+}
+
+/// How snapshots are rendered.
+#[derive(Debug, Clone, Copy, Default, PartialEq)]
+pub enum Renderer {
+    /// [`SoftwareBackend`], deterministic and available everywhere.
+    #[default]
+    Software,
+    /// [`GlBackend`] on a headless EGL pbuffer, the surfaceless Mesa platform
+    /// is tried first, then the default display.
+    Egl,
+}
+
+/// Renders a widget tree offscreen and compares it with a golden image.
+#[derive(Debug, Clone, Copy)]
+pub struct Snapshot {
+    width: i32,
+    height: i32,
+    scale: f64,
+    renderer: Renderer,
+    tolerance: u8,
+    max_diff_pixels: usize,
+    out_dir: Option<&'static Path>,
+}
+
+impl Snapshot {
+    /// Snapshot of `width` by `height` pixels, rendered in software and
+    /// matching exactly.
+    pub fn new(width: i32, height: i32) -> Snapshot {
+        Snapshot {
+            width,
+            height,
+            scale: 1.0,
+            renderer: Renderer::Software,
+            tolerance: 0,
+            max_diff_pixels: 0,
+            out_dir: None,
+        }
+    }
+
+    /// Renders `scale` physical pixels per logical pixel, the image is the
+    /// size of the snapshot times `scale`.
+    pub fn scale(mut self, scale: f64) -> Self {
+        self.scale = scale;
+        self
+    }
+
+    pub fn renderer(mut self, renderer: Renderer) -> Self {
+        self.renderer = renderer;
+        self
+    }
+
+    /// Largest difference allowed per channel.
+    pub fn tolerance(mut self, tolerance: u8) -> Self {
+        self.tolerance = tolerance;
+        self
+    }
+
+    /// Number of pixels allowed to differ by more than the tolerance, to
+    /// absorb antialiasing differences between GPUs.
+    pub fn max_diff_pixels(mut self, max_diff_pixels: usize) -> Self {
+        self.max_diff_pixels = max_diff_pixels;
+        self
+    }
+
+    /// Directory the images of a mismatch are written to, integration tests
+    /// pass `env!("CARGO_TARGET_TMPDIR")`. `dagt-snapshots` in the system's
+    /// temporary directory by default.
+    pub fn out_dir<P: AsRef<Path> + ?Sized>(mut self, dir: &'static P) -> Self {
+        self.out_dir = Some(dir.as_ref());
+        self
+    }
+
+    /// Draws `root` filling a transparent target and reads the pixels back.
+    pub fn render(&self, root: &mut dyn Draw) -> Result<Image> {
+        let target = self.target();
+        let prev_scale = backend::scale();
+        backend::set_scale(self.scale);
+        let pixels = match self.renderer {
+            Renderer::Software => {
+                let prev =
+                    backend::set(Box::new(SoftwareBackend::new(target.width, target.height)));
+                let pixels = self.draw(root);
+                restore(prev);
+                Ok(pixels)
+            }
+            Renderer::Egl => render_egl(self, root),
+        };
+        backend::set_scale(prev_scale);
+
+        Ok(Image {
+            width: target.width as u32,
+            height: target.height as u32,
+            pixels: pixels?,
+        })
+    }
+
+    /// Renders `root` and compares it with the golden at `golden`, panicking
+    /// on a mismatch. Writes the golden instead when `DAGT_BLESS` is set.
+    pub fn assert_matches(&self, root: &mut dyn Draw, golden: impl AsRef<Path>) {
+        let golden = golden.as_ref();
+        let actual = self
+            .render(root)
+            .unwrap_or_else(|e| panic!("failed to render snapshot: {e}"));
+
+        let bless = std::env::var_os(BLESS_VAR).is_some_and(|v| v != "0");
+        if bless {
+            actual
+                .write_png(golden)
+                .unwrap_or_else(|e| panic!("failed to write {}: {e}", golden.display()));
+            return;
+        }
+        if !golden.exists() {
+            panic!(
+                "golden {} doesn't exist, set {BLESS_VAR}=1 to create it",
+                golden.display()
+            );
+        }
+
+        let expected = Image::read_png(golden)
+            .unwrap_or_else(|e| panic!("failed to read {}: {e}", golden.display()));
+        let actual_path = self.output(golden, "actual");
+        let diff_path = self.output(golden, "diff");
+
+        if (expected.width, expected.height) != (actual.width, actual.height) {
+            let _ = actual.write_png(&actual_path);
+            panic!(
+                "snapshot {} is {}x{}, the golden is {}x{}, actual image written to {}",
+                golden.display(),
+                actual.width,
+                actual.height,
+                expected.width,
+                expected.height,
+                actual_path.display()
+            );
+        }
+
+        let (count, diff) = expected.diff(&actual, self.tolerance);
+        if count > self.max_diff_pixels {
+            let _ = actual.write_png(&actual_path);
+            let _ = diff.write_png(&diff_path);
+            panic!(
+                "snapshot {} differs from its golden in {count} pixels, \
+                 see {} and {}, set {BLESS_VAR}=1 to update it",
+                golden.display(),
+                actual_path.display(),
+                diff_path.display()
+            );
+        }
+
+        let _ = fs::remove_file(actual_path);
+        let _ = fs::remove_file(diff_path);
+    }
+
+    fn area(&self) -> Constraints {
+        Constraints {
+            width: self.width,
+            height: self.height,
+            ..Default::default()
+        }
+    }
+
+    /// Area of the snapshot in physical pixels.
+    fn target(&self) -> Constraints {
+        self.area().to_physical(self.scale)
+    }
+
+    fn draw(&self, root: &mut dyn Draw) -> Vec<u8> {
+        backend::with(|b| b.clear(Color::rgba(0, 0, 0, 0)));
+        root.draw(self.area());
+        backend::with(|b| b.read_pixels(self.target()))
+    }
+
+    /// `<name>.<suffix>.png` in the output directory, for `golden`.
+    fn output(&self, golden: &Path, suffix: &str) -> PathBuf {
+        let stem = golden.file_stem().unwrap_or_default().to_string_lossy();
+        let dir = match self.out_dir {
+            Some(dir) => dir.to_owned(),
+            None => std::env::temp_dir().join("dagt-snapshots"),
+        };
+        dir.join(format!("{stem}.{suffix}.png"))
+    }
+}
+
+fn restore(prev: Option<Box<dyn Backend>>) {
+    match prev {
+        Some(prev) => backend::set(prev),
+        None => backend::reset(),
+    };
+}
+
+/// Headless context shared by every test thread, as the primitives' programs
+/// are built once per process.
+struct Headless {
+    // fields drop in order, the context goes before its display
+    context: EglContext,
+    config: EglConfig,
+    display: EglDisplay,
+}
+
+// the context is only used while `HEADLESS` is locked, and released before
+// unlocking
+unsafe impl Send for Headless {}
+
+static HEADLESS: Mutex<Option<Headless>> = Mutex::new(None);
+
+impl Headless {
+    fn new() -> Result<Headless> {
+        egl::load()?;
+        gl::load()?;
+
+        let display = EglDisplay::get_surfaceless()
+            .filter(|d| d.init().is_ok())
+            .or_else(|| EglDisplay::get_default().filter(|d| d.init().is_ok()))
+            .ok_or_else(|| unsafe { dagt_gl::error::Error::egl("eglInitialize") })?;
+        let config = EglConfChooser::new()
+            .surface_type(EglSurfaceType::empty().pbuffer())
+            .choose(&display)
+            .ok_or_else(|| unsafe { dagt_gl::error::Error::egl("eglChooseConfig") })?;
+        let context = display.create_context(
+            &config,
+            None,
+            Some(&EglCtxAttrs::new().major_version(3).minor_version(2)),
+        )?;
+
+        Ok(Headless {
+            display,
+            config,
+            context,
+        })
+    }
+}
+
+fn render_egl(snapshot: &Snapshot, root: &mut dyn Draw) -> Result<Vec<u8>> {
+    let mut headless = HEADLESS.lock().unwrap_or_else(|e| e.into_inner());
+    if headless.is_none() {
+        *headless = Some(Headless::new()?);
+    }
+    let Headless {
+        display,
+        config,
+        context,
+    } = headless.as_ref().unwrap();
+
+    let target = snapshot.target();
+    let surface = display.create_pbuffer_surface(
+        config,
+        Some(
+            &EglSurfAttrs::new()
+                .width(target.width)
+                .height(target.height),
+        ),
+    )?;
+    let pixels = context
+        .make_current(&surface, &surface)
+        .and_then(|_| draw_gl(snapshot, root));
+
+    // the surface is destroyed once it's no longer current
+    let released = context.release();
+    surface.term()?;
+    released?;
+    pixels
+}
+
+/// Draws `root` with [`GlBackend`] on the current context.
+fn draw_gl(snapshot: &Snapshot, root: &mut dyn Draw) -> Result<Vec<u8>> {
+    let target = snapshot.target();
+    func::enable(GlCap::Blend);
+    func::blend_func_separate(
+        GlBlendFact::SrcAlpha,
+        GlBlendFact::OneMinusSrcAlpha,
+        GlBlendFact::One,
+        GlBlendFact::One,
+    );
+    func::view_port(0, 0, target.width, target.height)?;
+
+    let prev = backend::set(Box::new(GlBackend::new()));
+    let pixels = snapshot.draw(root);
+    // drops the GL backend while its context is current
+    restore(prev);
+
+    func::disable(GlCap::Blend);
+    Ok(pixels)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn png_round_trip() {
+        let image = Image {
+            width: 3,
+            height: 2,
+            pixels: (0..24).map(|i| i * 10).collect(),
+        };
+        let (width, height, pixels) =
+            png::decode(&png::encode(image.width, image.height, &image.pixels)).unwrap();
+        assert_eq!((width, height, pixels), (3, 2, image.pixels));
+    }
+}
@@ -0,0 +1,456 @@
+//! Minimal PNG codec for snapshot images: writes 8 bit RGBA with stored
+//! deflate blocks, reads 8 bit non-interlaced grayscale, RGB and RGBA images.
+
+use std::io::{Error, ErrorKind, Result};
+
+const SIGNATURE: [u8; 8] = [0x89, b'P', b'N', b'G', b'\r', b'\n', 0x1A, b'\n'];
+
+/// Largest length of a stored deflate block.
+const MAX_STORED: usize = 0xFFFF;
+
+/// Encodes `pixels`, RGBA rows top first, as a PNG.
+pub fn encode(width: u32, height: u32, pixels: &[u8]) -> Vec<u8> {
+    let mut raw = Vec::with_capacity(pixels.len() + height as usize);
+    for row in pixels.chunks(width as usize * 4) {
+        raw.push(0);
+        raw.extend_from_slice(row);
+    }
+
+    let mut ihdr = Vec::new();
+    ihdr.extend_from_slice(&width.to_be_bytes());
+    ihdr.extend_from_slice(&height.to_be_bytes());
+    // bit depth, RGBA, deflate, no filter method extensions, not interlaced
+    ihdr.extend_from_slice(&[8, 6, 0, 0, 0]);
+
+    let mut png = SIGNATURE.to_vec();
+    chunk(&mut png, b"IHDR", &ihdr);
+    chunk(&mut png, b"IDAT", &zlib_stored(&raw));
+    chunk(&mut png, b"IEND", &[]);
+    png
+}
+
+/// Decodes a PNG into its size and RGBA pixels, top row first.
+pub fn decode(data: &[u8]) -> Result<(u32, u32, Vec<u8>)> {
+    if !data.starts_with(&SIGNATURE) {
+        return Err(invalid("not a PNG file"));
+    }
+
+    let mut pos = SIGNATURE.len();
+    let mut header = None;
+    let mut idat = Vec::new();
+    while pos + 8 <= data.len() {
+        let len = u32::from_be_bytes(data[pos..pos + 4].try_into().unwrap()) as usize;
+        let kind = &data[pos + 4..pos + 8];
+        let body = data
+            .get(pos + 8..pos + 8 + len)
+            .ok_or_else(|| invalid("truncated chunk"))?;
+        match kind {
+            b"IHDR" => header = Some(body.to_vec()),
+            b"IDAT" => idat.extend_from_slice(body),
+            b"IEND" => break,
+            _ => {}
+        }
+        pos += 12 + len;
+    }
+
+    let header = header.ok_or_else(|| invalid("missing IHDR"))?;
+    if header.len() < 13 {
+        return Err(invalid("truncated IHDR"));
+    }
+    let width = u32::from_be_bytes(header[0..4].try_into().unwrap());
+    let height = u32::from_be_bytes(header[4..8].try_into().unwrap());
+    let (depth, color, interlace) = (header[8], header[9], header[12]);
+    let channels = match color {
+        0 => 1,
+        2 => 3,
+        4 => 2,
+        6 => 4,
+        _ => return Err(invalid("unsupported color type")),
+    };
+    if depth != 8 || interlace != 0 {
+        return Err(invalid("only 8 bit non-interlaced images are supported"));
+    }
+
+    if idat.len() < 2 {
+        return Err(invalid("truncated image data"));
+    }
+    let raw = inflate(&idat[2..])?;
+    let stride = width as usize * channels;
+    if raw.len() < (stride + 1) * height as usize {
+        return Err(invalid("truncated image data"));
+    }
+
+    let mut prev = vec![0u8; stride];
+    let mut pixels = Vec::with_capacity(width as usize * height as usize * 4);
+    for row in raw.chunks(stride + 1).take(height as usize) {
+        let mut cur = row[1..].to_vec();
+        unfilter(row[0], &mut cur, &prev, channels)?;
+        for px in cur.chunks(channels) {
+            pixels.extend_from_slice(&match channels {
+                1 => [px[0], px[0], px[0], 255],
+                2 => [px[0], px[0], px[0], px[1]],
+                3 => [px[0], px[1], px[2], 255],
+                _ => [px[0], px[1], px[2], px[3]],
+            });
+        }
+        prev = cur;
+    }
+
+    Ok((width, height, pixels))
+}
+
+fn invalid(msg: &str) -> Error {
+    Error::new(ErrorKind::InvalidData, msg)
+}
+
+fn chunk(png: &mut Vec<u8>, kind: &[u8; 4], body: &[u8]) {
+    png.extend_from_slice(&(body.len() as u32).to_be_bytes());
+    let start = png.len();
+    png.extend_from_slice(kind);
+    png.extend_from_slice(body);
+    let crc = crc32(&png[start..]);
+    png.extend_from_slice(&crc.to_be_bytes());
+}
+
+fn crc32(data: &[u8]) -> u32 {
+    let mut crc = 0xFFFF_FFFFu32;
+    for byte in data {
+        crc ^= *byte as u32;
+        for _ in 0..8 {
+            crc = if crc & 1 != 0 {
+                0xEDB8_8320 ^ (crc >> 1)
+            } else {
+                crc >> 1
+            };
+        }
+    }
+    !crc
+}
+
+fn adler32(data: &[u8]) -> u32 {
+    let (mut a, mut b) = (1u32, 0u32);
+    for byte in data {
+        a = (a + *byte as u32) % 65521;
+        b = (b + a) % 65521;
+    }
+    (b << 16) | a
+}
+
+fn zlib_stored(data: &[u8]) -> Vec<u8> {
+    let mut out = vec![0x78, 0x01];
+    let mut blocks = data.chunks(MAX_STORED).peekable();
+    if blocks.peek().is_none() {
+        out.extend_from_slice(&[1, 0, 0, 0xFF, 0xFF]);
+    }
+    while let Some(block) = blocks.next() {
+        out.push(blocks.peek().is_none() as u8);
+        let len = block.len() as u16;
+        out.extend_from_slice(&len.to_le_bytes());
+        out.extend_from_slice(&(!len).to_le_bytes());
+        out.extend_from_slice(block);
+    }
+    out.extend_from_slice(&adler32(data).to_be_bytes());
+    out
+}
+
+fn unfilter(filter: u8, cur: &mut [u8], prev: &[u8], bpp: usize) -> Result<()> {
+    for i in 0..cur.len() {
+        let a = if i >= bpp { cur[i - bpp] } else { 0 };
+        let b = prev[i];
+        let c = if i >= bpp { prev[i - bpp] } else { 0 };
+        cur[i] = cur[i].wrapping_add(match filter {
+            0 => 0,
+            1 => a,
+            2 => b,
+            3 => ((a as u16 + b as u16) / 2) as u8,
+            4 => paeth(a, b, c),
+            _ => return Err(invalid("unknown filter type")),
+        });
+    }
+    Ok(())
+}
+
+fn paeth(a: u8, b: u8, c: u8) -> u8 {
+    let p = a as i16 + b as i16 - c as i16;
+    let (pa, pb, pc) = (
+        (p - a as i16).abs(),
+        (p - b as i16).abs(),
+        (p - c as i16).abs(),
+    );
+    if pa <= pb && pa <= pc {
+        a
+    } else if pb <= pc {
+        b
+    } else {
+        c
+    }
+}
+
+struct Bits<'a> {
+    data: &'a [u8],
+    pos: usize,
+}
+
+impl Bits<'_> {
+    fn bit(&mut self) -> Result<u32> {
+        let byte = self
+            .data
+            .get(self.pos / 8)
+            .ok_or_else(|| invalid("truncated deflate stream"))?;
+        let bit = (byte >> (self.pos % 8)) & 1;
+        self.pos += 1;
+        Ok(bit as u32)
+    }
+
+    fn bits(&mut self, n: u32) -> Result<u32> {
+        let mut value = 0;
+        for i in 0..n {
+            value |= self.bit()? << i;
+        }
+        Ok(value)
+    }
+}
+
+/// Canonical Huffman code, as counts of codes per length and symbols sorted
+/// by code.
+struct Huffman {
+    counts: [u16; 16],
+    symbols: Vec<u16>,
+}
+
+impl Huffman {
+    fn new(lengths: &[u8]) -> Huffman {
+        let mut counts = [0u16; 16];
+        for len in lengths {
+            counts[*len as usize] += 1;
+        }
+        counts[0] = 0;
+
+        let mut offsets = [0u16; 16];
+        for len in 1..16 {
+            offsets[len] = offsets[len - 1] + counts[len - 1];
+        }
+        let mut symbols = vec![0u16; lengths.len()];
+        for (symbol, len) in lengths.iter().enumerate() {
+            if *len != 0 {
+                symbols[offsets[*len as usize] as usize] = symbol as u16;
+                offsets[*len as usize] += 1;
+            }
+        }
+        Huffman { counts, symbols }
+    }
+
+    fn decode(&self, bits: &mut Bits) -> Result<u16> {
+        let (mut code, mut first, mut index) = (0i32, 0i32, 0i32);
+        for len in 1..16 {
+            code |= bits.bit()? as i32;
+            let count = self.counts[len] as i32;
+            if code - count < first {
+                return Ok(self.symbols[(index + code - first) as usize]);
+            }
+            index += count;
+            first += count;
+            first <<= 1;
+            code <<= 1;
+        }
+        Err(invalid("invalid Huffman code"))
+    }
+}
+
+const LENGTH_BASE: [u16; 29] = [
+    3, 4, 5, 6, 7, 8, 9, 10, 11, 13, 15, 17, 19, 23, 27, 31, 35, 43, 51, 59, 67, 83, 99, 115, 131,
+    163, 195, 227, 258,
+];
+const LENGTH_EXTRA: [u8; 29] = [
+    0, 0, 0, 0, 0, 0, 0, 0, 1, 1, 1, 1, 2, 2, 2, 2, 3, 3, 3, 3, 4, 4, 4, 4, 5, 5, 5, 5, 0,
+];
+const DIST_BASE: [u16; 30] = [
+    1, 2, 3, 4, 5, 7, 9, 13, 17, 25, 33, 49, 65, 97, 129, 193, 257, 385, 513, 769, 1025, 1537,
+    2049, 3073, 4097, 6145, 8193, 12289, 16385, 24577,
+];
+const DIST_EXTRA: [u8; 30] = [
+    0, 0, 0, 0, 1, 1, 2, 2, 3, 3, 4, 4, 5, 5, 6, 6, 7, 7, 8, 8, 9, 9, 10, 10, 11, 11, 12, 12, 13,
+    13,
+];
+/// Order the code length code lengths are stored in.
+const CL_ORDER: [usize; 19] = [
+    16, 17, 18, 0, 8, 7, 9, 6, 10, 5, 11, 4, 12, 3, 13, 2, 14, 1, 15,
+];
+
+/// Decompresses a raw deflate stream.
+fn inflate(data: &[u8]) -> Result<Vec<u8>> {
+    let mut bits = Bits { data, pos: 0 };
+    let mut out = Vec::new();
+
+    loop {
+        let last = bits.bit()?;
+        match bits.bits(2)? {
+            0 => {
+                bits.pos = bits.pos.div_ceil(8) * 8;
+                let start = bits.pos / 8;
+                let header = data
+                    .get(start..start + 4)
+                    .ok_or_else(|| invalid("truncated stored block"))?;
+                let len = u16::from_le_bytes([header[0], header[1]]) as usize;
+                let block = data
+                    .get(start + 4..start + 4 + len)
+                    .ok_or_else(|| invalid("truncated stored block"))?;
+                out.extend_from_slice(block);
+                bits.pos = (start + 4 + len) * 8;
+            }
+            1 => {
+                let mut lengths = [0u8; 288];
+                lengths[..144].fill(8);
+                lengths[144..256].fill(9);
+                lengths[256..280].fill(7);
+                lengths[280..].fill(8);
+                let lit = Huffman::new(&lengths);
+                let dist = Huffman::new(&[5; 30]);
+                inflate_block(&mut bits, &mut out, &lit, &dist)?;
+            }
+            2 => {
+                let hlit = bits.bits(5)? as usize + 257;
+                let hdist = bits.bits(5)? as usize + 1;
+                let hclen = bits.bits(4)? as usize + 4;
+
+                let mut cl_lengths = [0u8; 19];
+                for i in CL_ORDER.iter().take(hclen) {
+                    cl_lengths[*i] = bits.bits(3)? as u8;
+                }
+                let cl = Huffman::new(&cl_lengths);
+
+                let mut lengths = Vec::with_capacity(hlit + hdist);
+                while lengths.len() < hlit + hdist {
+                    let (len, repeat) = match cl.decode(&mut bits)? {
+                        sym @ 0..=15 => (sym as u8, 1),
+                        16 => {
+                            let prev = *lengths
+                                .last()
+                                .ok_or_else(|| invalid("repeat without a length"))?;
+                            (prev, 3 + bits.bits(2)?)
+                        }
+                        17 => (0, 3 + bits.bits(3)?),
+                        _ => (0, 11 + bits.bits(7)?),
+                    };
+                    lengths.extend(std::iter::repeat_n(len, repeat as usize));
+                }
+                if lengths.len() > hlit + hdist {
+                    return Err(invalid("too many code lengths"));
+                }
+
+                let lit = Huffman::new(&lengths[..hlit]);
+                let dist = Huffman::new(&lengths[hlit..]);
+                inflate_block(&mut bits, &mut out, &lit, &dist)?;
+            }
+            _ => return Err(invalid("invalid deflate block type")),
+        }
+
+        if last == 1 {
+            return Ok(out);
+        }
+    }
+}
+
+fn inflate_block(bits: &mut Bits, out: &mut Vec<u8>, lit: &Huffman, dist: &Huffman) -> Result<()> {
+    loop {
+        let sym = lit.decode(bits)? as usize;
+        match sym {
+            0..=255 => out.push(sym as u8),
+            256 => return Ok(()),
+            _ => {
+                let i = sym - 257;
+                if i >= LENGTH_BASE.len() {
+                    return Err(invalid("invalid length symbol"));
+                }
+                let len = LENGTH_BASE[i] as usize + bits.bits(LENGTH_EXTRA[i] as u32)? as usize;
+
+                let d = dist.decode(bits)? as usize;
+                if d >= DIST_BASE.len() {
+                    return Err(invalid("invalid distance symbol"));
+                }
+                let distance = DIST_BASE[d] as usize + bits.bits(DIST_EXTRA[d] as u32)? as usize;
+                if distance > out.len() {
+                    return Err(invalid("distance too far back"));
+                }
+
+                let start = out.len() - distance;
+                for k in 0..len {
+                    out.push(out[start + k]);
+                }
+            }
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// 16 by 16 grayscale ramp, compressed with fixed Huffman codes.
+    const FIXED: [u8; 29] = [
+        0x78, 0xda, 0x63, 0x60, 0x10, 0x50, 0x30, 0x70, 0x08, 0x48, 0x28, 0x68, 0x98, 0xb0, 0x60,
+        0xc3, 0x81, 0x0b, 0x0f, 0x3e, 0x30, 0x8c, 0x6c, 0x01, 0x00, 0x70, 0xb2, 0x78, 0x01,
+    ];
+
+    /// 8 by 8 RGB pattern, compressed with dynamic Huffman codes.
+    const DYNAMIC: [u8; 127] = [
+        0x78, 0xda, 0x1d, 0x8e, 0x41, 0x0d, 0x00, 0x41, 0x0c, 0x02, 0x91, 0x50, 0x09, 0x48, 0xa8,
+        0x04, 0x24, 0x54, 0x02, 0x12, 0x90, 0xb0, 0x52, 0x56, 0xca, 0x4a, 0xbb, 0xe6, 0x5e, 0x4d,
+        0x1a, 0x18, 0x06, 0x00, 0x0a, 0x20, 0xd0, 0x80, 0x80, 0x01, 0x0c, 0x04, 0xff, 0xbb, 0x8a,
+        0x2c, 0x75, 0x59, 0x75, 0xa6, 0xae, 0xeb, 0x65, 0xb3, 0x1b, 0x2e, 0x8a, 0x3c, 0xcd, 0xb7,
+        0x67, 0x68, 0xf3, 0x66, 0x09, 0x8b, 0xa8, 0x36, 0xfb, 0x75, 0x4b, 0x7d, 0xa7, 0xe9, 0x3e,
+        0x59, 0xee, 0x82, 0x4b, 0x67, 0x3b, 0xad, 0x2b, 0x69, 0xf4, 0x2c, 0x67, 0xd7, 0x76, 0xae,
+        0xe6, 0x72, 0xdc, 0x43, 0xcd, 0x9b, 0x39, 0x1e, 0x65, 0x1d, 0x56, 0xa2, 0xfc, 0xe8, 0xdb,
+        0x3e, 0xb2, 0x67, 0xf3, 0x66, 0xd6, 0x6c, 0xd5, 0x2a, 0x60, 0xd0, 0x81, 0x82, 0x09, 0x1c,
+        0x24, 0xf8, 0x00, 0xca, 0xda, 0x2b, 0xc1,
+    ];
+
+    /// PNG of `width` by `height` 8 bit pixels of `color` type with `zlib`
+    /// as its image data.
+    fn png(width: u32, height: u32, color: u8, zlib: &[u8]) -> Vec<u8> {
+        let mut ihdr = Vec::new();
+        ihdr.extend_from_slice(&width.to_be_bytes());
+        ihdr.extend_from_slice(&height.to_be_bytes());
+        ihdr.extend_from_slice(&[8, color, 0, 0, 0]);
+
+        let mut png = SIGNATURE.to_vec();
+        chunk(&mut png, b"IHDR", &ihdr);
+        chunk(&mut png, b"IDAT", zlib);
+        chunk(&mut png, b"IEND", &[]);
+        png
+    }
+
+    /// Type of the first deflate block of `zlib`.
+    fn block_type(zlib: &[u8]) -> u8 {
+        (zlib[2] >> 1) & 3
+    }
+
+    #[test]
+    fn fixed_huffman() {
+        assert_eq!(block_type(&FIXED), 1);
+        let (width, height, pixels) = decode(&png(16, 16, 0, &FIXED)).unwrap();
+        assert_eq!((width, height), (16, 16));
+        for (i, px) in pixels.chunks(4).enumerate() {
+            let v = (i % 16 * 16) as u8;
+            assert_eq!(px, [v, v, v, 255], "pixel {i}");
+        }
+    }
+
+    #[test]
+    fn dynamic_huffman() {
+        assert_eq!(block_type(&DYNAMIC), 2);
+        let (width, height, pixels) = decode(&png(8, 8, 2, &DYNAMIC)).unwrap();
+        assert_eq!((width, height), (8, 8));
+        for (i, px) in pixels.chunks(4).enumerate() {
+            let (x, y) = (i % 8, i / 8);
+            let expected = [(x * 16) as u8, (y * 16) as u8, (x * y % 7 * 32) as u8, 255];
+            assert_eq!(px, expected, "pixel {i}");
+        }
+    }
+
+    #[test]
+    fn truncated_huffman() {
+        let zlib = &DYNAMIC[..DYNAMIC.len() / 2];
+        assert!(decode(&png(8, 8, 2, zlib)).is_err());
+    }
+}
@@ -0,0 +1,70 @@
+use dagt_core::{Constraints, Draw};
+use dagt_primitives::{circle::Circle, color::Color, rect::Rect};
+use dagt_testing::{Renderer, Snapshot};
+use std::path::{Path, PathBuf};
+
+fn golden(name: &str) -> PathBuf {
+    Path::new(env!("CARGO_MANIFEST_DIR"))
+        .join("goldens")
+        .join(format!("{name}.png"))
+}
+
+/// Badge with its lengths multiplied by `.0`.
+struct Badge(i32);
+
+impl Draw for Badge {
+    fn draw(&mut self, constraints: Constraints) -> bool {
+        let k = self.0;
+        Rect {
+            bg_color: Color::rgb(30, 30, 30),
+            bd_color: Color::rgb(46, 46, 46),
+            bd_width: 2 * k,
+            bd_radius: 10 * k,
+        }
+        .draw(constraints);
+        Circle {
+            bg_color: Color::rgb(200, 80, 60),
+            bd_color: Color::rgb(240, 240, 240),
+            bd_width: 2 * k,
+        }
+        .draw(Constraints {
+            x: constraints.x + 16 * k,
+            y: constraints.y + 16 * k,
+            width: constraints.width - 32 * k,
+            height: constraints.height - 32 * k,
+            ..constraints
+        });
+        true
+    }
+}
+
+#[test]
+fn software_badge() {
+    Snapshot::new(64, 64)
+        .out_dir(env!("CARGO_TARGET_TMPDIR"))
+        .assert_matches(&mut Badge(1), golden("badge"));
+}
+
+#[test]
+fn software_scale() {
+    let scaled = Snapshot::new(64, 64)
+        .scale(2.0)
+        .render(&mut Badge(1))
+        .unwrap();
+    let physical = Snapshot::new(128, 128).render(&mut Badge(2)).unwrap();
+    assert_eq!(scaled, physical);
+}
+
+/// Needs an EGL driver, run with `cargo test -- --ignored`. Compares with
+/// the software golden, bless it from `software_badge` only.
+#[test]
+#[ignore]
+fn egl_badge() {
+    // GPUs antialias edges slightly differently from the software backend
+    Snapshot::new(64, 64)
+        .renderer(Renderer::Egl)
+        .tolerance(8)
+        .max_diff_pixels(64)
+        .out_dir(env!("CARGO_TARGET_TMPDIR"))
+        .assert_matches(&mut Badge(1), golden("badge"));
+}
@@ -1,6 +1,10 @@
 use crate::Text;
 use dagt_core::{Constraints, Draw, EventHandler, StateChanged, Widget, WidgetBuilder};
-use dagt_platform::desktop::event::{GlobalEvent, PointerEvent};
+use dagt_gl::gles::{debug::GlDebugGroup, profiler};
+use dagt_platform::desktop::{
+    cursor::{self, CursorIcon},
+    event::{GlobalEvent, PointerEvent},
+};
 use dagt_primitives::{color::Color, rect::Rect};
 use std::sync::Arc;
 
@@ -77,9 +81,13 @@ impl Widget<GlobalEvent> for Button {
 impl EventHandler<GlobalEvent> for Button {
     fn handle_event(&mut self, event: &GlobalEvent) -> bool {
         use GlobalEvent::*;
+        if let Pointer { .. } = event {
+            cursor::set(CursorIcon::Pointer);
+        }
+
         match event {
             Pointer {
-                e: PointerEvent::ButtonClicked(_),
+                e: PointerEvent::ButtonClicked { .. },
                 x: _,
                 y: _,
             } => {
@@ -93,6 +101,8 @@ impl EventHandler<GlobalEvent> for Button {
 
 impl Draw for Button {
     fn draw(&mut self, constraints: Constraints) -> bool {
+        let _group = GlDebugGroup::push("Button");
+        let _scope = profiler::scope("Button");
         Rect {
             bg_color: self.color_def,
             bd_radius: 30,
@@ -1,4 +1,5 @@
 use dagt_core::{Constraints, Draw, EventHandler, StateChanged, Widget, WidgetBuilder};
+use dagt_gl::gles::{debug::GlDebugGroup, profiler};
 use dagt_platform::desktop::event::GlobalEvent;
 
 #[derive(Default)]
@@ -102,7 +103,7 @@ impl EventHandler<GlobalEvent> for Center {
     fn handle_event(&mut self, event: &GlobalEvent) -> bool {
         use GlobalEvent::*;
         match event {
-            Pointer { e: _, x, y } => self
+            Pointer { e: _, x, y } | Touch { x, y, .. } | Drag { e: _, x, y } => self
                 .on_widget(*x, *y)
                 .map_or(false, |w| w.handle_event(event)),
             _ => false,
@@ -112,6 +113,8 @@ impl EventHandler<GlobalEvent> for Center {
 
 impl Draw for Center {
     fn draw(&mut self, constraints: Constraints) -> bool {
+        let _group = GlDebugGroup::push("Center");
+        let _scope = profiler::scope("Center");
         self.constraints = Some(constraints);
 
         self.child.as_mut().map_or(false, |child| {
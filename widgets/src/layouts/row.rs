@@ -1,4 +1,5 @@
 use dagt_core::{Constraints, Draw, EventHandler, StateChanged, Widget, WidgetBuilder};
+use dagt_gl::gles::{debug::GlDebugGroup, profiler};
 use dagt_platform::desktop::event::GlobalEvent;
 use dagt_primitives::{color::Color, rect::Rect};
 use std::sync::Arc;
@@ -146,7 +147,7 @@ impl EventHandler<GlobalEvent> for Row {
     fn handle_event(&mut self, event: &GlobalEvent) -> bool {
         use GlobalEvent::*;
         match event {
-            Pointer { e: _, x, y } => self
+            Pointer { e: _, x, y } | Touch { x, y, .. } | Drag { e: _, x, y } => self
                 .on_widget(*x, *y)
                 .map_or(false, |w| w.handle_event(event)),
             _ => false,
@@ -156,6 +157,8 @@ impl EventHandler<GlobalEvent> for Row {
 
 impl Draw for Row {
     fn draw(&mut self, constraints: Constraints) -> bool {
+        let _group = GlDebugGroup::push("Row");
+        let _scope = profiler::scope("Row");
         self.constraints = Some(constraints);
 
         let x = constraints.x;
@@ -1,7 +1,12 @@
 use dagt_core::{Constraints, Draw, EventHandler, StateChanged, Widget, WidgetBuilder};
-use dagt_fonts::Font;
-use dagt_platform::desktop::event::GlobalEvent;
-use dagt_primitives::glyph::Glyph;
+use dagt_fonts::{Font, DEFAULT_FONT};
+use dagt_gl::gles::{debug::GlDebugGroup, profiler};
+use dagt_platform::desktop::{
+    cursor::{self, CursorIcon},
+    event::GlobalEvent,
+};
+use dagt_primitives::{backend, glyph::Glyph};
+use std::collections::HashMap;
 
 #[derive(Clone)]
 pub struct Text {
@@ -17,11 +22,13 @@ pub struct Text {
     word_spacing_em: f64,
     line_spacing_em: f64,
     scale: f64,
+    /// Glyphs drawn so far, keeping what the backend made of them.
+    glyphs: HashMap<char, Glyph>,
 }
 
 impl Text {
     pub fn new() -> Text {
-        let font = Font::load("/usr/share/fonts/TTF/JetBrainsMonoNerdFontMono-Bold.ttf");
+        let font = Font::load(DEFAULT_FONT);
         let font_size = 26.0; // pt
         let line_height = font_size * 96.0 / 72.0;
 
@@ -46,6 +53,7 @@ impl Text {
             word_spacing_em,
             line_spacing_em,
             scale,
+            glyphs: HashMap::new(),
         }
     }
 
@@ -128,12 +136,25 @@ impl Widget<GlobalEvent> for Text {
     }
 }
 
-impl EventHandler<GlobalEvent> for Text {}
+impl EventHandler<GlobalEvent> for Text {
+    fn handle_event(&mut self, event: &GlobalEvent) -> bool {
+        if let GlobalEvent::Pointer { .. } = event {
+            cursor::set(CursorIcon::Text);
+        }
+        false
+    }
+}
 
 impl Draw for Text {
     fn draw(&mut self, constraints: Constraints) -> bool {
+        let _group = GlDebugGroup::push("Text");
+        let _scope = profiler::scope("Text");
         let scale = self.scale;
-        let line_height = self.line_height;
+        // laid out in physical pixels, glyphs start on whole pixels at any
+        // scale
+        let pixel_scale = backend::scale();
+        let line_height = self.line_height * pixel_scale;
+        let origin = constraints.to_physical(pixel_scale);
 
         let mut letter_advance = 0.0;
         let mut word_advance = 0.0;
@@ -150,7 +171,7 @@ impl Draw for Text {
                     letter_advance = 0.0;
                 }
                 _ => {
-                    let glyph = self.font.get_glyph(c).clone();
+                    let glyph = self.font.get_glyph(c);
 
                     let offset_x = glyph.min_x as f64 * scale;
                     let offset_y = glyph.min_y as f64 * scale;
@@ -173,8 +194,8 @@ impl Draw for Text {
                     let constr = Constraints {
                         height: (line_height * height) as i32,
                         width: (line_height * width) as i32,
-                        x: (line_height * x_advance) as i32 + constraints.x,
-                        y: (line_height * y_advance) as i32 + constraints.y,
+                        x: (line_height * x_advance) as i32 + origin.x,
+                        y: (line_height * y_advance) as i32 + origin.y,
                         ..Default::default()
                     };
 
@@ -191,7 +212,11 @@ impl Draw for Text {
                     // }
                     // .draw(constr);
 
-                    Glyph::new(glyph).draw(constr);
+                    let glyph = self
+                        .glyphs
+                        .entry(c)
+                        .or_insert_with(|| Glyph::new(glyph.clone()));
+                    backend::with(|b| b.glyph(glyph, constr));
                 }
             }
         }